@@ -22,6 +22,7 @@
 use bland::{Figure, Marker, PaperSize};
 use cepheid::entities::lightcurve::Lightcurve;
 use cepheid::entities::observation::Observation;
+use cepheid::functions::geometry::Site;
 use cepheid::assess_periodicity;
 use cepheid::entities::assessment::{PeriodSearchConfig, PeriodicityDecision};
 use cepheid::functions::periodicity::{
@@ -85,6 +86,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[allow(clippy::collapsible_if)]
 async fn analyze(
     client: &lemonaid::Client,
     http: &reqwest::Client,
@@ -404,10 +406,8 @@ impl Sgp4Propagator {
             .ok()?;
         let sat_teme_km = pred.position; // [x, y, z] in km
 
-        // Convert sensor geodetic → ECEF → TEME (about Z by GMST).
-        let sensor_ecef_km = geodetic_to_ecef_km(sensor_lat_deg, sensor_lon_deg, sensor_alt_km);
-        let gmst = gmst_rad(when);
-        let sensor_teme_km = rotate_z(sensor_ecef_km, gmst);
+        // Sensor geodetic → ECEF → TEME (about Z by GMST).
+        let sensor_teme_km = Site::new(sensor_lat_deg, sensor_lon_deg, sensor_alt_km).eci_km(when);
 
         let dx = sat_teme_km[0] - sensor_teme_km[0];
         let dy = sat_teme_km[1] - sensor_teme_km[1];
@@ -416,47 +416,6 @@ impl Sgp4Propagator {
     }
 }
 
-fn geodetic_to_ecef_km(lat_deg: f64, lon_deg: f64, alt_km: f64) -> [f64; 3] {
-    // WGS-84 ellipsoid.
-    const A_KM: f64 = 6378.137;
-    const F: f64 = 1.0 / 298.257_223_563;
-    let e2 = 2.0 * F - F * F;
-    let lat = lat_deg.to_radians();
-    let lon = lon_deg.to_radians();
-    let sin_lat = lat.sin();
-    let n = A_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
-    [
-        (n + alt_km) * lat.cos() * lon.cos(),
-        (n + alt_km) * lat.cos() * lon.sin(),
-        (n * (1.0 - e2) + alt_km) * sin_lat,
-    ]
-}
-
-fn gmst_rad(when: DateTime<Utc>) -> f64 {
-    // Vallado §3.5.1: GMST in seconds, then to radians. Skipping nutation/
-    // precession is fine for kilometer-level range — magnitude normalization
-    // depends on log10(range), so even a few-arcsecond pointing error is
-    // millimagnitude-level in the corrected vmag.
-    let jd_ut1 = unix_to_jd(when.timestamp_millis() as f64 / 1000.0);
-    let t = (jd_ut1 - 2_451_545.0) / 36_525.0;
-    let gmst_sec = 67_310.548_41
-        + (876_600.0 * 3600.0 + 8_640_184.812_866) * t
-        + 0.093_104 * t * t
-        - 6.2e-6 * t * t * t;
-    let gmst_sec = gmst_sec.rem_euclid(86_400.0);
-    gmst_sec * std::f64::consts::TAU / 86_400.0
-}
-
-fn unix_to_jd(unix_seconds: f64) -> f64 {
-    2_440_587.5 + unix_seconds / 86_400.0
-}
-
-fn rotate_z(v: [f64; 3], theta: f64) -> [f64; 3] {
-    let c = theta.cos();
-    let s = theta.sin();
-    [v[0] * c - v[1] * s, v[0] * s + v[1] * c, v[2]]
-}
-
 async fn lookup_satellite_uuid(
     client: &lemonaid::Client,
     norad: i64,
@@ -22,6 +22,7 @@ use chrono::{DateTime, Utc};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 
+#[allow(clippy::collapsible_if)]
 fn main() {
     let true_period = 47.3_f64;
     let amplitude = 1.5;
@@ -50,6 +51,9 @@ fn main() {
                 phase_rad: 0.0,
                 std_magnitude: mag,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             }
         })
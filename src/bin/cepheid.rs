@@ -0,0 +1,888 @@
+//! `cepheid` command-line tool (feature `cli`).
+//!
+//! Reads CSV / TSV, FITS binary tables, versioned JSON series and (with the
+//! `arrow` feature) Parquet series tables, and runs the library's
+//! periodicity assessment on each series found.
+
+use cepheid::entities::assessment::{
+    DetrendMode, FapMode, MethodId, PeriodSearchConfig, PeriodicityAssessment, SamplingDiagnostics,
+    SearchScale,
+};
+use cepheid::entities::schema::{self, Versioned};
+use cepheid::entities::series::{Series, SigmaSpec, TimeFrame};
+use cepheid::functions::periodicity::{assess_periodicity, assess_sampling, fold_phase};
+use cepheid::functions::time_scale::TimeScale;
+use cepheid::io::delimited::{
+    AngleUnit, ColumnRef, DelimitedConfig, TimeFormat, read_delimited_file,
+};
+use cepheid::io::fits::{FitsConfig, TimeColumn, read_fits_file};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rayon::prelude::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "cepheid",
+    version,
+    about = "Periodicity assessment for lightcurves"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Assess every series in a file.
+    Assess {
+        file: PathBuf,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        search: SearchArgs,
+        /// Print the versioned JSON assessment instead of a summary.
+        #[arg(long)]
+        json: bool,
+        /// Write a review plot per series (`.svg` or `.png`); files holding
+        /// several series get `-1`, `-2`, … before the extension.
+        #[cfg(feature = "plot")]
+        #[arg(long)]
+        plot: Option<PathBuf>,
+        /// Write a self-contained HTML report per series, numbered as for `--plot`.
+        #[cfg(feature = "plot")]
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Print the sampling diagnostics the search would use.
+    Diagnose {
+        file: PathBuf,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        search: SearchArgs,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write phase-folded data (`phase,t_s,y[,sigma]`) at a given period.
+    Fold {
+        file: PathBuf,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Fold period in seconds.
+        #[arg(long)]
+        period: f64,
+        /// Output CSV; standard output when omitted.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Assess many files in parallel; one JSON object per line.
+    Batch {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        search: SearchArgs,
+        /// Worker threads (default: all cores).
+        #[arg(long)]
+        jobs: Option<usize>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Csv,
+    Tsv,
+    Fits,
+    Json,
+    Parquet,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TimeArg {
+    Iso,
+    Unix,
+    Mjd,
+    Jd,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TimeScaleArg {
+    Utc,
+    Tai,
+    Tt,
+    Tdb,
+}
+
+impl From<TimeScaleArg> for TimeScale {
+    fn from(arg: TimeScaleArg) -> Self {
+        match arg {
+            TimeScaleArg::Utc => TimeScale::Utc,
+            TimeScaleArg::Tai => TimeScale::Tai,
+            TimeScaleArg::Tt => TimeScale::Tt,
+            TimeScaleArg::Tdb => TimeScale::Tdb,
+        }
+    }
+}
+
+/// How to read an input file. Column options apply to CSV / TSV and FITS.
+#[derive(Args, Debug, Clone)]
+struct InputArgs {
+    /// Input format; guessed from the extension when omitted.
+    #[arg(long, value_enum)]
+    format: Option<InputFormat>,
+    /// Time column (name, or 0-based index with --no-header).
+    #[arg(long)]
+    time_col: Option<String>,
+    #[arg(long, value_enum, default_value = "iso")]
+    time_format: TimeArg,
+    /// Scale of the CSV / TSV time column, unless the file has a leading
+    /// `# time_scale:` comment (FITS reads TIMESYS).
+    #[arg(long, value_enum, default_value = "utc")]
+    time_scale: TimeScaleArg,
+    /// Magnitude column.
+    #[arg(long)]
+    mag_col: Option<String>,
+    #[arg(long)]
+    sigma_col: Option<String>,
+    #[arg(long)]
+    range_col: Option<String>,
+    /// Range column is in km rather than m.
+    #[arg(long)]
+    range_km: bool,
+    #[arg(long)]
+    phase_col: Option<String>,
+    #[arg(long)]
+    elevation_col: Option<String>,
+    /// Phase and elevation columns are in degrees.
+    #[arg(long)]
+    degrees: bool,
+    #[arg(long)]
+    sensor_col: Option<String>,
+    #[arg(long)]
+    band_col: Option<String>,
+    /// CSV / TSV without a header row; columns are then 0-based indices.
+    #[arg(long)]
+    no_header: bool,
+    /// FITS extension to read (`EXTNAME`).
+    #[arg(long)]
+    extname: Option<String>,
+    /// Retime samples to emission at the target using the range column.
+    #[arg(long)]
+    light_time: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Preset {
+    Conservative,
+    Sensitive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ScaleArg {
+    Auto,
+    Intra,
+    Inter,
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum FapModeArg {
+    Baluev,
+    PermMax,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum DetrendArg {
+    Auto,
+    Linear,
+    Phase,
+    Elevation,
+    None,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum MethodArg {
+    Gls,
+    Pdm,
+    GregoryLoredo,
+    QpGp,
+    StringLength,
+    Multiband,
+    Entropy,
+    LaflerKinman,
+    Bls,
+}
+
+/// [`PeriodSearchConfig`] knobs. Unset options keep the preset's value.
+#[derive(Args, Debug, Clone)]
+struct SearchArgs {
+    #[arg(long, value_enum, default_value = "conservative")]
+    preset: Preset,
+    /// Start from a versioned JSON `PeriodSearchConfig` instead of a preset.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    #[arg(long)]
+    min_period: Option<f64>,
+    #[arg(long)]
+    max_period: Option<f64>,
+    #[arg(long, value_enum)]
+    scale: Option<ScaleArg>,
+    #[arg(long)]
+    fap_threshold: Option<f64>,
+    #[arg(long)]
+    permutations: Option<usize>,
+    #[arg(long, value_enum)]
+    fap_mode: Option<FapModeArg>,
+    #[arg(long)]
+    harmonics: Option<usize>,
+    #[arg(long, value_enum)]
+    detrend: Option<DetrendArg>,
+    /// Comma-separated estimators, primary first.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    methods: Option<Vec<MethodArg>>,
+    #[arg(long)]
+    require_agreement: Option<bool>,
+    #[arg(long)]
+    window_veto: Option<bool>,
+    /// Known physical period in seconds (repeatable).
+    #[arg(long = "known-period")]
+    known_periods: Vec<f64>,
+    #[arg(long)]
+    oversample: Option<f64>,
+    /// Offset PDM bin sets pooled into θ (1 = classic PDM).
+    #[arg(long)]
+    pdm_covers: Option<usize>,
+    /// Phase bins of the conditional-entropy grid.
+    #[arg(long)]
+    entropy_phase_bins: Option<usize>,
+    /// Magnitude bins of the conditional-entropy grid.
+    #[arg(long)]
+    entropy_mag_bins: Option<usize>,
+    /// Widest BLS glint box as a fraction of the period.
+    #[arg(long)]
+    bls_max_duty: Option<f64>,
+    /// Also search a linear period drift up to this |Ṗ/P| per day.
+    #[arg(long)]
+    max_pdot_per_day: Option<f64>,
+    /// Time scale to search in; by default UTC input spanning a leap second
+    /// is searched in TAI.
+    #[arg(long, value_enum)]
+    search_time_scale: Option<TimeScaleArg>,
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+impl SearchArgs {
+    fn to_config(&self) -> Result<PeriodSearchConfig, String> {
+        let mut c = match &self.config {
+            Some(path) => {
+                let text = std::fs::read_to_string(path)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                schema::from_json(&text).map_err(|e| format!("{}: {e}", path.display()))?
+            }
+            None => match self.preset {
+                Preset::Conservative => PeriodSearchConfig::conservative(),
+                Preset::Sensitive => PeriodSearchConfig::sensitive(),
+            },
+        };
+        if self.min_period.is_some() {
+            c.min_period_s = self.min_period;
+        }
+        if self.max_period.is_some() {
+            c.max_period_s = self.max_period;
+        }
+        if let Some(s) = self.scale {
+            c.scale = match s {
+                ScaleArg::Auto => SearchScale::Auto,
+                ScaleArg::Intra => SearchScale::IntraPass,
+                ScaleArg::Inter => SearchScale::InterPass,
+                ScaleArg::Full => SearchScale::Full,
+            };
+        }
+        if let Some(v) = self.fap_threshold {
+            c.fap_threshold = v;
+        }
+        if let Some(v) = self.permutations {
+            c.n_permutations = v;
+        }
+        if let Some(m) = self.fap_mode {
+            c.fap_mode = match m {
+                FapModeArg::Baluev => FapMode::BaluevH1,
+                FapModeArg::PermMax => FapMode::PermMax,
+            };
+        }
+        if self.harmonics.is_some() {
+            c.n_harmonics = self.harmonics;
+        }
+        if let Some(d) = self.detrend {
+            c.detrend = match d {
+                DetrendArg::Auto => DetrendMode::Auto,
+                DetrendArg::Linear => DetrendMode::LinearTime,
+                DetrendArg::Phase => DetrendMode::PhaseFunction,
+                DetrendArg::Elevation => DetrendMode::Elevation,
+                DetrendArg::None => DetrendMode::None,
+            };
+        }
+        if let Some(ms) = &self.methods {
+            c.methods = ms
+                .iter()
+                .map(|m| match m {
+                    MethodArg::Gls => MethodId::Gls,
+                    MethodArg::Pdm => MethodId::Pdm,
+                    MethodArg::GregoryLoredo => MethodId::GregoryLoredo,
+                    MethodArg::QpGp => MethodId::QuasiPeriodicGp,
+                    MethodArg::StringLength => MethodId::StringLength,
+                    MethodArg::Multiband => MethodId::MultibandGls,
+                    MethodArg::Entropy => MethodId::ConditionalEntropy,
+                    MethodArg::LaflerKinman => MethodId::LaflerKinman,
+                    MethodArg::Bls => MethodId::Bls,
+                })
+                .collect();
+        }
+        if let Some(v) = self.require_agreement {
+            c.require_method_agreement = v;
+        }
+        if let Some(v) = self.window_veto {
+            c.window_veto = v;
+        }
+        c.known_periods_s.extend(&self.known_periods);
+        if let Some(s) = self.search_time_scale {
+            c.time_scale = Some(s.into());
+        }
+        if let Some(v) = self.oversample {
+            c.oversample = v;
+        }
+        if let Some(v) = self.pdm_covers {
+            c.pdm.covers = v;
+        }
+        if let Some(v) = self.entropy_phase_bins {
+            c.entropy.phase_bins = v;
+        }
+        if let Some(v) = self.entropy_mag_bins {
+            c.entropy.mag_bins = v;
+        }
+        if let Some(v) = self.bls_max_duty {
+            c.bls.max_duty = v;
+        }
+        if let Some(v) = self.max_pdot_per_day {
+            c.pdot.max_rel_per_day = v;
+        }
+        if let Some(v) = self.seed {
+            c.rng_seed = v;
+        }
+        c.validate().map_err(|e| format!("config: {e}"))?;
+        Ok(c)
+    }
+}
+
+/// A series read from `file`, labelled for output.
+struct Loaded {
+    file: PathBuf,
+    label: String,
+    series: Series,
+}
+
+fn guess_format(path: &Path) -> Result<InputFormat, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "csv" | "txt" => Ok(InputFormat::Csv),
+        "tsv" | "tab" => Ok(InputFormat::Tsv),
+        "fits" | "fit" | "fts" => Ok(InputFormat::Fits),
+        "json" => Ok(InputFormat::Json),
+        "parquet" | "pq" => Ok(InputFormat::Parquet),
+        _ => Err(format!(
+            "{}: cannot tell the format from the extension; pass --format",
+            path.display()
+        )),
+    }
+}
+
+fn column(name: &str, no_header: bool) -> Result<ColumnRef, String> {
+    if no_header {
+        name.parse::<usize>()
+            .map(ColumnRef::Index)
+            .map_err(|_| format!("--no-header needs column indices, got {name:?}"))
+    } else {
+        Ok(ColumnRef::Name(name.to_string()))
+    }
+}
+
+fn delimited_config(input: &InputArgs, fmt: InputFormat) -> Result<DelimitedConfig, String> {
+    let col = |c: &Option<String>| c.as_deref().map(|n| column(n, input.no_header)).transpose();
+    let base = if fmt == InputFormat::Tsv {
+        DelimitedConfig::tsv()
+    } else {
+        DelimitedConfig::csv()
+    };
+    Ok(DelimitedConfig {
+        has_header: !input.no_header,
+        time: col(&input.time_col)?.unwrap_or(base.time.clone()),
+        time_format: match input.time_format {
+            TimeArg::Iso => TimeFormat::Iso8601,
+            TimeArg::Unix => TimeFormat::Unix,
+            TimeArg::Mjd => TimeFormat::Mjd,
+            TimeArg::Jd => TimeFormat::Jd,
+        },
+        time_scale: input.time_scale.into(),
+        vismag: col(&input.mag_col)?.unwrap_or(base.vismag.clone()),
+        sigma: col(&input.sigma_col)?,
+        range: col(&input.range_col)?,
+        range_scale_m: if input.range_km { 1000.0 } else { 1.0 },
+        phase: col(&input.phase_col)?,
+        elevation: col(&input.elevation_col)?,
+        angle_unit: if input.degrees {
+            AngleUnit::Degrees
+        } else {
+            AngleUnit::Radians
+        },
+        sensor: col(&input.sensor_col)?,
+        band: col(&input.band_col)?,
+        ..base
+    })
+}
+
+fn fits_config(input: &InputArgs) -> FitsConfig {
+    let d = FitsConfig::default();
+    FitsConfig {
+        extname: input.extname.clone(),
+        time: match (&input.time_col, input.time_format) {
+            (None, _) => TimeColumn::Auto,
+            (Some(n), TimeArg::Mjd) => TimeColumn::Mjd(n.clone()),
+            (Some(n), TimeArg::Jd) => TimeColumn::Jd(n.clone()),
+            (Some(n), _) => TimeColumn::Relative(n.clone()),
+        },
+        mag: input.mag_col.clone().unwrap_or(d.mag),
+        magerr: input.sigma_col.clone().or(d.magerr),
+        filter: input.band_col.clone().or(d.filter),
+    }
+}
+
+fn load(path: &Path, input: &InputArgs) -> Result<Vec<Loaded>, String> {
+    let fmt = match input.format {
+        Some(f) => f,
+        None => guess_format(path)?,
+    };
+    let err = |e: &dyn std::fmt::Display| format!("{}: {e}", path.display());
+    let series: Vec<Series> = match fmt {
+        InputFormat::Csv | InputFormat::Tsv => {
+            let rows =
+                read_delimited_file(path, &delimited_config(input, fmt)?).map_err(|e| err(&e))?;
+            for e in rows.errors.iter().take(5) {
+                eprintln!("{}:{}: {}", path.display(), e.line, e.message);
+            }
+            if rows.errors.len() > 5 {
+                eprintln!(
+                    "{}: {} more bad rows",
+                    path.display(),
+                    rows.errors.len() - 5
+                );
+            }
+            vec![rows.to_series().map_err(|e| err(&e))?]
+        }
+        InputFormat::Fits => {
+            let p = read_fits_file(path, &fits_config(input)).map_err(|e| err(&e))?;
+            vec![p.to_series().map_err(|e| err(&e))?]
+        }
+        InputFormat::Json => {
+            let text = std::fs::read_to_string(path).map_err(|e| err(&e))?;
+            vec![schema::from_json::<Series>(&text).map_err(|e| err(&e))?]
+        }
+        #[cfg(feature = "arrow")]
+        InputFormat::Parquet => {
+            let f = std::fs::File::open(path).map_err(|e| err(&e))?;
+            cepheid::io::columnar::read_series_parquet(f).map_err(|e| err(&e))?
+        }
+        #[cfg(not(feature = "arrow"))]
+        InputFormat::Parquet => {
+            return Err(err(&"Parquet input needs the `arrow` feature"));
+        }
+    };
+    let series = if input.light_time {
+        series
+            .iter()
+            .map(|s| s.to_time_frame(TimeFrame::Target).map_err(|e| err(&e)))
+            .collect::<Result<_, _>>()?
+    } else {
+        series
+    };
+    let stem = path.display().to_string();
+    let many = series.len() > 1;
+    Ok(series
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| Loaded {
+            file: path.to_path_buf(),
+            label: s.meta().label.clone().unwrap_or_else(|| {
+                if many {
+                    format!("{stem}#{i}")
+                } else {
+                    stem.clone()
+                }
+            }),
+            series: s,
+        })
+        .collect())
+}
+
+fn fmt_opt(v: Option<f64>) -> String {
+    v.map(|x| format!("{x:.3e}")).unwrap_or_else(|| "-".into())
+}
+
+fn print_assessment(label: &str, a: &PeriodicityAssessment) {
+    println!("{label}");
+    println!("  decision     {:?}", a.decision);
+    if let Some(p) = a.period_s {
+        match a.period_unc_s {
+            Some(u) => println!("  period_s     {p:.6} ± {u:.2e}"),
+            None => println!("  period_s     {p:.6}"),
+        }
+    }
+    if let Some(c) = &a.sidereal {
+        println!(
+            "  sidereal     {:.6} ± {:.2e} prograde, {:.6} retrograde ({:?} PAB)",
+            c.period_s, c.period_unc_s, c.retrograde_period_s, c.source
+        );
+    }
+    if let Some(d) = &a.drift {
+        println!(
+            "  pdot         {:.3e} ± {} at t={:.0} s",
+            d.pdot,
+            fmt_opt(d.pdot_unc),
+            d.epoch_s
+        );
+    }
+    if let Some(g) = &a.glint {
+        println!(
+            "  glint        P={:.6} width={:.3} s depth={:.3} at t={:.3} s, {} flash(es)",
+            g.period_s,
+            g.width_s,
+            g.depth,
+            g.epoch_s,
+            g.events.len()
+        );
+    }
+    println!(
+        "  fap          {} (baluev {}, pdm {}, bls {}, perm {}, block {})",
+        fmt_opt(a.fap),
+        fmt_opt(a.fap_baluev),
+        fmt_opt(a.fap_pdm),
+        fmt_opt(a.fap_bls),
+        fmt_opt(a.fap_perm),
+        fmt_opt(a.fap_block)
+    );
+    println!(
+        "  score        {:.4} ({:?}, {:?})",
+        a.score, a.score_kind, a.method
+    );
+    if let Some(c) = &a.confirmation {
+        println!(
+            "  confirmation {:?} P={:.6} score={:.4} {}",
+            c.method,
+            c.period_s,
+            c.score,
+            if c.agrees { "agrees" } else { "disagrees" }
+        );
+    }
+    let q = &a.quality;
+    println!(
+        "  quality      n={} passes={} duty={:.3} window_contaminated={} undersampled={} detrended={}",
+        q.n, q.n_passes, q.duty_cycle, q.window_contaminated, q.undersampled, q.detrended
+    );
+    for al in &a.aliases {
+        println!(
+            "  alias        {:?} P={:.6} score={:.4}{}",
+            al.kind,
+            al.period_s,
+            al.score,
+            if al.vetoed { " (vetoed)" } else { "" }
+        );
+    }
+    for n in &a.notes {
+        println!("  note         {n}");
+    }
+}
+
+fn print_sampling(label: &str, s: &SamplingDiagnostics) {
+    println!("{label}");
+    println!("  n            {} ({} merged)", s.n, s.n_merged_duplicates);
+    println!("  span_s       {:.3}", s.span_s);
+    println!(
+        "  dt_s         min {:.3} median {:.3} p95 {:.3} intra-pass {:.3}",
+        s.min_dt_s, s.median_dt_s, s.p95_dt_s, s.median_intrapass_dt_s
+    );
+    println!("  passes       {} (duty {:.3})", s.n_passes, s.duty_cycle);
+    println!(
+        "  searchable   {:.3} – {:.3} s{}",
+        s.min_searchable_period_s,
+        s.max_searchable_period_s,
+        if s.is_too_sparse() {
+            " (too sparse)"
+        } else {
+            ""
+        }
+    );
+    for w in &s.window_peaks {
+        println!(
+            "  window peak  {:?} P={:.3} power={:.3}",
+            w.kind, w.period_s, w.power
+        );
+    }
+}
+
+fn write_fold(mut w: impl Write, s: &Series, period: f64) -> std::io::Result<()> {
+    let sigma: Option<Vec<f64>> = match s.sigma_spec() {
+        SigmaSpec::Unknown => None,
+        SigmaSpec::Homoscedastic(v) => Some(vec![*v; s.len()]),
+        SigmaSpec::PerPoint(v) => Some(v.clone()),
+    };
+    let mut idx: Vec<usize> = (0..s.len()).collect();
+    let phase: Vec<f64> = s.t_s().iter().map(|&t| fold_phase(t, period)).collect();
+    idx.sort_by(|&a, &b| phase[a].total_cmp(&phase[b]));
+    match sigma {
+        Some(_) => writeln!(w, "phase,t_s,y,sigma")?,
+        None => writeln!(w, "phase,t_s,y")?,
+    }
+    for i in idx {
+        match &sigma {
+            Some(sg) => writeln!(w, "{},{},{},{}", phase[i], s.t_s()[i], s.y()[i], sg[i])?,
+            None => writeln!(w, "{},{},{}", phase[i], s.t_s()[i], s.y()[i])?,
+        }
+    }
+    Ok(())
+}
+
+fn json_line(item: Result<(&Loaded, &PeriodicityAssessment), (&Path, &str)>) -> String {
+    let v = match item {
+        Ok((l, a)) => serde_json::json!({
+            "file": l.file.display().to_string(),
+            "label": l.label,
+            "assessment": Versioned::new(a),
+        }),
+        Err((file, e)) => serde_json::json!({
+            "file": file.display().to_string(),
+            "error": e,
+        }),
+    };
+    v.to_string()
+}
+
+/// `path` with `-{k}` (1-based) before the extension when `n > 1`.
+#[cfg(feature = "plot")]
+fn numbered_path(path: &Path, k: usize, n: usize) -> PathBuf {
+    if n <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{k}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{k}"),
+    };
+    path.with_file_name(name)
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Command::Assess {
+            file,
+            input,
+            search,
+            json,
+            #[cfg(feature = "plot")]
+            plot,
+            #[cfg(feature = "plot")]
+            report,
+        } => {
+            let cfg = search.to_config()?;
+            let loaded = load(&file, &input)?;
+            #[cfg(feature = "plot")]
+            let (n, mut k) = (loaded.len(), 0);
+            for l in loaded {
+                let a = assess_periodicity(&l.series, &cfg);
+                if json {
+                    println!("{}", schema::to_json_pretty(&a).map_err(|e| e.to_string())?);
+                } else {
+                    print_assessment(&l.label, &a);
+                }
+                #[cfg(feature = "plot")]
+                {
+                    k += 1;
+                    if let Some(path) = &plot {
+                        let path = numbered_path(path, k, n);
+                        cepheid::io::plot::write_assessment_plot(&path, &l.series, &a, &cfg)
+                            .map_err(|e| format!("{}: {e}", path.display()))?;
+                    }
+                    if let Some(path) = &report {
+                        let path = numbered_path(path, k, n);
+                        cepheid::io::report::write_assessment_html(&path, &l.series, &a, &cfg)
+                            .map_err(|e| format!("{}: {e}", path.display()))?;
+                    }
+                }
+            }
+        }
+        Command::Diagnose {
+            file,
+            input,
+            search,
+            json,
+        } => {
+            let cfg = search.to_config()?;
+            for l in load(&file, &input)? {
+                let s = assess_sampling(&l.series, &cfg);
+                if json {
+                    println!("{}", schema::to_json_pretty(&s).map_err(|e| e.to_string())?);
+                } else {
+                    print_sampling(&l.label, &s);
+                }
+            }
+        }
+        Command::Fold {
+            file,
+            input,
+            period,
+            output,
+        } => {
+            if !(period > 0.0 && period.is_finite()) {
+                return Err(format!("--period must be positive, got {period}"));
+            }
+            let loaded = load(&file, &input)?;
+            let [l] = loaded.as_slice() else {
+                return Err(format!(
+                    "{}: fold needs exactly one series, found {}",
+                    file.display(),
+                    loaded.len()
+                ));
+            };
+            let res = match &output {
+                Some(p) => std::fs::File::create(p)
+                    .and_then(|f| write_fold(std::io::BufWriter::new(f), &l.series, period)),
+                None => write_fold(std::io::stdout().lock(), &l.series, period),
+            };
+            res.map_err(|e| e.to_string())?;
+        }
+        Command::Batch {
+            files,
+            input,
+            search,
+            jobs,
+        } => {
+            let cfg = search.to_config()?;
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs.unwrap_or(0))
+                .build()
+                .map_err(|e| e.to_string())?;
+            let stdout = std::sync::Mutex::new(std::io::stdout());
+            let emit = |line: String| {
+                let mut out = stdout.lock().expect("stdout lock");
+                let _ = writeln!(out, "{line}");
+            };
+            // Files load inside the pool so parsing overlaps with assessment.
+            pool.install(|| {
+                files.par_iter().for_each(|f| match load(f, &input) {
+                    Ok(loaded) => loaded.par_iter().for_each(|l| {
+                        let a = assess_periodicity(&l.series, &cfg);
+                        emit(json_line(Ok((l, &a))));
+                    }),
+                    Err(e) => emit(json_line(Err((f, &e)))),
+                });
+            });
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("cepheid: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn flags_override_the_preset() {
+        let cli = Cli::parse_from([
+            "cepheid",
+            "assess",
+            "x.csv",
+            "--preset",
+            "sensitive",
+            "--max-period",
+            "600",
+            "--methods",
+            "multiband,pdm",
+            "--known-period",
+            "5400",
+            "--require-agreement",
+            "true",
+            "--max-pdot-per-day",
+            "0.02",
+        ]);
+        let Command::Assess { search, .. } = cli.command else {
+            panic!("parsed {:?}", cli.command);
+        };
+        let c = search.to_config().unwrap();
+        assert_eq!(
+            c.fap_threshold,
+            PeriodSearchConfig::sensitive().fap_threshold
+        );
+        assert_eq!(c.max_period_s, Some(600.0));
+        assert_eq!(c.methods, vec![MethodId::MultibandGls, MethodId::Pdm]);
+        assert_eq!(c.known_periods_s, vec![5400.0]);
+        assert!(c.require_method_agreement);
+        assert_eq!(c.pdot.max_rel_per_day, 0.02);
+
+        let bad = Cli::parse_from(["cepheid", "batch", "a.csv", "--fap-mode", "perm-max"]);
+        let Command::Batch { search, .. } = bad.command else {
+            panic!("parsed {:?}", bad.command);
+        };
+        assert!(search.to_config().unwrap_err().starts_with("config:"));
+    }
+
+    #[test]
+    fn headerless_columns_are_indices() {
+        let cli = Cli::parse_from([
+            "cepheid",
+            "fold",
+            "x.tsv",
+            "--period",
+            "10",
+            "--no-header",
+            "--time-col",
+            "0",
+            "--mag-col",
+            "2",
+            "--time-format",
+            "mjd",
+        ]);
+        let Command::Fold { input, .. } = cli.command else {
+            panic!("parsed {:?}", cli.command);
+        };
+        let d = delimited_config(&input, InputFormat::Tsv).unwrap();
+        assert_eq!(d.delimiter, '\t');
+        assert_eq!(
+            (d.time, d.vismag),
+            (ColumnRef::Index(0), ColumnRef::Index(2))
+        );
+        assert_eq!(d.time_format, TimeFormat::Mjd);
+        assert_eq!(guess_format(Path::new("a/b.FITS")), Ok(InputFormat::Fits));
+    }
+}
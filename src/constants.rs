@@ -1,6 +1,7 @@
 pub const SOLAR_VISMAG: f64 = -26.74; // Solar visual magnitude
 
 /// IAU mean sidereal day (seconds). Used to tag sampling-window aliases.
+#[allow(clippy::inconsistent_digit_grouping)]
 pub const SIDEREAL_DAY_S: f64 = 86_164.0905;
 
 /// Mean solar day (seconds).
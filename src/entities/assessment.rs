@@ -1,7 +1,11 @@
 //! Periodicity assessment types, search config, and sampling diagnostics.
 
+use crate::functions::phase_function::PhaseFunction;
+use crate::functions::time_scale::TimeScale;
+
 /// Three-way product decision.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PeriodicityDecision {
     Periodic,
     NotPeriodic,
@@ -9,25 +13,50 @@ pub enum PeriodicityDecision {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreKind {
     GlsPower,
     PdmTheta,
     LogOdds,
     StringLengthRatio,
+    /// Graham et al. (2013) conditional entropy `H(m | φ)` in nats.
+    ConditionalEntropy,
+    /// Lafler–Kinman θ, normalized to 1 for white noise.
+    LaflerKinmanTheta,
+    /// Fraction of the weighted variance removed by the best single box
+    /// (BLS glint search). Higher is better.
+    BlsPower,
     /// DFT sampling window \(W(f)\). Not a data periodogram.
     SpectralWindow,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MethodId {
     Gls,
     Pdm,
     GregoryLoredo,
     QuasiPeriodicGp,
     StringLength,
+    /// Shared base + per-group terms over `band_key` (or `sensor_key`).
+    /// Replaces single-band GLS as the primary statistic when ≥ 2 groups
+    /// are in scope; otherwise falls back to [`Gls`](MethodId::Gls).
+    MultibandGls,
+    /// Conditional entropy of the folded (phase, magnitude) occupancy.
+    /// Confirms the primary period like PDM; it has no closed-form null, so
+    /// [`PeriodSearchConfig::validate`] rejects it as `methods[0]`.
+    ConditionalEntropy,
+    /// Normalized Lafler–Kinman phase-ordered string length. Confirmation
+    /// only, like [`ConditionalEntropy`](MethodId::ConditionalEntropy).
+    LaflerKinman,
+    /// Box-least-squares search for narrow brightenings (glints). As
+    /// `methods[0]` it decides on its own FAP; elsewhere in `methods` it
+    /// runs after the primary decision and only adds a [`GlintReport`].
+    Bls,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AliasKind {
     Harmonic,
     HalfPeriod,
@@ -39,9 +68,12 @@ pub enum AliasKind {
     PassDuration,
     WindowPeak,
     OtherPeak,
+    /// Power carried only by points in Earth shadow; never reported.
+    EclipseIngress,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SearchScale {
     #[default]
     Auto,
@@ -51,6 +83,7 @@ pub enum SearchScale {
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DetrendMode {
     #[default]
     Auto,
@@ -61,6 +94,7 @@ pub enum DetrendMode {
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FapMode {
     #[default]
     BaluevH1,
@@ -68,18 +102,28 @@ pub enum FapMode {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DetrendColumn {
     PassMean { pass: usize },
     GlobalMean,
     Tau,
     PhaseLambert,
+    PhaseFunction,
     Elevation,
     GlobalTime,
+    Illumination,
+    BandOffset { band: u16 },
+    SensorOffset { key: u16 },
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Periodogram {
     pub period_s: Vec<f64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_vec")
+    )]
     pub score: Vec<f64>,
     pub score_kind: ScoreKind,
 }
@@ -109,6 +153,7 @@ impl Default for Periodogram {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pass {
     pub t_start_s: f64,
     pub t_end_s: f64,
@@ -127,6 +172,7 @@ impl Pass {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowPeak {
     pub period_s: f64,
     pub power: f64,
@@ -134,18 +180,25 @@ pub struct WindowPeak {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SamplingDiagnostics {
     pub n: usize,
     pub n_merged_duplicates: usize,
     pub span_s: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub min_dt_s: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub median_dt_s: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub median_intrapass_dt_s: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub p95_dt_s: f64,
     pub duty_cycle: f64,
     pub n_passes: usize,
     pub passes: Vec<Pass>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub min_searchable_period_s: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub max_searchable_period_s: f64,
     pub spectral_window: Periodogram,
     pub window_peaks: Vec<WindowPeak>,
@@ -179,6 +232,7 @@ impl Default for SamplingDiagnostics {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Alias {
     pub period_s: f64,
     pub score: f64,
@@ -188,6 +242,7 @@ pub struct Alias {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualityFlags {
     pub n: usize,
     pub n_passes: usize,
@@ -200,6 +255,7 @@ pub struct QualityFlags {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Confirmation {
     pub method: MethodId,
     pub period_s: f64,
@@ -207,17 +263,104 @@ pub struct Confirmation {
     pub agrees: bool,
 }
 
+/// How the phase-angle bisector track was obtained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PabSource {
+    /// Target, Sun and site positions.
+    Vectors,
+    /// Solar phase angle only; the bisector rate is a lower bound.
+    PhaseAngle,
+}
+
+/// Synodic → sidereal correction of the detected period; see
+/// [`synodic`](crate::functions::synodic).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SiderealCorrection {
+    pub source: PabSource,
+    /// Mean angular rate of the phase-angle bisector within passes (rad/s).
+    pub pab_rate_rad_s: f64,
+    /// Synodic − sidereal (s) for prograde spin; ≥ 0.
+    pub delta_s: f64,
+    pub delta_unc_s: f64,
+    /// Sidereal period for prograde spin.
+    pub period_s: f64,
+    pub period_unc_s: f64,
+    /// Sidereal period for retrograde spin; ∞ once the bisector turns
+    /// faster than the spin.
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
+    pub retrograde_period_s: f64,
+}
+
+/// Linear period drift from the (P, Ṗ) search; see
+/// [`pdot`](crate::functions::periodicity::pdot). `period_s` on the
+/// assessment is the period at `epoch_s`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeriodDrift {
+    /// Ṗ (s/s); positive for spin-down.
+    pub pdot: f64,
+    pub pdot_unc: Option<f64>,
+    /// Reference time of `period_s` (series seconds).
+    pub epoch_s: f64,
+}
+
+/// One observed flash of a [`GlintReport`]: the in-box points of one
+/// cycle that rise above half the box depth.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlintEvent {
+    /// Excess-weighted centroid time (series seconds).
+    pub t_s: f64,
+    /// Spread of the sample times above half depth, first to last. This is
+    /// not a fitted duration: it is 0 when one sample catches the flash and
+    /// understates the width at coarse cadence. The box estimate is
+    /// [`GlintReport::width_s`].
+    pub width_s: f64,
+    /// Peak excess over the out-of-box level, in `y` units, brighter
+    /// positive.
+    pub amplitude: f64,
+}
+
+/// Best box of the BLS glint search; see
+/// [`bls`](crate::functions::periodicity::bls).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlintReport {
+    pub period_s: f64,
+    /// Mid-box time of the cycle nearest the start of the series.
+    pub epoch_s: f64,
+    /// Box duration, `duty · period_s`.
+    pub width_s: f64,
+    /// Fraction of the cycle inside the box.
+    pub duty: f64,
+    /// In-box minus out-of-box mean, in `y` units, brighter positive.
+    pub depth: f64,
+    pub power: f64,
+    pub events: Vec<GlintEvent>,
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DetrendReport {
     pub mode: DetrendMode,
     pub scale: SearchScale,
     pub columns: Vec<DetrendColumn>,
     pub coeffs: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::entities::schema::nonfinite"))]
     pub cond: f64,
     pub n_beta: usize,
     pub floating_mean_gls: bool,
     pub fallback: bool,
     pub pass_mean: Vec<f64>,
+    /// `(band_key, offset)` in `y` units relative to the reference band
+    /// (offset `0.0`). Empty unless ≥ 2 bands were in scope and at least one
+    /// offset survived the collinearity check.
+    pub band_offsets: Vec<(u16, f64)>,
+    /// `(sensor_key, offset)` relative to the most populous sensor, as for
+    /// `band_offsets`. Empty unless ≥ 2 sensors were in scope.
+    pub sensor_offsets: Vec<(u16, f64)>,
 }
 
 impl Default for DetrendReport {
@@ -232,6 +375,8 @@ impl Default for DetrendReport {
             floating_mean_gls: false,
             fallback: false,
             pass_mean: Vec::new(),
+            band_offsets: Vec::new(),
+            sensor_offsets: Vec::new(),
         }
     }
 }
@@ -239,6 +384,7 @@ impl Default for DetrendReport {
 /// Product result. `non_exhaustive` so later PRs can add fields.
 #[non_exhaustive]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodicityAssessment {
     pub decision: PeriodicityDecision,
     pub period_s: Option<f64>,
@@ -247,6 +393,12 @@ pub struct PeriodicityAssessment {
     pub fap_perm: Option<f64>,
     pub fap_block: Option<f64>,
     pub fap_baluev: Option<f64>,
+    /// Beta-law FAP of the lowest PDM θ when PDM ran.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fap_pdm: Option<f64>,
+    /// FAP of the best BLS box when the glint search ran.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fap_bls: Option<f64>,
     pub score: f64,
     pub score_kind: ScoreKind,
     pub aliases: Vec<Alias>,
@@ -254,6 +406,14 @@ pub struct PeriodicityAssessment {
     pub periodogram: Periodogram,
     pub periodogram_h2: Option<Periodogram>,
     pub confirmation: Option<Confirmation>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sidereal: Option<SiderealCorrection>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub drift: Option<PeriodDrift>,
+    /// Glint period, epoch, width and flashes when the BLS search is
+    /// significant.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub glint: Option<GlintReport>,
     pub sampling: SamplingDiagnostics,
     pub detrend: DetrendReport,
     pub method: MethodId,
@@ -270,6 +430,8 @@ impl PeriodicityAssessment {
             fap_perm: None,
             fap_block: None,
             fap_baluev: None,
+            fap_pdm: None,
+            fap_bls: None,
             score: 0.0,
             score_kind: ScoreKind::GlsPower,
             aliases: Vec::new(),
@@ -277,6 +439,9 @@ impl PeriodicityAssessment {
             periodogram: Periodogram::default(),
             periodogram_h2: None,
             confirmation: None,
+            sidereal: None,
+            drift: None,
+            glint: None,
             sampling: SamplingDiagnostics::default(),
             detrend: DetrendReport::default(),
             method: MethodId::Gls,
@@ -286,20 +451,84 @@ impl PeriodicityAssessment {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlsOptions {}
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PdmOptions {
     pub m_bins: Option<usize>,
+    /// Offset bin sets pooled into θ (Stellingwerf covers); 1 is classic PDM.
+    pub covers: usize,
 }
 
 impl Default for PdmOptions {
     fn default() -> Self {
-        Self { m_bins: None }
+        Self {
+            m_bins: None,
+            covers: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntropyOptions {
+    pub phase_bins: usize,
+    /// Bins over the min–max normalized magnitude.
+    pub mag_bins: usize,
+}
+
+impl Default for EntropyOptions {
+    fn default() -> Self {
+        Self {
+            phase_bins: 10,
+            mag_bins: 5,
+        }
     }
 }
 
+/// Box-least-squares glint search.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlsOptions {
+    /// Phase bins; the narrowest box is one bin.
+    pub n_bins: usize,
+    /// Widest box as a fraction of the period.
+    pub max_duty: f64,
+    /// Fewest points a box must hold, so one outlier is not a glint.
+    pub min_in_box: usize,
+    /// Cap on frequency trials.
+    pub max_trials: usize,
+}
+
+impl Default for BlsOptions {
+    fn default() -> Self {
+        Self {
+            n_bins: 200,
+            max_duty: 0.1,
+            min_in_box: 3,
+            max_trials: 20_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultibandOptions {
+    /// Harmonics in each per-group term. The shared base follows `n_harmonics`.
+    pub n_band: usize,
+}
+
+impl Default for MultibandOptions {
+    fn default() -> Self {
+        Self { n_band: 1 }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlOptions {
     pub bin_range: (usize, usize),
 }
@@ -311,6 +540,7 @@ impl Default for GlOptions {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QpGpOptions {
     pub max_obs: usize,
 }
@@ -321,13 +551,116 @@ impl Default for QpGpOptions {
     }
 }
 
+/// Windows for [`track_periods`](crate::functions::periodicity::track::track_periods).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackOptions {
+    /// Intra-eligible passes per window; 1 gives one epoch per pass.
+    pub passes_per_window: usize,
+    /// Passes the window advances by within a run of eligible passes.
+    pub step_passes: usize,
+    /// Trial periods in the shared grid of the score matrix.
+    pub n_periods: usize,
+}
+
+impl Default for TrackOptions {
+    fn default() -> Self {
+        Self {
+            passes_per_window: 1,
+            step_passes: 1,
+            n_periods: 500,
+        }
+    }
+}
+
+/// One window of a [`PeriodTrack`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackEpoch {
+    /// Indices into `sampling.passes`.
+    pub passes: Vec<usize>,
+    pub t_start_s: f64,
+    pub t_end_s: f64,
+    pub n: usize,
+    pub decision: PeriodicityDecision,
+    pub period_s: Option<f64>,
+    pub period_unc_s: Option<f64>,
+    pub fap: Option<f64>,
+    /// Zero-mean H=1 GLS power on the track's shared period grid, whatever
+    /// the primary method.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_vec")
+    )]
+    pub score: Vec<f64>,
+}
+
+impl TrackEpoch {
+    pub fn t_mid_s(&self) -> f64 {
+        0.5 * (self.t_start_s + self.t_end_s)
+    }
+}
+
+/// Time-resolved periodogram: one row of `score` per epoch over the shared
+/// `period_s` grid, and the per-window decision as the period track.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeriodTrack {
+    pub period_s: Vec<f64>,
+    pub score_kind: ScoreKind,
+    pub epochs: Vec<TrackEpoch>,
+    pub sampling: SamplingDiagnostics,
+    pub notes: Vec<String>,
+}
+
+impl PeriodTrack {
+    /// `(t_mid_s, period_s, period_unc_s)` for epochs with a Periodic decision.
+    pub fn periodic(&self) -> Vec<(f64, f64, Option<f64>)> {
+        self.epochs
+            .iter()
+            .filter(|e| e.decision == PeriodicityDecision::Periodic)
+            .filter_map(|e| e.period_s.map(|p| (e.t_mid_s(), p, e.period_unc_s)))
+            .collect()
+    }
+}
+
+/// (P, Ṗ) search. Off by default. Under [`SearchScale::Auto`] with three or
+/// more passes, a Periodic intra-pass result still gets a Full (P, Ṗ)
+/// search, which replaces it when it fits a drift.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdotOptions {
+    /// Largest |Ṗ/P| searched, per day; 0 disables the search.
+    pub max_rel_per_day: f64,
+    /// Cap on Ṗ trials per candidate period.
+    pub max_trials: usize,
+}
+
+impl Default for PdotOptions {
+    fn default() -> Self {
+        Self {
+            max_rel_per_day: 0.0,
+            max_trials: 401,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ConfigError {
     EmptyMethods,
     ZeroHarmonics,
+    ZeroCovers,
+    EntropyBins,
+    BlsBox,
     Oversample,
     WindowRatio,
-    PermMaxBudget { n_permutations: usize, min: usize },
+    MaxPdot,
+    PermMaxBudget {
+        n_permutations: usize,
+        min: usize,
+    },
+    /// A confirmation-only method listed first.
+    ConfirmerFirst(MethodId),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -335,14 +668,27 @@ impl std::fmt::Display for ConfigError {
         match self {
             ConfigError::EmptyMethods => write!(f, "methods must be non-empty"),
             ConfigError::ZeroHarmonics => write!(f, "n_harmonics must be ≥ 1"),
+            ConfigError::ZeroCovers => write!(f, "pdm.covers must be ≥ 1"),
+            ConfigError::EntropyBins => {
+                write!(f, "entropy.phase_bins and entropy.mag_bins must be ≥ 2")
+            }
+            ConfigError::BlsBox => write!(
+                f,
+                "bls needs n_bins ≥ 2, 0 < max_duty < 0.5 and max_trials ≥ 2"
+            ),
             ConfigError::Oversample => write!(f, "oversample must be ≥ 1"),
             ConfigError::WindowRatio => write!(f, "window_ratio must be > 0"),
+            ConfigError::MaxPdot => write!(f, "pdot.max_rel_per_day must be finite and ≥ 0"),
             ConfigError::PermMaxBudget {
                 n_permutations,
                 min,
             } => write!(
                 f,
-                "PermMax needs n_permutations ≥ {min} to resolve α (got {n_permutations})"
+                "PermMax and multiband GLS need n_permutations ≥ {min} to resolve α (got {n_permutations})"
+            ),
+            ConfigError::ConfirmerFirst(m) => write!(
+                f,
+                "{m:?} only confirms; methods must start with Gls, Pdm, MultibandGls or Bls"
             ),
         }
     }
@@ -351,6 +697,7 @@ impl std::fmt::Display for ConfigError {
 impl std::error::Error for ConfigError {}
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodSearchConfig {
     pub min_period_s: Option<f64>,
     pub max_period_s: Option<f64>,
@@ -360,6 +707,10 @@ pub struct PeriodSearchConfig {
     pub fap_mode: FapMode,
     pub n_harmonics: Option<usize>,
     pub detrend: DetrendMode,
+    /// Model behind the phase detrend column. The default
+    /// [`LambertApprox`](PhaseFunction::LambertApprox) keeps the
+    /// `PhaseLambert` column; any other model is reported as `PhaseFunction`.
+    pub phase_function: PhaseFunction,
     pub methods: Vec<MethodId>,
     pub require_method_agreement: bool,
     pub window_veto: bool,
@@ -371,8 +722,20 @@ pub struct PeriodSearchConfig {
     pub rng_seed: u64,
     pub gls: GlsOptions,
     pub pdm: PdmOptions,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub entropy: EntropyOptions,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bls: BlsOptions,
+    pub multiband: MultibandOptions,
     pub gregory_loredo: GlOptions,
     pub qp_gp: QpGpOptions,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pdot: PdotOptions,
+    /// Scale the search counts time in. `None` keeps the series' own scale,
+    /// except that a UTC series spanning a leap second is searched in TAI.
+    /// Times in the result are reported in the series' scale either way.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_scale: Option<TimeScale>,
 }
 
 impl PeriodSearchConfig {
@@ -386,6 +749,7 @@ impl PeriodSearchConfig {
             fap_mode: FapMode::BaluevH1,
             n_harmonics: None,
             detrend: DetrendMode::Auto,
+            phase_function: PhaseFunction::default(),
             methods: vec![MethodId::Gls, MethodId::Pdm],
             require_method_agreement: true,
             window_veto: true,
@@ -397,8 +761,13 @@ impl PeriodSearchConfig {
             rng_seed: 0x00C0_FFEE,
             gls: GlsOptions::default(),
             pdm: PdmOptions::default(),
+            entropy: EntropyOptions::default(),
+            bls: BlsOptions::default(),
+            multiband: MultibandOptions::default(),
             gregory_loredo: GlOptions::default(),
             qp_gp: QpGpOptions::default(),
+            pdot: PdotOptions::default(),
+            time_scale: None,
         }
     }
 
@@ -410,20 +779,41 @@ impl PeriodSearchConfig {
         c
     }
 
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
     pub fn validate(&self) -> Result<(), ConfigError> {
-        if self.methods.is_empty() {
-            return Err(ConfigError::EmptyMethods);
+        match self.methods.first() {
+            None => return Err(ConfigError::EmptyMethods),
+            Some(&m @ (MethodId::ConditionalEntropy | MethodId::LaflerKinman)) => {
+                return Err(ConfigError::ConfirmerFirst(m));
+            }
+            Some(_) => {}
         }
         if self.n_harmonics == Some(0) {
             return Err(ConfigError::ZeroHarmonics);
         }
+        if self.pdm.covers == 0 {
+            return Err(ConfigError::ZeroCovers);
+        }
+        if self.entropy.phase_bins < 2 || self.entropy.mag_bins < 2 {
+            return Err(ConfigError::EntropyBins);
+        }
+        if self.bls.n_bins < 2
+            || !(self.bls.max_duty > 0.0 && self.bls.max_duty < 0.5)
+            || self.bls.max_trials < 2
+        {
+            return Err(ConfigError::BlsBox);
+        }
         if !(self.oversample >= 1.0) {
             return Err(ConfigError::Oversample);
         }
         if !(self.window_ratio > 0.0) {
             return Err(ConfigError::WindowRatio);
         }
-        if self.fap_mode == FapMode::PermMax {
+        if !(self.pdot.max_rel_per_day >= 0.0 && self.pdot.max_rel_per_day.is_finite()) {
+            return Err(ConfigError::MaxPdot);
+        }
+        // Multiband GLS has no analytic tail and always uses the permutation maximum.
+        if self.fap_mode == FapMode::PermMax || self.methods.contains(&MethodId::MultibandGls) {
             let min = ((1.0 / self.fap_threshold).ceil() as usize).saturating_sub(1);
             if self.n_permutations < min {
                 return Err(ConfigError::PermMaxBudget {
@@ -1,8 +1,12 @@
 use crate::entities::assessment::{PeriodicityAssessment, PeriodicityDecision};
 use crate::entities::observation::Observation;
+use crate::entities::passband::{Passband, PhotometricCalibration};
 use crate::entities::series::{Series, SeriesError};
+use crate::functions::extinction::Extinction;
+use crate::functions::normalization::correct_extinction;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lightcurve {
     pub observations: Vec<Observation>,
     pub is_periodic: Option<bool>, // Optional: indicates if the lightcurve is periodic
@@ -53,6 +57,49 @@ impl Lightcurve {
         obs_by_time.last().unwrap().unix_seconds() - obs_by_time.first().unwrap().unix_seconds()
     }
 
+    /// Distinct passbands present, in order of first appearance. Untagged
+    /// observations are not listed.
+    pub fn bands(&self) -> Vec<Passband> {
+        let mut out: Vec<Passband> = Vec::new();
+        for b in self.observations.iter().filter_map(|o| o.band) {
+            if !out.contains(&b) {
+                out.push(b);
+            }
+        }
+        out
+    }
+
+    /// Shift `vismag` and `std_magnitude` by each observation's band
+    /// zero-point and colour term. Untagged / uncalibrated bands are untouched.
+    pub fn apply_calibration(&mut self, cal: &PhotometricCalibration) {
+        for obs in &mut self.observations {
+            let dm = cal.correction(obs.band);
+            obs.vismag += dm;
+            obs.std_magnitude += dm;
+        }
+    }
+
+    /// Remove `k·X` from `vismag` and `std_magnitude` using each
+    /// observation's band, time and `elevation_rad`. Bands without a
+    /// coefficient are untouched; returns how many observations that have
+    /// one were skipped for want of an elevation above the horizon.
+    pub fn apply_extinction(&mut self, ext: &Extinction) -> usize {
+        let mut n_skipped = 0;
+        for obs in &mut self.observations {
+            let Some(k) = ext.k_for(obs.band, obs.timestamp) else {
+                continue;
+            };
+            let Some(x) = obs.elevation_rad.and_then(|el| ext.model.airmass(el)) else {
+                n_skipped += 1;
+                continue;
+            };
+            let corrected = correct_extinction(obs.vismag, x, k);
+            obs.std_magnitude += corrected - obs.vismag;
+            obs.vismag = corrected;
+        }
+        n_skipped
+    }
+
     pub fn observation_count(&self) -> usize {
         self.observations.len()
     }
@@ -103,6 +150,9 @@ mod tests {
             phase_rad: 0.0,
             std_magnitude: 10.0,
             timestamp: base + chrono::Duration::milliseconds((offset_s * 1000.0) as i64),
+            band: None,
+            elevation_rad: None,
+            illumination: None,
             fractional_period: None,
         }
     }
@@ -135,6 +185,60 @@ mod tests {
         assert!(lc.observations[0].fractional_period.is_none());
     }
 
+    #[test]
+    fn calibration_shifts_only_tagged_bands() {
+        let mut lc = Lightcurve::new(
+            vec![
+                obs_at(0.0).with_band(Passband::JohnsonV),
+                obs_at(1.0).with_band(Passband::SloanR),
+                obs_at(2.0),
+            ],
+            None,
+            None,
+        );
+        assert_eq!(lc.bands(), vec![Passband::JohnsonV, Passband::SloanR]);
+        let mut cal = PhotometricCalibration::default();
+        cal.set(
+            Passband::JohnsonV,
+            crate::entities::passband::BandCalibration {
+                zero_point_mag: 0.3,
+                colour_term: 0.0,
+            },
+        );
+        lc.apply_calibration(&cal);
+        assert!((lc.observations[0].std_magnitude - 10.3).abs() < 1e-12);
+        assert!((lc.observations[0].vismag - 10.3).abs() < 1e-12);
+        assert_eq!(lc.observations[1].std_magnitude, 10.0);
+        assert_eq!(lc.observations[2].std_magnitude, 10.0);
+    }
+
+    #[test]
+    fn extinction_flows_into_std_magnitude() {
+        let mut lc = Lightcurve::new(
+            vec![
+                obs_at(0.0).with_band(Passband::JohnsonV),
+                obs_at(1.0).with_band(Passband::JohnsonV),
+                obs_at(2.0).with_band(Passband::SloanR),
+            ],
+            None,
+            None,
+        );
+        lc.observations[0].elevation_rad = Some(30.0_f64.to_radians());
+        let mut ext = Extinction::default();
+        ext.set(Passband::JohnsonV, 0.2);
+        assert_eq!(
+            lc.apply_extinction(&ext),
+            1,
+            "V without elevation is skipped"
+        );
+        let x = ext.model.airmass(30.0_f64.to_radians()).unwrap();
+        assert!((x - 1.995).abs() < 0.01, "{x}");
+        assert!((lc.observations[0].std_magnitude - (10.0 - 0.2 * x)).abs() < 1e-12);
+        assert!((lc.observations[0].vismag - (10.0 - 0.2 * x)).abs() < 1e-12);
+        assert_eq!(lc.observations[1].std_magnitude, 10.0);
+        assert_eq!(lc.observations[2].std_magnitude, 10.0);
+    }
+
     #[test]
     fn to_series_round_trip_unknown_sigma() {
         let lc = Lightcurve::new(vec![obs_at(0.0), obs_at(10.0)], None, None);
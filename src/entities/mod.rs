@@ -1,5 +1,8 @@
 pub mod assessment;
 pub mod lightcurve;
 pub mod observation;
+pub mod passband;
 pub mod rf;
+#[cfg(feature = "serde")]
+pub mod schema;
 pub mod series;
@@ -1,13 +1,19 @@
+use crate::entities::passband::Passband;
+use crate::functions::geometry::Geometry;
+use crate::functions::time_scale::{SPEED_OF_LIGHT_M_S, TimeScale, from_utc, seconds_to_mjd};
 use chrono::{DateTime, Utc};
 
-
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Observation {
     pub vismag: f64,
     pub range_m: f64,
     pub phase_rad: f64,
     pub std_magnitude: f64,
     pub timestamp: DateTime<Utc>,
+    pub band: Option<Passband>,
+    pub elevation_rad: Option<f64>, // Optional: target elevation at the sensor
+    pub illumination: Option<f64>, // Optional: visible fraction of the solar disc (Earth shadow)
     pub fractional_period: Option<f64> // Optional: phase within a periodic cycle
 }
 
@@ -34,6 +40,9 @@ impl Observation {
             phase_rad,
             std_magnitude,
             timestamp,
+            band: None,
+            elevation_rad: None,
+            illumination: None,
             fractional_period: None, // default to None, compute later if needed
         }
     }
@@ -42,6 +51,21 @@ impl Observation {
         self.timestamp.timestamp_micros() as f64 / 1_000_000.0
     }
 
+    /// Seconds since 1970-01-01 counted in `scale`; see [`time_scale`](crate::functions::time_scale).
+    pub fn time_s(&self, scale: TimeScale) -> f64 {
+        from_utc(self.unix_seconds(), scale)
+    }
+
+    /// Time the light left the target, `time_s(scale) − range_m / c`.
+    pub fn emission_time_s(&self, scale: TimeScale) -> f64 {
+        self.time_s(scale) - self.range_m / SPEED_OF_LIGHT_M_S
+    }
+
+    /// Modified Julian Date in `scale`.
+    pub fn mjd(&self, scale: TimeScale) -> f64 {
+        seconds_to_mjd(self.time_s(scale))
+    }
+
     pub fn new_default_normalization(vismag: f64, range_m: f64, phase_rad: f64, timestamp: DateTime<Utc>) -> Self {
         // new with default std range 1000 km and std phase 90 deg
         let std_magnitude = crate::functions::normalization::normalize_vismag(
@@ -57,7 +81,35 @@ impl Observation {
             phase_rad,
             std_magnitude,
             timestamp,
+            band: None,
+            elevation_rad: None,
+            illumination: None,
             fractional_period: None,
         }
     }
+
+    /// `new` with range and phase taken from a computed [`Geometry`].
+    pub fn from_geometry(
+        vismag: f64,
+        geometry: &Geometry,
+        timestamp: DateTime<Utc>,
+        std_range: f64,
+        std_phase: f64,
+    ) -> Self {
+        let mut obs = Self::new(
+            vismag,
+            geometry.range_m,
+            geometry.phase_rad,
+            timestamp,
+            std_range,
+            std_phase,
+        );
+        obs.elevation_rad = Some(geometry.elevation_rad);
+        obs
+    }
+
+    pub fn with_band(mut self, band: Passband) -> Self {
+        self.band = Some(band);
+        self
+    }
 }
\ No newline at end of file
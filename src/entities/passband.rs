@@ -0,0 +1,176 @@
+//! Photometric passbands and per-band calibration.
+//!
+//! A [`Passband`] travels with each [`Observation`](crate::entities::observation::Observation)
+//! and becomes `Covariates::band_key` on a [`Series`](crate::entities::series::Series).
+//! Key `0` is reserved for untagged points.
+
+/// `band_key` value for observations without a passband tag.
+pub const UNTAGGED_BAND_KEY: u16 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Passband {
+    JohnsonB,
+    JohnsonV,
+    JohnsonR,
+    CousinsI,
+    SloanG,
+    SloanR,
+    SloanI,
+    SloanZ,
+    /// Clear / unfiltered CCD response.
+    Clear,
+}
+
+impl Passband {
+    pub const ALL: [Passband; 9] = [
+        Passband::JohnsonB,
+        Passband::JohnsonV,
+        Passband::JohnsonR,
+        Passband::CousinsI,
+        Passband::SloanG,
+        Passband::SloanR,
+        Passband::SloanI,
+        Passband::SloanZ,
+        Passband::Clear,
+    ];
+
+    /// Stable covariate key (`≥ 1`; `0` is [`UNTAGGED_BAND_KEY`]).
+    pub fn key(self) -> u16 {
+        match self {
+            Passband::JohnsonB => 1,
+            Passband::JohnsonV => 2,
+            Passband::JohnsonR => 3,
+            Passband::CousinsI => 4,
+            Passband::SloanG => 5,
+            Passband::SloanR => 6,
+            Passband::SloanI => 7,
+            Passband::SloanZ => 8,
+            Passband::Clear => 9,
+        }
+    }
+
+    pub fn from_key(key: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|b| b.key() == key)
+    }
+
+    /// Short conventional name (`"V"`, `"g'"`, `"CL"`, …).
+    pub fn name(self) -> &'static str {
+        match self {
+            Passband::JohnsonB => "B",
+            Passband::JohnsonV => "V",
+            Passband::JohnsonR => "R",
+            Passband::CousinsI => "I",
+            Passband::SloanG => "g'",
+            Passband::SloanR => "r'",
+            Passband::SloanI => "i'",
+            Passband::SloanZ => "z'",
+            Passband::Clear => "CL",
+        }
+    }
+
+    /// Lenient parse of filter names as they appear in sensor headers.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let s = name.trim().trim_end_matches('\'');
+        match s {
+            "B" | "Johnson_B" | "JohnsonB" => Some(Passband::JohnsonB),
+            "V" | "Johnson_V" | "JohnsonV" => Some(Passband::JohnsonV),
+            "R" | "Johnson_R" | "JohnsonR" | "Rc" => Some(Passband::JohnsonR),
+            "I" | "Ic" | "Cousins_I" | "CousinsI" => Some(Passband::CousinsI),
+            "g" | "sdss_g" | "SloanG" => Some(Passband::SloanG),
+            "r" | "sdss_r" | "SloanR" => Some(Passband::SloanR),
+            "i" | "sdss_i" | "SloanI" => Some(Passband::SloanI),
+            "z" | "sdss_z" | "SloanZ" => Some(Passband::SloanZ),
+            _ => match s.to_ascii_uppercase().as_str() {
+                "CL" | "C" | "CLEAR" | "L" | "LUM" | "NONE" | "OPEN" | "UNFILTERED" => {
+                    Some(Passband::Clear)
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// First-order transformation to the standard system:
+/// `m_std = m_inst + zero_point_mag + colour_term · CI`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandCalibration {
+    pub zero_point_mag: f64,
+    pub colour_term: f64,
+}
+
+impl BandCalibration {
+    pub fn correction(&self, colour_index: f64) -> f64 {
+        self.zero_point_mag + self.colour_term * colour_index
+    }
+}
+
+/// Per-band calibration table. Bands without an entry are left unchanged.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhotometricCalibration {
+    pub bands: Vec<(Passband, BandCalibration)>,
+    /// Assumed target colour index fed to every colour term. Default 0.
+    pub colour_index: f64,
+}
+
+impl PhotometricCalibration {
+    pub fn get(&self, band: Passband) -> Option<&BandCalibration> {
+        self.bands.iter().find(|(b, _)| *b == band).map(|(_, c)| c)
+    }
+
+    pub fn set(&mut self, band: Passband, cal: BandCalibration) {
+        match self.bands.iter_mut().find(|(b, _)| *b == band) {
+            Some(entry) => entry.1 = cal,
+            None => self.bands.push((band, cal)),
+        }
+    }
+
+    /// Magnitude correction for `band`; `0.0` for untagged or uncalibrated bands.
+    pub fn correction(&self, band: Option<Passband>) -> f64 {
+        band.and_then(|b| self.get(b))
+            .map(|c| c.correction(self.colour_index))
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip_and_skip_untagged() {
+        for b in Passband::ALL {
+            assert_ne!(b.key(), UNTAGGED_BAND_KEY);
+            assert_eq!(Passband::from_key(b.key()), Some(b));
+        }
+        assert_eq!(Passband::from_key(UNTAGGED_BAND_KEY), None);
+    }
+
+    #[test]
+    fn header_names_parse() {
+        assert_eq!(Passband::from_name("V"), Some(Passband::JohnsonV));
+        assert_eq!(Passband::from_name("r'"), Some(Passband::SloanR));
+        assert_eq!(Passband::from_name(" clear "), Some(Passband::Clear));
+        assert_eq!(Passband::from_name("Ha"), None);
+    }
+
+    #[test]
+    fn calibration_applies_zero_point_and_colour_term() {
+        let mut cal = PhotometricCalibration {
+            colour_index: 0.5,
+            ..PhotometricCalibration::default()
+        };
+        cal.set(
+            Passband::JohnsonV,
+            BandCalibration {
+                zero_point_mag: 21.0,
+                colour_term: -0.1,
+            },
+        );
+        assert!((cal.correction(Some(Passband::JohnsonV)) - 20.95).abs() < 1e-12);
+        assert_eq!(cal.correction(Some(Passband::SloanR)), 0.0);
+        assert_eq!(cal.correction(None), 0.0);
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Polarization {
     H,
     V,
@@ -10,6 +11,7 @@ pub enum Polarization {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RfPowerObservation {
     pub timestamp: DateTime<Utc>,
     pub power_dbm: f64,
@@ -24,12 +26,14 @@ pub struct RfPowerObservation {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RfYDomain {
     Decibel,
     Linear,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RfNormConfig {
     pub path_exponent: f64,
     pub ref_range_m: f64,
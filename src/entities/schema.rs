@@ -0,0 +1,329 @@
+//! Versioned JSON envelope for entities and assessments (feature `serde`).
+//!
+//! Every type under [`entities`](crate::entities), plus the function-module
+//! records they reference, derives `Serialize` / `Deserialize` with field
+//! and variant names exactly as in Rust. [`to_json`] wraps a value as
+//! `{"schema": "cepheid", "schema_version": N, "data": …}`; [`from_json`]
+//! reads any version up to the current one. [`SCHEMA_VERSION`] is bumped
+//! only when a field is renamed, removed or changes meaning. Added fields
+//! carry `#[serde(default)]`, so older documents still parse.
+//!
+//! JSON has no NaN or ±∞. Fields that legitimately carry them (PDM θ,
+//! `cond` on a failed fit, padded covariates) write the strings `"NaN"`,
+//! `"inf"` and `"-inf"` instead.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Schema identifier written into every envelope.
+pub const SCHEMA_NAME: &str = "cepheid";
+/// Current JSON schema version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub schema: String,
+    pub schema_version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            schema: SCHEMA_NAME.to_string(),
+            schema_version: SCHEMA_VERSION,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaError {
+    Json(String),
+    Schema(String),
+    Version { found: u32, supported: u32 },
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::Json(e) => write!(f, "JSON error: {e}"),
+            SchemaError::Schema(s) => write!(f, "not a {SCHEMA_NAME} document (schema {s:?})"),
+            SchemaError::Version { found, supported } => write!(
+                f,
+                "schema version {found} is not supported (this build reads 1..={supported})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// `value` inside a [`Versioned`] envelope, as compact JSON.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, SchemaError> {
+    serde_json::to_string(&Versioned::new(value)).map_err(|e| SchemaError::Json(e.to_string()))
+}
+
+/// [`to_json`], indented.
+pub fn to_json_pretty<T: Serialize>(value: &T) -> Result<String, SchemaError> {
+    serde_json::to_string_pretty(&Versioned::new(value))
+        .map_err(|e| SchemaError::Json(e.to_string()))
+}
+
+/// Parse a [`Versioned`] envelope, checking name and version before `data`.
+/// Versions from 1 up to [`SCHEMA_VERSION`] are accepted.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, SchemaError> {
+    let env: Versioned<serde_json::Value> =
+        serde_json::from_str(json).map_err(|e| SchemaError::Json(e.to_string()))?;
+    if env.schema != SCHEMA_NAME {
+        return Err(SchemaError::Schema(env.schema));
+    }
+    if !(1..=SCHEMA_VERSION).contains(&env.schema_version) {
+        return Err(SchemaError::Version {
+            found: env.schema_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    serde_json::from_value(env.data).map_err(|e| SchemaError::Json(e.to_string()))
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Num {
+    Finite(f64),
+    Tag(String),
+}
+
+fn encode(v: f64) -> Num {
+    if v.is_finite() {
+        Num::Finite(v)
+    } else if v.is_nan() {
+        Num::Tag("NaN".into())
+    } else if v > 0.0 {
+        Num::Tag("inf".into())
+    } else {
+        Num::Tag("-inf".into())
+    }
+}
+
+fn decode<E: serde::de::Error>(n: Num) -> Result<f64, E> {
+    match n {
+        Num::Finite(v) => Ok(v),
+        Num::Tag(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "inf" => Ok(f64::INFINITY),
+            "-inf" => Ok(f64::NEG_INFINITY),
+            other => Err(E::custom(format!("expected a number, got {other:?}"))),
+        },
+    }
+}
+
+/// `#[serde(with)]` for an `f64` that may be non-finite.
+pub(crate) mod nonfinite {
+    use super::{Num, decode, encode};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &f64, s: S) -> Result<S::Ok, S::Error> {
+        encode(*v).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+        decode(Num::deserialize(d)?)
+    }
+}
+
+/// `#[serde(with)]` for a `Vec<f64>` that may hold non-finite values.
+pub(crate) mod nonfinite_vec {
+    use super::{Num, decode, encode};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &[f64], s: S) -> Result<S::Ok, S::Error> {
+        v.iter()
+            .map(|&x| encode(x))
+            .collect::<Vec<_>>()
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<f64>, D::Error> {
+        Vec::<Num>::deserialize(d)?
+            .into_iter()
+            .map(decode)
+            .collect()
+    }
+}
+
+/// `#[serde(with)]` for an `Option<Vec<f64>>` that may hold non-finite values.
+pub(crate) mod nonfinite_opt_vec {
+    use super::{Num, decode, encode};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Option<Vec<f64>>, s: S) -> Result<S::Ok, S::Error> {
+        v.as_ref()
+            .map(|v| v.iter().map(|&x| encode(x)).collect::<Vec<_>>())
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<f64>>, D::Error> {
+        Option::<Vec<Num>>::deserialize(d)?
+            .map(|v| v.into_iter().map(decode).collect())
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assessment::{
+        Alias, AliasKind, DetrendColumn, PeriodSearchConfig, PeriodicityAssessment,
+        PeriodicityDecision, ScoreKind,
+    };
+    use crate::entities::lightcurve::Lightcurve;
+    use crate::entities::observation::Observation;
+    use crate::entities::passband::Passband;
+    use crate::entities::series::{Series, TimeFrame};
+    use crate::functions::phase_function::PhaseFunction;
+    use crate::functions::time_scale::TimeScale;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn alias_layout_is_pinned() {
+        let a = Alias {
+            period_s: 5400.0,
+            score: 0.25,
+            kind: AliasKind::Orbital,
+            relative_delta: 0.0,
+            vetoed: true,
+        };
+        assert_eq!(
+            to_json(&a).unwrap(),
+            r#"{"schema":"cepheid","schema_version":1,"data":{"period_s":5400.0,"score":0.25,"kind":"Orbital","relative_delta":0.0,"vetoed":true}}"#
+        );
+    }
+
+    #[test]
+    fn assessment_round_trips_with_non_finite_values() {
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Periodic, Some(47.3));
+        a.periodogram.period_s = vec![10.0, 20.0, 30.0];
+        a.periodogram.score = vec![0.1, f64::NAN, f64::INFINITY];
+        a.periodogram.score_kind = ScoreKind::PdmTheta;
+        a.detrend.cond = f64::INFINITY;
+        a.detrend.columns = vec![DetrendColumn::PassMean { pass: 2 }, DetrendColumn::Tau];
+        a.detrend.band_offsets = vec![(2, 0.0), (6, -0.4)];
+        a.sampling.min_dt_s = f64::INFINITY;
+        a.notes.push("note".into());
+
+        let json = to_json_pretty(&a).unwrap();
+        assert!(json.contains(r#""inf""#) && json.contains(r#""NaN""#));
+        let b: PeriodicityAssessment = from_json(&json).unwrap();
+        assert_eq!(b.decision, PeriodicityDecision::Periodic);
+        assert_eq!(b.period_s, Some(47.3));
+        assert!(b.periodogram.score[1].is_nan());
+        assert_eq!(b.periodogram.score[2], f64::INFINITY);
+        assert_eq!(b.detrend.cond, f64::INFINITY);
+        assert_eq!(b.detrend.columns, a.detrend.columns);
+        assert_eq!(b.detrend.band_offsets, a.detrend.band_offsets);
+        assert_eq!(b.sampling.min_dt_s, f64::INFINITY);
+        assert_eq!(b.notes, a.notes);
+    }
+
+    #[test]
+    fn config_and_lightcurve_round_trip() {
+        let mut cfg = PeriodSearchConfig::sensitive();
+        cfg.phase_function = PhaseFunction::Hg { g: 0.15 };
+        cfg.known_periods_s = vec![5400.0];
+        let back: PeriodSearchConfig = from_json(&to_json(&cfg).unwrap()).unwrap();
+        assert_eq!(back.phase_function, cfg.phase_function);
+        assert_eq!(back.known_periods_s, cfg.known_periods_s);
+        assert_eq!(back.fap_threshold, cfg.fap_threshold);
+
+        let t0: DateTime<Utc> = DateTime::parse_from_rfc3339("2025-01-01T00:00:00.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let obs: Vec<Observation> = (0..4)
+            .map(|i| {
+                Observation::new(
+                    10.0 + i as f64,
+                    1.0e6,
+                    0.5,
+                    t0 + chrono::Duration::seconds(i),
+                    1.0e6,
+                    0.5,
+                )
+                .with_band(Passband::SloanR)
+            })
+            .collect();
+        let lc = Lightcurve::new(obs, Some(true), Some(3.0));
+        let back: Lightcurve = from_json(&to_json(&lc).unwrap()).unwrap();
+        assert_eq!(back.observations[3].timestamp, lc.observations[3].timestamp);
+        assert_eq!(back.observations[3].band, Some(Passband::SloanR));
+        assert_eq!(back.period_sec, Some(3.0));
+
+        let s = lc.to_series().unwrap();
+        let back: Series = from_json(&to_json(&s).unwrap()).unwrap();
+        assert_eq!(back.t_s(), s.t_s());
+        assert_eq!(back.y(), s.y());
+        assert_eq!(back.covariates().band_key, s.covariates().band_key);
+    }
+
+    #[test]
+    fn documents_without_additive_fields_still_parse() {
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Periodic, Some(47.3));
+        a.fap_pdm = Some(0.01);
+        let mut v: serde_json::Value = serde_json::from_str(&to_json(&a).unwrap()).unwrap();
+        for key in ["fap_pdm", "fap_bls", "sidereal", "drift", "glint"] {
+            v["data"].as_object_mut().unwrap().remove(key);
+        }
+        let b: PeriodicityAssessment = from_json(&v.to_string()).unwrap();
+        assert_eq!(b.period_s, Some(47.3));
+        assert_eq!(b.fap_pdm, None);
+
+        let mut v: serde_json::Value =
+            serde_json::from_str(&to_json(&PeriodSearchConfig::default()).unwrap()).unwrap();
+        for key in ["entropy", "bls", "pdot"] {
+            v["data"].as_object_mut().unwrap().remove(key);
+        }
+        v["data"]["pdm"].as_object_mut().unwrap().remove("covers");
+        let c: PeriodSearchConfig = from_json(&v.to_string()).unwrap();
+        assert_eq!(c.pdm.covers, 1);
+        assert!(c.validate().is_ok());
+
+        let old = r#"{"schema":"cepheid","schema_version":1,"data":{
+            "t_s":[0.0,1.0],"y":[1.0,2.0],"sigma":"Unknown","covariates":{
+            "solar_phase_rad":null,"range_m":null,"elevation_rad":null,"illumination":null,
+            "sensor_key":null,"band_key":null,"keep":null},
+            "meta":{"modality":"Generic","y_unit":"Dimensionless","label":null}}}"#;
+        let s: Series = from_json(old).unwrap();
+        assert_eq!(s.meta().time_scale, TimeScale::Utc);
+        assert_eq!(s.meta().time_frame, TimeFrame::Observer);
+    }
+
+    #[test]
+    fn bad_version_and_broken_series_are_rejected() {
+        let json = to_json(&PeriodSearchConfig::default())
+            .unwrap()
+            .replace(r#""schema_version":1"#, r#""schema_version":99"#);
+        assert_eq!(
+            from_json::<PeriodSearchConfig>(&json).unwrap_err(),
+            SchemaError::Version {
+                found: 99,
+                supported: SCHEMA_VERSION
+            }
+        );
+        let json = json.replace(r#""schema_version":99"#, r#""schema_version":0"#);
+        assert!(matches!(
+            from_json::<PeriodSearchConfig>(&json),
+            Err(SchemaError::Version { found: 0, .. })
+        ));
+
+        let broken = r#"{"schema":"cepheid","schema_version":1,"data":{
+            "t_s":[0.0,1.0],"y":[1.0],"sigma":"Unknown","covariates":{
+            "solar_phase_rad":null,"range_m":null,"elevation_rad":null,"illumination":null,
+            "sensor_key":null,"band_key":null,"keep":null},
+            "meta":{"modality":"Generic","y_unit":"Dimensionless","label":null,
+            "time_scale":"Utc","time_frame":"Observer"}}}"#;
+        assert!(matches!(
+            from_json::<Series>(broken),
+            Err(SchemaError::Json(e)) if e.contains("inconsistent lengths")
+        ));
+    }
+}
@@ -1,4 +1,7 @@
 use crate::entities::lightcurve::Lightcurve;
+use crate::entities::passband::{Passband, UNTAGGED_BAND_KEY};
+use crate::functions::shadow::{DEFAULT_MIN_ILLUMINATION, eclipse_keep};
+use crate::functions::time_scale::{SPEED_OF_LIGHT_M_S, TimeScale, convert};
 
 /// Duplicate-time quantum: 1 microsecond, matching [`Observation::unix_seconds`](crate::entities::observation::Observation::unix_seconds).
 pub const T_DUP_S: f64 = 1.0e-6;
@@ -8,6 +11,11 @@ pub const T_DUP_S: f64 = 1.0e-6;
 /// Constructors enforce: finite `t`/`y` after filtering; `σ > 0` when present;
 /// sorted `t`; pairs with `|t_i − t_j| ≤ T_DUP_S` merged; all vectors the same length.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SeriesWire")
+)]
 pub struct Series {
     t_s: Vec<f64>,
     y: Vec<f64>,
@@ -17,11 +25,37 @@ pub struct Series {
     n_merged_duplicates: usize,
 }
 
+/// Deserialized form of [`Series`]; goes back through [`Series::try_new`]
+/// so a hand-edited document cannot break the invariants.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SeriesWire {
+    t_s: Vec<f64>,
+    y: Vec<f64>,
+    sigma: SigmaSpec,
+    covariates: Covariates,
+    meta: SeriesMeta,
+    #[serde(default)]
+    n_merged_duplicates: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SeriesWire> for Series {
+    type Error = SeriesError;
+
+    fn try_from(w: SeriesWire) -> Result<Self, Self::Error> {
+        let mut s = Series::try_new(w.t_s, w.y, w.sigma, w.covariates, w.meta)?;
+        s.n_merged_duplicates += w.n_merged_duplicates;
+        Ok(s)
+    }
+}
+
 /// Distinguish “caller did not measure errors” from “errors really are 1”.
 ///
 /// Never overwrite [`Homoscedastic`](SigmaSpec::Homoscedastic) / [`PerPoint`](SigmaSpec::PerPoint)
 /// because the values happen to be 1.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SigmaSpec {
     /// No measured errors. Weighted methods are *unweighted*. Scale-needing
     /// methods (G-L, QP-GP, PDM scatter, pair-noise) may estimate σ from
@@ -42,22 +76,63 @@ impl SigmaSpec {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Covariates {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_opt_vec")
+    )]
     pub solar_phase_rad: Option<Vec<f64>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_opt_vec")
+    )]
     pub range_m: Option<Vec<f64>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_opt_vec")
+    )]
     pub elevation_rad: Option<Vec<f64>>,
+    /// Visible fraction of the solar disc, `[0, 1]`; see
+    /// [`shadow`](crate::functions::shadow).
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_opt_vec")
+    )]
+    pub illumination: Option<Vec<f64>>,
     pub sensor_key: Option<Vec<u16>>,
+    /// [`Passband::key`] per point; [`UNTAGGED_BAND_KEY`] when untagged.
+    pub band_key: Option<Vec<u16>>,
     pub keep: Option<Vec<bool>>,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeriesMeta {
     pub modality: Modality,
     pub y_unit: YUnit,
     pub label: Option<String>,
+    /// Scale `t_s` is counted in; see [`time_scale`](crate::functions::time_scale).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_scale: TimeScale,
+    /// Whether `t_s` is reception at the sensor or emission at the target.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_frame: TimeFrame,
+}
+
+/// Where along the light path `t_s` is measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeFrame {
+    /// Reception time at the sensor, as recorded.
+    #[default]
+    Observer,
+    /// Emission time at the target, `t − range_m / c`.
+    Target,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Modality {
     OpticalPhotometry,
     RfPower,
@@ -65,6 +140,7 @@ pub enum Modality {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum YUnit {
     Magnitude,
     Decibels,
@@ -75,10 +151,15 @@ pub enum YUnit {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SeriesError {
     LengthMismatch,
-    TooFewPoints { n: usize, min: usize },
+    TooFewPoints {
+        n: usize,
+        min: usize,
+    },
     NonFinite,
     NonPositiveSigma,
     EmptyAfterFilter,
+    /// Retiming to another [`TimeFrame`] needs the `range_m` covariate.
+    MissingRange,
 }
 
 impl std::fmt::Display for SeriesError {
@@ -97,6 +178,9 @@ impl std::fmt::Display for SeriesError {
             SeriesError::EmptyAfterFilter => {
                 write!(f, "no points remain after keep / finite-value filter")
             }
+            SeriesError::MissingRange => {
+                write!(f, "light-time correction needs range_m on every point")
+            }
         }
     }
 }
@@ -110,7 +194,9 @@ struct Row {
     phase: Option<f64>,
     range: Option<f64>,
     elev: Option<f64>,
+    illum: Option<f64>,
     sensor: Option<u16>,
+    band: Option<u16>,
 }
 
 impl Row {
@@ -121,6 +207,7 @@ impl Row {
         self.phase = weighted_opt(self.phase, self.inv_var, other.phase, other.inv_var, w);
         self.range = weighted_opt(self.range, self.inv_var, other.range, other.inv_var, w);
         self.elev = weighted_opt(self.elev, self.inv_var, other.elev, other.inv_var, w);
+        self.illum = weighted_opt(self.illum, self.inv_var, other.illum, other.inv_var, w);
         if self.sensor.is_none() {
             self.sensor = other.sensor;
         }
@@ -146,6 +233,7 @@ fn inv_var_of(sigma: &SigmaSpec, i: usize) -> f64 {
 }
 
 impl Series {
+    #[allow(clippy::collapsible_if)]
     pub fn try_new(
         t_s: Vec<f64>,
         y: Vec<f64>,
@@ -169,18 +257,23 @@ impl Series {
             }
             _ => {}
         }
-        for opt_f in [
+        for v in [
             &covariates.solar_phase_rad,
             &covariates.range_m,
             &covariates.elevation_rad,
-        ] {
-            if let Some(v) = opt_f {
-                if v.len() != n {
-                    return Err(SeriesError::LengthMismatch);
-                }
+            &covariates.illumination,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if v.len() != n {
+                return Err(SeriesError::LengthMismatch);
             }
         }
-        if let Some(v) = &covariates.sensor_key {
+        for v in [&covariates.sensor_key, &covariates.band_key]
+            .into_iter()
+            .flatten()
+        {
             if v.len() != n {
                 return Err(SeriesError::LengthMismatch);
             }
@@ -224,7 +317,9 @@ impl Series {
                 phase: covariates.solar_phase_rad.as_ref().map(|v| v[i]),
                 range: covariates.range_m.as_ref().map(|v| v[i]),
                 elev: covariates.elevation_rad.as_ref().map(|v| v[i]),
+                illum: covariates.illumination.as_ref().map(|v| v[i]),
                 sensor: covariates.sensor_key.as_ref().map(|v| v[i]),
+                band: covariates.band_key.as_ref().map(|v| v[i]),
             });
         }
 
@@ -232,7 +327,8 @@ impl Series {
         let mut n_merged_duplicates = 0usize;
         for row in rows {
             if let Some(last) = merged.last_mut() {
-                if (row.t - last.t).abs() <= T_DUP_S {
+                // Simultaneous exposures in different filters are distinct samples.
+                if (row.t - last.t).abs() <= T_DUP_S && row.band == last.band {
                     last.absorb(&row);
                     n_merged_duplicates += 1;
                     continue;
@@ -247,12 +343,16 @@ impl Series {
         let mut phase_out = Vec::with_capacity(m);
         let mut range_out = Vec::with_capacity(m);
         let mut elev_out = Vec::with_capacity(m);
+        let mut illum_out = Vec::with_capacity(m);
         let mut sensor_out = Vec::with_capacity(m);
+        let mut band_out = Vec::with_capacity(m);
         let mut sigma_out = Vec::with_capacity(m);
         let has_phase = covariates.solar_phase_rad.is_some();
         let has_range = covariates.range_m.is_some();
         let has_elev = covariates.elevation_rad.is_some();
+        let has_illum = covariates.illumination.is_some();
         let has_sensor = covariates.sensor_key.is_some();
+        let has_band = covariates.band_key.is_some();
 
         for r in &merged {
             t_out.push(r.t);
@@ -266,9 +366,15 @@ impl Series {
             if has_elev {
                 elev_out.push(r.elev.unwrap_or(f64::NAN));
             }
+            if has_illum {
+                illum_out.push(r.illum.unwrap_or(f64::NAN));
+            }
             if has_sensor {
                 sensor_out.push(r.sensor.unwrap_or(0));
             }
+            if has_band {
+                band_out.push(r.band.unwrap_or(UNTAGGED_BAND_KEY));
+            }
             sigma_out.push(1.0 / r.inv_var.sqrt());
         }
 
@@ -286,7 +392,9 @@ impl Series {
                 solar_phase_rad: has_phase.then_some(phase_out),
                 range_m: has_range.then_some(range_out),
                 elevation_rad: has_elev.then_some(elev_out),
+                illumination: has_illum.then_some(illum_out),
                 sensor_key: has_sensor.then_some(sensor_out),
+                band_key: has_band.then_some(band_out),
                 keep: None,
             },
             meta,
@@ -294,19 +402,48 @@ impl Series {
         })
     }
 
-    /// Optical ingest: `t = unix_seconds()`, `y = std_magnitude`, `sigma = Unknown`.
+    /// Optical ingest: `t = unix_seconds()` (UTC), `y = std_magnitude`, `sigma = Unknown`.
+    ///
+    /// `band_key` is filled only when at least one observation carries a passband.
     pub fn from_lightcurve(lc: &Lightcurve) -> Result<Self, SeriesError> {
+        Self::from_lightcurve_in(lc, TimeFrame::Observer)
+    }
+
+    /// [`from_lightcurve`](Self::from_lightcurve) with `t` taken in `frame`;
+    /// [`TimeFrame::Target`] uses [`Observation::emission_time_s`](crate::entities::observation::Observation::emission_time_s).
+    pub fn from_lightcurve_in(lc: &Lightcurve, frame: TimeFrame) -> Result<Self, SeriesError> {
         let n = lc.observations.len();
         let mut t_s = Vec::with_capacity(n);
         let mut y = Vec::with_capacity(n);
         let mut phases = Vec::with_capacity(n);
         let mut ranges = Vec::with_capacity(n);
+        let mut bands = Vec::with_capacity(n);
         for o in &lc.observations {
-            t_s.push(o.unix_seconds());
+            t_s.push(match frame {
+                TimeFrame::Observer => o.unix_seconds(),
+                TimeFrame::Target => o.emission_time_s(TimeScale::Utc),
+            });
             y.push(o.std_magnitude);
             phases.push(o.phase_rad);
             ranges.push(o.range_m);
+            bands.push(o.band.map(Passband::key).unwrap_or(UNTAGGED_BAND_KEY));
         }
+        let any_band = lc.observations.iter().any(|o| o.band.is_some());
+        // Elevation is only a usable covariate when every point has it.
+        let elevations: Option<Vec<f64>> = if n > 0 {
+            lc.observations.iter().map(|o| o.elevation_rad).collect()
+        } else {
+            None
+        };
+        // Same rule for illumination; eclipsed points are then dropped.
+        let illumination: Option<Vec<f64>> = if n > 0 {
+            lc.observations.iter().map(|o| o.illumination).collect()
+        } else {
+            None
+        };
+        let keep = illumination
+            .as_deref()
+            .map(|f| eclipse_keep(f, DEFAULT_MIN_ILLUMINATION));
         Self::try_new(
             t_s,
             y,
@@ -314,12 +451,18 @@ impl Series {
             Covariates {
                 solar_phase_rad: (n > 0).then_some(phases),
                 range_m: (n > 0).then_some(ranges),
+                elevation_rad: elevations,
+                illumination,
+                band_key: any_band.then_some(bands),
+                keep,
                 ..Covariates::default()
             },
             SeriesMeta {
                 modality: Modality::OpticalPhotometry,
                 y_unit: YUnit::Magnitude,
                 label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: frame,
             },
         )
     }
@@ -356,10 +499,89 @@ impl Series {
         &self.meta
     }
 
+    /// The same series with `t_s` re-counted in `scale`. TAI and TT are
+    /// continuous across leap seconds where UTC is not, so convert before
+    /// folding a campaign that spans one. Going back to UTC re-runs the
+    /// constructor checks, since points inside a leap second collide.
+    pub fn to_time_scale(&self, scale: TimeScale) -> Result<Self, SeriesError> {
+        let from = self.meta.time_scale;
+        let t_s = self.t_s.iter().map(|&t| convert(t, from, scale)).collect();
+        let mut s = Self::try_new(
+            t_s,
+            self.y.clone(),
+            self.sigma.clone(),
+            self.covariates.clone(),
+            SeriesMeta {
+                time_scale: scale,
+                ..self.meta.clone()
+            },
+        )?;
+        s.n_merged_duplicates += self.n_merged_duplicates;
+        Ok(s)
+    }
+
+    /// The same series retimed to `frame` by the one-way light time
+    /// `range_m / c`. At GEO that is ~120 ms and drifts with geometry, which
+    /// smears a fold of a fast tumbler over a long campaign.
+    pub fn to_time_frame(&self, frame: TimeFrame) -> Result<Self, SeriesError> {
+        if frame == self.meta.time_frame {
+            return Ok(self.clone());
+        }
+        let range = self
+            .covariates
+            .range_m
+            .as_deref()
+            .ok_or(SeriesError::MissingRange)?;
+        if range.iter().any(|r| !r.is_finite()) {
+            return Err(SeriesError::MissingRange);
+        }
+        let sign = match frame {
+            TimeFrame::Observer => 1.0,
+            TimeFrame::Target => -1.0,
+        };
+        let t_s = self
+            .t_s
+            .iter()
+            .zip(range)
+            .map(|(&t, &r)| t + sign * r / SPEED_OF_LIGHT_M_S)
+            .collect();
+        let mut s = Self::try_new(
+            t_s,
+            self.y.clone(),
+            self.sigma.clone(),
+            self.covariates.clone(),
+            SeriesMeta {
+                time_frame: frame,
+                ..self.meta.clone()
+            },
+        )?;
+        s.n_merged_duplicates += self.n_merged_duplicates;
+        Ok(s)
+    }
+
+    /// Distinct `band_key` values in first-seen order; empty when untagged.
+    pub fn band_keys(&self) -> Vec<u16> {
+        let mut out: Vec<u16> = Vec::new();
+        if let Some(keys) = &self.covariates.band_key {
+            for &k in keys {
+                if !out.contains(&k) {
+                    out.push(k);
+                }
+            }
+        }
+        out
+    }
+
     pub fn n_merged_duplicates(&self) -> usize {
         self.n_merged_duplicates
     }
 
+    /// Carry over duplicates merged before the series was stored.
+    #[cfg(feature = "arrow")]
+    pub(crate) fn add_merged_duplicates(&mut self, n: usize) {
+        self.n_merged_duplicates += n;
+    }
+
     pub fn len(&self) -> usize {
         self.t_s.len()
     }
@@ -400,6 +622,8 @@ mod tests {
             modality: Modality::Generic,
             y_unit: YUnit::Dimensionless,
             label: None,
+            time_scale: TimeScale::Utc,
+            time_frame: TimeFrame::Observer,
         }
     }
 
@@ -570,6 +794,9 @@ mod tests {
             phase_rad: 0.5,
             std_magnitude: 9.5,
             timestamp: ts,
+            band: None,
+            elevation_rad: None,
+            illumination: None,
             fractional_period: None,
         };
         let lc = Lightcurve::new(vec![obs], None, None);
@@ -583,6 +810,58 @@ mod tests {
         assert!(s.span_s().is_none());
     }
 
+    #[test]
+    fn simultaneous_different_bands_are_not_merged() {
+        let s = Series::try_new(
+            vec![0.0, 0.0, 1.0, 1.0],
+            vec![10.0, 9.5, 10.1, 10.1],
+            SigmaSpec::Unknown,
+            Covariates {
+                band_key: Some(vec![2, 6, 2, 2]),
+                ..Covariates::default()
+            },
+            meta(),
+        )
+        .unwrap();
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.n_merged_duplicates(), 1);
+        assert_eq!(s.covariates().band_key.as_deref(), Some(&[2, 6, 2][..]));
+        assert_eq!(s.band_keys(), vec![2, 6]);
+    }
+
+    #[test]
+    fn from_lightcurve_tags_bands_only_when_present() {
+        let ts = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let obs = |dt: i64| Observation {
+            vismag: 10.0,
+            range_m: 1.0e6,
+            phase_rad: 0.5,
+            std_magnitude: 9.5,
+            timestamp: ts + chrono::Duration::seconds(dt),
+            band: None,
+            elevation_rad: None,
+            illumination: None,
+            fractional_period: None,
+        };
+        let plain = Lightcurve::new(vec![obs(0), obs(1)], None, None);
+        assert!(
+            Series::from_lightcurve(&plain)
+                .unwrap()
+                .covariates()
+                .band_key
+                .is_none()
+        );
+
+        let tagged = Lightcurve::new(vec![obs(0).with_band(Passband::SloanG), obs(1)], None, None);
+        let s = Series::from_lightcurve(&tagged).unwrap();
+        assert_eq!(
+            s.covariates().band_key.as_deref(),
+            Some(&[Passband::SloanG.key(), UNTAGGED_BAND_KEY][..])
+        );
+    }
+
     #[test]
     fn from_empty_lightcurve_is_ok() {
         let lc = Lightcurve::new(vec![], None, None);
@@ -590,4 +869,70 @@ mod tests {
         assert!(s.is_empty());
         assert!(s.span_s().is_none());
     }
+
+    #[test]
+    fn leap_second_is_continuous_in_tai() {
+        // 10 s cadence across 2016-12-31T23:59:60Z.
+        let t: Vec<f64> = (-3..3).map(|k| 1_483_228_800.0 + 10.0 * k as f64).collect();
+        let s = Series::try_new(
+            t,
+            vec![1.0; 6],
+            SigmaSpec::Unknown,
+            Covariates::default(),
+            meta(),
+        )
+        .unwrap();
+        let tai = s.to_time_scale(TimeScale::Tai).unwrap();
+        assert_eq!(tai.meta().time_scale, TimeScale::Tai);
+        let dt: Vec<f64> = tai.t_s().windows(2).map(|w| w[1] - w[0]).collect();
+        assert_eq!(dt, vec![10.0, 10.0, 11.0, 10.0, 10.0]);
+        let back = tai.to_time_scale(TimeScale::Utc).unwrap();
+        assert_eq!(back.t_s(), s.t_s());
+        assert_eq!(back.meta().time_scale, TimeScale::Utc);
+    }
+
+    #[test]
+    fn light_time_retimes_to_emission_and_back() {
+        let ts = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        // GEO range drifting by 1,000 km across the night.
+        let obs = |dt: i64, range_m: f64| Observation {
+            vismag: 10.0,
+            range_m,
+            phase_rad: 0.5,
+            std_magnitude: 9.5,
+            timestamp: ts + chrono::Duration::seconds(dt),
+            band: None,
+            elevation_rad: None,
+            illumination: None,
+            fractional_period: None,
+        };
+        let lc = Lightcurve::new(vec![obs(0, 3.6e7), obs(10, 3.7e7)], None, None);
+        let s = Series::from_lightcurve_in(&lc, TimeFrame::Target).unwrap();
+        assert_eq!(s.meta().time_frame, TimeFrame::Target);
+        let lag = |r: f64| r / SPEED_OF_LIGHT_M_S;
+        assert!((s.t_s()[0] - (lc.observations[0].unix_seconds() - lag(3.6e7))).abs() < 1e-6);
+        assert!((s.t_s()[1] - s.t_s()[0] - (10.0 - lag(3.7e7) + lag(3.6e7))).abs() < 1e-6);
+
+        let observer = Series::from_lightcurve(&lc).unwrap();
+        assert_eq!(observer.meta().time_frame, TimeFrame::Observer);
+        let back = s.to_time_frame(TimeFrame::Observer).unwrap();
+        for (a, b) in back.t_s().iter().zip(observer.t_s()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        let bare = Series::try_new(
+            vec![0.0, 1.0],
+            vec![1.0, 2.0],
+            SigmaSpec::Unknown,
+            Covariates::default(),
+            meta(),
+        )
+        .unwrap();
+        assert_eq!(
+            bare.to_time_frame(TimeFrame::Target).unwrap_err(),
+            SeriesError::MissingRange
+        );
+    }
 }
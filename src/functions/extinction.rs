@@ -0,0 +1,292 @@
+//! Airmass models and first-order atmospheric extinction.
+//!
+//! `m₀ = m − k·X`: `k` (mag / airmass) per band, `X` from the target
+//! elevation. Coefficients come from a fixed per-band table or are fitted
+//! night by night from comparison stars ([`fit_nightly_extinction`]);
+//! [`Lightcurve::apply_extinction`](crate::entities::lightcurve::Lightcurve::apply_extinction)
+//! pushes the correction into `vismag` and `std_magnitude`.
+
+use crate::entities::passband::Passband;
+use crate::functions::geometry::airmass as kasten_young;
+use chrono::{DateTime, Utc};
+
+/// Comparison measurements further apart than this start a new night.
+pub const NIGHT_GAP_S: f64 = 6.0 * 3600.0;
+/// Fewest comparison points for a nightly fit.
+pub const MIN_NIGHTLY_POINTS: usize = 3;
+/// Smallest airmass range (max − min) that constrains `k`.
+pub const MIN_AIRMASS_SPREAD: f64 = 0.2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AirmassModel {
+    /// `sec z`. Diverges at the horizon; fine above ~30° elevation.
+    PlaneParallel,
+    /// Kasten & Young (1989); finite down to the horizon.
+    #[default]
+    KastenYoung,
+    /// Hardie (1962) polynomial in `sec z − 1`; good to ~85° zenith distance.
+    Hardie,
+}
+
+impl AirmassModel {
+    /// Relative airmass at `elevation_rad`. `None` at or below the horizon.
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    pub fn airmass(self, elevation_rad: f64) -> Option<f64> {
+        if !(elevation_rad > 0.0) {
+            return None;
+        }
+        match self {
+            AirmassModel::PlaneParallel => Some(1.0 / elevation_rad.sin()),
+            AirmassModel::KastenYoung => kasten_young(elevation_rad),
+            AirmassModel::Hardie => {
+                let s = 1.0 / elevation_rad.sin() - 1.0;
+                Some(1.0 + s - 0.001_816_7 * s - 0.002_875 * s * s - 0.000_808_3 * s * s * s)
+            }
+        }
+    }
+}
+
+/// One night's fitted extinction in one band:
+/// `m_inst − m_cat = zero_point_mag + k·X`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NightlyExtinction {
+    pub band: Option<Passband>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub k: f64,
+    pub k_sigma: f64,
+    pub zero_point_mag: f64,
+    pub n: usize,
+}
+
+impl NightlyExtinction {
+    pub fn covers(&self, when: DateTime<Utc>) -> bool {
+        when >= self.start && when <= self.end
+    }
+}
+
+/// Extinction coefficients for correcting observations. Bands without an
+/// entry are left unchanged; a nightly fit covering the observation time
+/// overrides the fixed coefficient for its band.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Extinction {
+    pub model: AirmassModel,
+    pub bands: Vec<(Passband, f64)>,
+    /// `k` for untagged observations; `None` leaves them unchanged.
+    pub untagged_k: Option<f64>,
+    pub nightly: Vec<NightlyExtinction>,
+}
+
+impl Extinction {
+    pub fn get(&self, band: Passband) -> Option<f64> {
+        self.bands.iter().find(|(b, _)| *b == band).map(|(_, k)| *k)
+    }
+
+    pub fn set(&mut self, band: Passband, k: f64) {
+        match self.bands.iter_mut().find(|(b, _)| *b == band) {
+            Some(entry) => entry.1 = k,
+            None => self.bands.push((band, k)),
+        }
+    }
+
+    /// Coefficient for `band` at `when`: covering nightly fit first, then
+    /// the fixed table.
+    pub fn k_for(&self, band: Option<Passband>, when: DateTime<Utc>) -> Option<f64> {
+        self.nightly
+            .iter()
+            .find(|n| n.band == band && n.covers(when))
+            .map(|n| n.k)
+            .or_else(|| match band {
+                Some(b) => self.get(b),
+                None => self.untagged_k,
+            })
+    }
+}
+
+/// One comparison-star measurement for extinction fitting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparisonMeasurement {
+    pub timestamp: DateTime<Utc>,
+    pub band: Option<Passband>,
+    pub instrumental_mag: f64,
+    pub catalogue_mag: f64,
+    pub elevation_rad: f64,
+}
+
+/// Bouguer fit of `k` per band per night. Nights are split at gaps longer
+/// than [`NIGHT_GAP_S`]; a (night, band) group is skipped when it has fewer
+/// than [`MIN_NIGHTLY_POINTS`] usable points or spans less than
+/// [`MIN_AIRMASS_SPREAD`] in airmass.
+pub fn fit_nightly_extinction(
+    measurements: &[ComparisonMeasurement],
+    model: AirmassModel,
+) -> Vec<NightlyExtinction> {
+    let mut rows: Vec<(DateTime<Utc>, Option<Passband>, f64, f64)> = measurements
+        .iter()
+        .filter_map(|m| {
+            let x = model.airmass(m.elevation_rad)?;
+            let dm = m.instrumental_mag - m.catalogue_mag;
+            (x.is_finite() && dm.is_finite()).then_some((m.timestamp, m.band, x, dm))
+        })
+        .collect();
+    rows.sort_by_key(|r| r.0);
+
+    let mut out = Vec::new();
+    let mut start = 0usize;
+    for i in 0..rows.len() {
+        let last = i + 1 == rows.len();
+        let gap =
+            !last && (rows[i + 1].0 - rows[i].0).num_milliseconds() as f64 / 1000.0 > NIGHT_GAP_S;
+        if last || gap {
+            fit_night(&rows[start..=i], &mut out);
+            start = i + 1;
+        }
+    }
+    out
+}
+
+fn fit_night(
+    night: &[(DateTime<Utc>, Option<Passband>, f64, f64)],
+    out: &mut Vec<NightlyExtinction>,
+) {
+    let mut bands: Vec<Option<Passband>> = Vec::new();
+    for r in night {
+        if !bands.contains(&r.1) {
+            bands.push(r.1);
+        }
+    }
+    for band in bands {
+        let pts: Vec<&(DateTime<Utc>, Option<Passband>, f64, f64)> =
+            night.iter().filter(|r| r.1 == band).collect();
+        let n = pts.len();
+        if n < MIN_NIGHTLY_POINTS {
+            continue;
+        }
+        let (x_min, x_max) = pts
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), r| {
+                (lo.min(r.2), hi.max(r.2))
+            });
+        if x_max - x_min < MIN_AIRMASS_SPREAD {
+            continue;
+        }
+        let nf = n as f64;
+        let mx = pts.iter().map(|r| r.2).sum::<f64>() / nf;
+        let my = pts.iter().map(|r| r.3).sum::<f64>() / nf;
+        let sxx: f64 = pts.iter().map(|r| (r.2 - mx).powi(2)).sum();
+        let sxy: f64 = pts.iter().map(|r| (r.2 - mx) * (r.3 - my)).sum();
+        let k = sxy / sxx;
+        let zp = my - k * mx;
+        let ssr: f64 = pts.iter().map(|r| (r.3 - zp - k * r.2).powi(2)).sum();
+        let k_sigma = (ssr / (nf - 2.0) / sxx).sqrt();
+        out.push(NightlyExtinction {
+            band,
+            start: pts[0].0,
+            end: pts[n - 1].0,
+            k,
+            k_sigma,
+            zero_point_mag: zp,
+            n,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn airmass_models_agree_high_and_split_low() {
+        let el = 60.0_f64.to_radians();
+        let pp = AirmassModel::PlaneParallel.airmass(el).unwrap();
+        let ky = AirmassModel::KastenYoung.airmass(el).unwrap();
+        let ha = AirmassModel::Hardie.airmass(el).unwrap();
+        assert!(
+            (pp - ky).abs() < 2e-3 && (pp - ha).abs() < 2e-3,
+            "{pp} {ky} {ha}"
+        );
+
+        let low = 5.0_f64.to_radians();
+        let pp = AirmassModel::PlaneParallel.airmass(low).unwrap();
+        let ky = AirmassModel::KastenYoung.airmass(low).unwrap();
+        // Refraction and curvature: ~10.3 vs sec z ≈ 11.5.
+        assert!(pp > ky + 0.8, "{pp} {ky}");
+        assert!((ky - 10.3).abs() < 0.2, "{ky}");
+        assert!(AirmassModel::Hardie.airmass(0.0).is_none());
+    }
+
+    #[test]
+    fn nightly_fit_recovers_k_per_night_and_band() {
+        let mut comps = Vec::new();
+        let nights = [
+            ("2025-03-01T02:00:00Z", 0.18),
+            ("2025-03-02T02:00:00Z", 0.27),
+        ];
+        for (start, k_v) in nights {
+            for i in 0..10 {
+                let when = at(start) + chrono::Duration::minutes(20 * i);
+                let el = (20.0 + 6.0 * i as f64).to_radians();
+                let x = AirmassModel::KastenYoung.airmass(el).unwrap();
+                for (band, k) in [(Some(Passband::JohnsonV), k_v), (None, 0.5 * k_v)] {
+                    comps.push(ComparisonMeasurement {
+                        timestamp: when,
+                        band,
+                        instrumental_mag: 10.0 - 21.0 + k * x,
+                        catalogue_mag: 10.0,
+                        elevation_rad: el,
+                    });
+                }
+            }
+        }
+        let fits = fit_nightly_extinction(&comps, AirmassModel::KastenYoung);
+        assert_eq!(fits.len(), 4);
+        for (start, k_v) in nights {
+            let mid = at(start) + chrono::Duration::minutes(60);
+            let v = fits
+                .iter()
+                .find(|f| f.band == Some(Passband::JohnsonV) && f.covers(mid))
+                .unwrap();
+            assert!((v.k - k_v).abs() < 1e-9, "{}", v.k);
+            assert!((v.zero_point_mag + 21.0).abs() < 1e-9);
+            assert_eq!(v.n, 10);
+        }
+
+        let ext = Extinction {
+            nightly: fits,
+            ..Extinction::default()
+        };
+        let mid = at("2025-03-02T03:00:00Z");
+        assert!((ext.k_for(None, mid).unwrap() - 0.135).abs() < 1e-9);
+        assert_eq!(ext.k_for(Some(Passband::SloanR), mid), None);
+    }
+
+    #[test]
+    fn table_lookup_and_flat_night_is_skipped() {
+        let mut ext = Extinction::default();
+        ext.set(Passband::JohnsonB, 0.25);
+        ext.set(Passband::JohnsonB, 0.3);
+        let when = at("2025-01-01T00:00:00Z");
+        assert_eq!(ext.k_for(Some(Passband::JohnsonB), when), Some(0.3));
+        assert_eq!(ext.bands.len(), 1);
+        assert!(ext.k_for(None, when).is_none());
+
+        let flat: Vec<ComparisonMeasurement> = (0..5)
+            .map(|i| ComparisonMeasurement {
+                timestamp: when + chrono::Duration::minutes(i),
+                band: None,
+                instrumental_mag: -10.0,
+                catalogue_mag: 10.0,
+                elevation_rad: 1.2,
+            })
+            .collect();
+        assert!(fit_nightly_extinction(&flat, AirmassModel::Hardie).is_empty());
+    }
+}
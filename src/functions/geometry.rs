@@ -0,0 +1,259 @@
+//! Observer / target / Sun geometry.
+//!
+//! Target and Sun positions are Earth-centred inertial vectors in km (TEME or
+//! any frame that differs from it by less than precession–nutation). The site
+//! is rotated into that frame by GMST alone; for magnitude normalization the
+//! resulting arcsecond-level error is negligible, since range enters as
+//! `log10(range)`.
+
+use crate::entities::series::Covariates;
+use chrono::{DateTime, Utc};
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/// WGS-84 equatorial radius (km).
+pub const WGS84_A_KM: f64 = 6378.137;
+/// WGS-84 flattening.
+pub const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Geodetic observing site (WGS-84).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Site {
+    pub lat_deg: f64,
+    pub lon_deg: f64,
+    pub alt_km: f64,
+}
+
+impl Site {
+    pub fn new(lat_deg: f64, lon_deg: f64, alt_km: f64) -> Self {
+        Self {
+            lat_deg,
+            lon_deg,
+            alt_km,
+        }
+    }
+
+    pub fn ecef_km(&self) -> [f64; 3] {
+        geodetic_to_ecef_km(self.lat_deg, self.lon_deg, self.alt_km)
+    }
+
+    /// Site position in the inertial frame at `when`.
+    pub fn eci_km(&self, when: DateTime<Utc>) -> [f64; 3] {
+        rotate_z(self.ecef_km(), gmst_rad(when))
+    }
+}
+
+/// Everything one observation needs from geometry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geometry {
+    pub range_m: f64,
+    /// Sun–target–observer angle.
+    pub phase_rad: f64,
+    pub elevation_rad: f64,
+    /// From north through east, `[0, 2π)`.
+    pub azimuth_rad: f64,
+    /// Kasten–Young airmass; `None` at or below the horizon.
+    pub airmass: Option<f64>,
+}
+
+/// Geometry of `target_eci_km` seen from `site` at `when`, lit by the Sun at
+/// `sun_eci_km` (geocentric; any distance ≫ the Earth–target separation).
+pub fn observe(
+    site: &Site,
+    when: DateTime<Utc>,
+    target_eci_km: [f64; 3],
+    sun_eci_km: [f64; 3],
+) -> Geometry {
+    let gmst = gmst_rad(when);
+    let site_eci = rotate_z(site.ecef_km(), gmst);
+    let rho = sub(target_eci_km, site_eci);
+    let range_km = norm(rho);
+
+    let to_sun = sub(sun_eci_km, target_eci_km);
+    let to_obs = sub(site_eci, target_eci_km);
+    let phase_rad = angle_between(to_sun, to_obs);
+
+    let (east, north, up) = enu(site, rotate_z(rho, -gmst));
+    let elevation_rad = (up / range_km).clamp(-1.0, 1.0).asin();
+    let azimuth_rad = east.atan2(north).rem_euclid(TAU);
+
+    Geometry {
+        range_m: range_km * 1000.0,
+        phase_rad,
+        elevation_rad,
+        azimuth_rad,
+        airmass: airmass(elevation_rad),
+    }
+}
+
+/// Unit phase-angle bisector at the target: the direction halfway between
+/// target→Sun and target→observer, for the same inputs as [`observe`].
+pub fn phase_angle_bisector(
+    site: &Site,
+    when: DateTime<Utc>,
+    target_eci_km: [f64; 3],
+    sun_eci_km: [f64; 3],
+) -> [f64; 3] {
+    let site_eci = site.eci_km(when);
+    let to_sun = sub(sun_eci_km, target_eci_km);
+    let to_obs = sub(site_eci, target_eci_km);
+    let (ns, no) = (norm(to_sun), norm(to_obs));
+    let b = [
+        to_sun[0] / ns + to_obs[0] / no,
+        to_sun[1] / ns + to_obs[1] / no,
+        to_sun[2] / ns + to_obs[2] / no,
+    ];
+    let nb = norm(b);
+    [b[0] / nb, b[1] / nb, b[2] / nb]
+}
+
+/// Kasten & Young (1989) relative airmass. `None` at or below the horizon.
+pub fn airmass(elevation_rad: f64) -> Option<f64> {
+    if elevation_rad <= 0.0 {
+        return None;
+    }
+    let el_deg = elevation_rad.to_degrees();
+    Some(1.0 / (elevation_rad.sin() + 0.50572 * (el_deg + 6.07995).powf(-1.6364)))
+}
+
+/// `Covariates` with `solar_phase_rad`, `range_m` and `elevation_rad` filled
+/// from per-point target and Sun positions. All slices must be the same length.
+pub fn geometry_covariates(
+    site: &Site,
+    times: &[DateTime<Utc>],
+    target_eci_km: &[[f64; 3]],
+    sun_eci_km: &[[f64; 3]],
+) -> Covariates {
+    let geo: Vec<Geometry> = times
+        .iter()
+        .zip(target_eci_km.iter())
+        .zip(sun_eci_km.iter())
+        .map(|((&t, &x), &s)| observe(site, t, x, s))
+        .collect();
+    Covariates {
+        solar_phase_rad: Some(geo.iter().map(|g| g.phase_rad).collect()),
+        range_m: Some(geo.iter().map(|g| g.range_m).collect()),
+        elevation_rad: Some(geo.iter().map(|g| g.elevation_rad).collect()),
+        ..Covariates::default()
+    }
+}
+
+pub fn geodetic_to_ecef_km(lat_deg: f64, lon_deg: f64, alt_km: f64) -> [f64; 3] {
+    let e2 = 2.0 * WGS84_F - WGS84_F * WGS84_F;
+    let lat = lat_deg.to_radians();
+    let lon = lon_deg.to_radians();
+    let sin_lat = lat.sin();
+    let n = WGS84_A_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + alt_km) * lat.cos() * lon.cos(),
+        (n + alt_km) * lat.cos() * lon.sin(),
+        (n * (1.0 - e2) + alt_km) * sin_lat,
+    ]
+}
+
+/// Greenwich mean sidereal time (Vallado §3.5.1), UT1 ≈ UTC.
+pub fn gmst_rad(when: DateTime<Utc>) -> f64 {
+    let jd_ut1 = unix_to_jd(when.timestamp_millis() as f64 / 1000.0);
+    let t = (jd_ut1 - 2_451_545.0) / 36_525.0;
+    let gmst_sec = 67_310.548_41 + (876_600.0 * 3600.0 + 8_640_184.812_866) * t + 0.093_104 * t * t
+        - 6.2e-6 * t * t * t;
+    let gmst_sec = gmst_sec.rem_euclid(86_400.0);
+    gmst_sec * TAU / 86_400.0
+}
+
+pub fn unix_to_jd(unix_seconds: f64) -> f64 {
+    2_440_587.5 + unix_seconds / 86_400.0
+}
+
+pub fn rotate_z(v: [f64; 3], theta: f64) -> [f64; 3] {
+    let c = theta.cos();
+    let s = theta.sin();
+    [v[0] * c - v[1] * s, v[0] * s + v[1] * c, v[2]]
+}
+
+/// Topocentric (east, north, up) components of an ECEF offset vector.
+fn enu(site: &Site, d: [f64; 3]) -> (f64, f64, f64) {
+    let (sl, cl) = site.lat_deg.to_radians().sin_cos();
+    let (so, co) = site.lon_deg.to_radians().sin_cos();
+    let east = -so * d[0] + co * d[1];
+    let north = -sl * co * d[0] - sl * so * d[1] + cl * d[2];
+    let up = cl * co * d[0] + cl * so * d[1] + sl * d[2];
+    (east, north, up)
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+pub(crate) fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (na, nb) = (norm(a), norm(b));
+    if na == 0.0 || nb == 0.0 {
+        return FRAC_PI_2;
+    }
+    let c = (a[0] * b[0] + a[1] * b[1] + a[2] * b[2]) / (na * nb);
+    c.clamp(-1.0, 1.0).acos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn gmst_at_j2000_epoch() {
+        // 2000-01-01T12:00:00 UT1: GMST = 280.46061837°.
+        let g = gmst_rad(at("2000-01-01T12:00:00Z")).to_degrees();
+        assert!((g - 280.460_618_37).abs() < 1e-4, "{g}");
+    }
+
+    #[test]
+    fn ecef_hits_equator_and_pole() {
+        let eq = geodetic_to_ecef_km(0.0, 90.0, 0.0);
+        assert!(eq[0].abs() < 1e-9 && (eq[1] - WGS84_A_KM).abs() < 1e-9);
+        let pole = geodetic_to_ecef_km(90.0, 0.0, 0.0);
+        assert!((pole[2] - WGS84_A_KM * (1.0 - WGS84_F)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn zenith_target_with_sun_behind_observer() {
+        let site = Site::new(32.0, -110.0, 1.0);
+        let when = at("2025-03-20T06:00:00Z");
+        let s = site.eci_km(when);
+        let r = norm(s);
+        let up = [s[0] / r, s[1] / r, s[2] / r];
+        // Radial-up is the geocentric vertical; at 32° latitude it departs
+        // from the geodetic zenith by ~0.18°.
+        let target = [
+            s[0] + 36_000.0 * up[0],
+            s[1] + 36_000.0 * up[1],
+            s[2] + 36_000.0 * up[2],
+        ];
+        let sun = [-1.5e8 * up[0], -1.5e8 * up[1], -1.5e8 * up[2]];
+        let g = observe(&site, when, target, sun);
+        assert!((g.range_m - 36_000.0e3).abs() < 1.0);
+        assert!(g.elevation_rad.to_degrees() > 89.5);
+        assert!((g.airmass.unwrap() - 1.0).abs() < 1e-3);
+        assert!(g.phase_rad.to_degrees() < 0.1);
+    }
+
+    #[test]
+    fn azimuth_points_east_for_an_eastward_offset() {
+        let site = Site::new(0.0, 0.0, 0.0);
+        let when = at("2025-01-01T00:00:00Z");
+        let gmst = gmst_rad(when);
+        // ECEF: site at (a, 0, 0); target low in the east, along +y.
+        let target = rotate_z([WGS84_A_KM + 100.0, 1000.0, 0.0], gmst);
+        let g = observe(&site, when, target, [1.5e8, 0.0, 0.0]);
+        assert!((g.azimuth_rad.to_degrees() - 90.0).abs() < 1e-6);
+        assert!(g.elevation_rad > 0.0);
+        assert!(airmass(-0.1).is_none());
+    }
+}
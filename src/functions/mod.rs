@@ -1,5 +1,15 @@
+pub mod extinction;
+pub mod geometry;
 pub mod normalization;
+#[cfg(feature = "sgp4")]
+pub mod orbit;
 pub mod periodicity;
+pub mod phase_curve;
+pub mod phase_function;
 pub mod rf_normalization;
 pub mod sampling;
+pub mod shadow;
+pub mod sun;
+pub mod synodic;
+pub mod time_scale;
 pub mod vismag;
@@ -1,5 +1,7 @@
 // Functions to normalize vismag data
 
+use crate::functions::phase_function::PhaseFunction;
+
 // normalize vismag by range
 pub fn normalize_to_range(vismag: f64, obs_range: f64, std_range: f64) -> f64 {
     // range correction: 5log10(obs_range / std_range)
@@ -9,13 +11,27 @@ pub fn normalize_to_range(vismag: f64, obs_range: f64, std_range: f64) -> f64 {
 
 // normalize vismag by phase
 pub fn normalize_to_phase(vismag: f64, obs_phase: f64, std_phase: f64) -> f64 {
-    // phase correction relative to lambertian sphere
-    let phase_factor_obs = (1.0 + obs_phase.cos()) / 2.0;
-    let phase_factor_std = (1.0 + std_phase.cos()) / 2.0;
+    normalize_to_phase_with(vismag, obs_phase, std_phase, PhaseFunction::LambertApprox)
+}
+
+// normalize vismag by phase under the given phase-function model
+pub fn normalize_to_phase_with(
+    vismag: f64,
+    obs_phase: f64,
+    std_phase: f64,
+    model: PhaseFunction,
+) -> f64 {
+    let phase_factor_obs = model.phase_integral(obs_phase);
+    let phase_factor_std = model.phase_integral(std_phase);
     let phase_correction = -2.5 * (phase_factor_obs / phase_factor_std).log10();
     vismag - phase_correction
 }
 
+// remove first-order atmospheric extinction: m0 = m - k * X
+pub fn correct_extinction(vismag: f64, airmass: f64, k: f64) -> f64 {
+    vismag - k * airmass
+}
+
 // normalize vismag by both range and phase
 pub fn normalize_vismag(
     vismag: f64,
@@ -23,9 +39,28 @@ pub fn normalize_vismag(
     std_range: f64,
     obs_phase: f64,
     std_phase: f64,
+) -> f64 {
+    normalize_vismag_with(
+        vismag,
+        obs_range,
+        std_range,
+        obs_phase,
+        std_phase,
+        PhaseFunction::LambertApprox,
+    )
+}
+
+// normalize vismag by both range and phase under the given phase-function model
+pub fn normalize_vismag_with(
+    vismag: f64,
+    obs_range: f64,
+    std_range: f64,
+    obs_phase: f64,
+    std_phase: f64,
+    model: PhaseFunction,
 ) -> f64 {
     let vismag_range_normalized = normalize_to_range(vismag, obs_range, std_range);
-    normalize_to_phase(vismag_range_normalized, obs_phase, std_phase)
+    normalize_to_phase_with(vismag_range_normalized, obs_phase, std_phase, model)
 }
 
 // bulk normalization of vismag data
@@ -35,6 +70,25 @@ pub fn bulk_normalize_vismag(
     std_range: f64,
     obs_phases: &[f64],
     std_phase: f64,
+) -> Vec<f64> {
+    bulk_normalize_vismag_with(
+        vismags,
+        obs_ranges,
+        std_range,
+        obs_phases,
+        std_phase,
+        PhaseFunction::LambertApprox,
+    )
+}
+
+// bulk normalization of vismag data under the given phase-function model
+pub fn bulk_normalize_vismag_with(
+    vismags: &[f64],
+    obs_ranges: &[f64],
+    std_range: f64,
+    obs_phases: &[f64],
+    std_phase: f64,
+    model: PhaseFunction,
 ) -> Vec<f64> {
     // TODO: optimize to avoid redundant trig calcs on std values
     vismags
@@ -42,7 +96,7 @@ pub fn bulk_normalize_vismag(
         .zip(obs_ranges.iter())
         .zip(obs_phases.iter())
         .map(|((&vismag, &obs_range), &obs_phase)| {
-            normalize_vismag(vismag, obs_range, std_range, obs_phase, std_phase)
+            normalize_vismag_with(vismag, obs_range, std_range, obs_phase, std_phase, model)
         })
         .collect()
 }
\ No newline at end of file
@@ -0,0 +1,278 @@
+//! SGP4 propagation of TLE / OMM element sets (feature `sgp4`).
+//!
+//! Fills `range_m`, `phase_rad`, `elevation_rad` and `illumination` on each
+//! [`Observation`] from the propagated TEME position, and registers the mean-motion orbital
+//! period in [`PeriodSearchConfig::known_periods_s`] so `Orbital` /
+//! `OrbitalHalf` window lines are tagged without manual setup.
+
+use crate::entities::assessment::PeriodSearchConfig;
+use crate::entities::lightcurve::Lightcurve;
+use crate::entities::observation::Observation;
+use crate::functions::geometry::{Geometry, Site, observe};
+use crate::functions::normalization::normalize_vismag;
+use crate::functions::shadow::ShadowModel;
+use chrono::{DateTime, Utc};
+
+/// Relative tolerance for treating a known period as already registered.
+const KNOWN_PERIOD_REL_TOL: f64 = 1e-3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropagationError {
+    Parse(String),
+    Elements(String),
+    Propagate(String),
+}
+
+impl std::fmt::Display for PropagationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropagationError::Parse(e) => write!(f, "element set parse failed: {e}"),
+            PropagationError::Elements(e) => write!(f, "element set rejected by SGP4: {e}"),
+            PropagationError::Propagate(e) => write!(f, "SGP4 propagation failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PropagationError {}
+
+/// One element set, initialized for SGP4.
+pub struct TlePropagator {
+    elements: sgp4::Elements,
+    constants: sgp4::Constants,
+}
+
+impl TlePropagator {
+    pub fn from_elements(elements: sgp4::Elements) -> Result<Self, PropagationError> {
+        let constants = sgp4::Constants::from_elements(&elements)
+            .map_err(|e| PropagationError::Elements(e.to_string()))?;
+        Ok(Self {
+            elements,
+            constants,
+        })
+    }
+
+    pub fn from_tle(line1: &str, line2: &str) -> Result<Self, PropagationError> {
+        let elements =
+            sgp4::Elements::from_tle(None, line1.trim().as_bytes(), line2.trim().as_bytes())
+                .map_err(|e| PropagationError::Parse(e.to_string()))?;
+        Self::from_elements(elements)
+    }
+
+    /// One CCSDS OMM record in the Space-Track / CelesTrak JSON layout.
+    pub fn from_omm_json(json: &str) -> Result<Self, PropagationError> {
+        let elements: sgp4::Elements =
+            serde_json::from_str(json).map_err(|e| PropagationError::Parse(e.to_string()))?;
+        Self::from_elements(elements)
+    }
+
+    pub fn elements(&self) -> &sgp4::Elements {
+        &self.elements
+    }
+
+    /// Mean-motion (Kozai) orbital period in seconds.
+    pub fn orbital_period_s(&self) -> f64 {
+        86_400.0 / self.elements.mean_motion
+    }
+
+    /// TEME position (km) at `when`.
+    pub fn position_teme_km(&self, when: DateTime<Utc>) -> Result<[f64; 3], PropagationError> {
+        let dt = self
+            .elements
+            .datetime_to_minutes_since_epoch(&when.naive_utc())
+            .map_err(|e| PropagationError::Propagate(e.to_string()))?;
+        let pred = self
+            .constants
+            .propagate(dt)
+            .map_err(|e| PropagationError::Propagate(e.to_string()))?;
+        Ok(pred.position)
+    }
+
+    /// Geometry at `when` from `site`, with the Sun at `sun_eci_km`.
+    pub fn geometry(
+        &self,
+        site: &Site,
+        when: DateTime<Utc>,
+        sun_eci_km: [f64; 3],
+    ) -> Result<Geometry, PropagationError> {
+        Ok(observe(
+            site,
+            when,
+            self.position_teme_km(when)?,
+            sun_eci_km,
+        ))
+    }
+}
+
+/// Overwrite `range_m`, `phase_rad`, `elevation_rad` and `illumination`
+/// ([`ShadowModel::Conical`]) on every observation and recompute
+/// `std_magnitude` against `(std_range_m, std_phase_rad)`.
+/// `sun_eci_km` gives the geocentric Sun at a time, e.g.
+/// [`sun::sun_eci_km`](crate::functions::sun::sun_eci_km). Observations whose epoch
+/// fails to propagate are left untouched; returns how many were.
+pub fn fill_lightcurve_geometry(
+    lc: &mut Lightcurve,
+    propagator: &TlePropagator,
+    site: &Site,
+    sun_eci_km: impl Fn(DateTime<Utc>) -> [f64; 3],
+    std_range_m: f64,
+    std_phase_rad: f64,
+) -> usize {
+    let mut n_failed = 0;
+    for obs in &mut lc.observations {
+        let sun = sun_eci_km(obs.timestamp);
+        match propagator.position_teme_km(obs.timestamp) {
+            Ok(x) => {
+                apply_geometry(
+                    obs,
+                    &observe(site, obs.timestamp, x, sun),
+                    std_range_m,
+                    std_phase_rad,
+                );
+                obs.illumination = Some(ShadowModel::Conical.illumination(x, sun));
+            }
+            Err(_) => n_failed += 1,
+        }
+    }
+    n_failed
+}
+
+/// Add the propagator's orbital period to `known_periods_s` unless an equal
+/// period is already listed.
+pub fn register_orbital_period(config: &mut PeriodSearchConfig, propagator: &TlePropagator) {
+    let p = propagator.orbital_period_s();
+    if !(p.is_finite() && p > 0.0) {
+        return;
+    }
+    let known = config
+        .known_periods_s
+        .iter()
+        .any(|&k| (k - p).abs() <= KNOWN_PERIOD_REL_TOL * p);
+    if !known {
+        config.known_periods_s.push(p);
+    }
+}
+
+/// [`fill_lightcurve_geometry`] followed by [`register_orbital_period`].
+pub fn annotate_from_elements(
+    lc: &mut Lightcurve,
+    config: &mut PeriodSearchConfig,
+    propagator: &TlePropagator,
+    site: &Site,
+    sun_eci_km: impl Fn(DateTime<Utc>) -> [f64; 3],
+    std_range_m: f64,
+    std_phase_rad: f64,
+) -> usize {
+    let n_failed =
+        fill_lightcurve_geometry(lc, propagator, site, sun_eci_km, std_range_m, std_phase_rad);
+    register_orbital_period(config, propagator);
+    n_failed
+}
+
+fn apply_geometry(obs: &mut Observation, g: &Geometry, std_range_m: f64, std_phase_rad: f64) {
+    obs.range_m = g.range_m;
+    obs.phase_rad = g.phase_rad;
+    obs.elevation_rad = Some(g.elevation_rad);
+    obs.std_magnitude = normalize_vismag(
+        obs.vismag,
+        g.range_m,
+        std_range_m,
+        g.phase_rad,
+        std_phase_rad,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS_L1: &str = "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927";
+    const ISS_L2: &str = "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+    fn epoch() -> DateTime<Utc> {
+        // 2008 day 264.51782528.
+        DateTime::parse_from_rfc3339("2008-09-20T12:25:40.104Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn tle_period_and_leo_radius() {
+        let p = TlePropagator::from_tle(ISS_L1, ISS_L2).unwrap();
+        assert!((p.orbital_period_s() - 86_400.0 / 15.72125391).abs() < 1e-6);
+        let r = p.position_teme_km(epoch()).unwrap();
+        let rad = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+        assert!(rad > 6600.0 && rad < 6800.0, "{rad}");
+    }
+
+    #[test]
+    fn omm_json_matches_tle() {
+        let omm = r#"{
+            "OBJECT_NAME": "ISS (ZARYA)",
+            "OBJECT_ID": "1998-067A",
+            "EPOCH": "2008-09-20T12:25:40.104192",
+            "MEAN_MOTION": 15.72125391,
+            "ECCENTRICITY": 0.0006703,
+            "INCLINATION": 51.6416,
+            "RA_OF_ASC_NODE": 247.4627,
+            "ARG_OF_PERICENTER": 130.536,
+            "MEAN_ANOMALY": 325.0288,
+            "EPHEMERIS_TYPE": 0,
+            "CLASSIFICATION_TYPE": "U",
+            "NORAD_CAT_ID": 25544,
+            "ELEMENT_SET_NO": 292,
+            "REV_AT_EPOCH": 56353,
+            "BSTAR": -1.1606e-5,
+            "MEAN_MOTION_DOT": -2.182e-5,
+            "MEAN_MOTION_DDOT": 0
+        }"#;
+        let a = TlePropagator::from_omm_json(omm).unwrap();
+        let b = TlePropagator::from_tle(ISS_L1, ISS_L2).unwrap();
+        let (ra, rb) = (
+            a.position_teme_km(epoch()).unwrap(),
+            b.position_teme_km(epoch()).unwrap(),
+        );
+        let d =
+            ((ra[0] - rb[0]).powi(2) + (ra[1] - rb[1]).powi(2) + (ra[2] - rb[2]).powi(2)).sqrt();
+        assert!(d < 1.0, "{d} km");
+    }
+
+    #[test]
+    fn annotate_fills_observations_and_known_period() {
+        let prop = TlePropagator::from_tle(ISS_L1, ISS_L2).unwrap();
+        let site = Site::new(0.0, 0.0, 0.0);
+        let obs: Vec<Observation> = (0..5)
+            .map(|i| {
+                let ts = epoch() + chrono::Duration::seconds(60 * i);
+                Observation::new(5.0, 1.0, 0.0, ts, 1.0e6, std::f64::consts::FRAC_PI_2)
+            })
+            .collect();
+        let mut lc = Lightcurve::new(obs, None, None);
+        let mut cfg = PeriodSearchConfig::conservative();
+        let sun = |_t: DateTime<Utc>| [1.496e8, 0.0, 0.0];
+        let failed = annotate_from_elements(
+            &mut lc,
+            &mut cfg,
+            &prop,
+            &site,
+            sun,
+            1.0e6,
+            std::f64::consts::FRAC_PI_2,
+        );
+        assert_eq!(failed, 0);
+        for o in &lc.observations {
+            assert!(
+                o.range_m > 300.0e3 && o.range_m < 14_000.0e3,
+                "{}",
+                o.range_m
+            );
+            assert!(o.elevation_rad.is_some());
+            assert!(o.illumination.is_some());
+            assert!(o.std_magnitude.is_finite());
+        }
+        assert_eq!(cfg.known_periods_s.len(), 1);
+        register_orbital_period(&mut cfg, &prop);
+        assert_eq!(cfg.known_periods_s.len(), 1, "no duplicate registration");
+        let s = lc.to_series().unwrap();
+        assert!(s.covariates().elevation_rad.is_some());
+    }
+}
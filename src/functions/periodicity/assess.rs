@@ -1,39 +1,128 @@
 //! Pass-aware `assess_periodicity`.
 
+use crate::constants::SOLAR_DAY_S;
 use crate::entities::assessment::{
-    Alias, AliasKind, Confirmation, DetrendMode, FapMode, MethodId, PeriodSearchConfig,
-    PeriodicityAssessment, PeriodicityDecision, QualityFlags, ScoreKind, SearchScale,
+    Alias, AliasKind, Confirmation, DetrendMode, FapMode, GlintReport, MethodId, PeriodDrift,
+    PeriodSearchConfig, PeriodicityAssessment, PeriodicityDecision, QualityFlags,
+    SamplingDiagnostics, ScoreKind, SearchScale,
+};
+use crate::entities::series::{Modality, Series, T_DUP_S, YUnit};
+use crate::functions::periodicity::bls::{
+    BoxFit, bls_box, bls_independent_trials, bls_periodogram, bls_periods, fap_bls, glint_report,
 };
-use crate::entities::series::{Modality, Series};
 use crate::functions::periodicity::detrend::{auto_detrend, pass_index_lists};
+use crate::functions::periodicity::entropy::entropy_periodogram;
 use crate::functions::periodicity::fap::{
     fap_baluev, fap_from_beats, local_zero_beat, perm_seed, teff,
 };
 use crate::functions::periodicity::gls::{
     argmax, coarse_refine_periods, gls_periodogram, gls_power_zero_mean, interpolate_peak,
-    is_interior_maximum, subtract_h1,
+    is_interior_maximum, subtract_h1, top_k_peaks,
+};
+use crate::functions::periodicity::lafler_kinman::lafler_kinman_periodogram;
+use crate::functions::periodicity::multiband::{
+    extra_dof, group_keys, multiband_periodogram, multiband_power, multiband_zero_beat, n_groups,
 };
 use crate::functions::periodicity::pdm::{
-    argmin_finite, pdm_bin_count, pdm_periodogram, pdm_theta,
+    argmin_finite, fap_pdm, pdm_bin_count, pdm_fit, pdm_periodogram_covers, pdm_theta_covers,
 };
+use crate::functions::periodicity::pdot::{drift_search, warp_times};
 use crate::functions::periodicity::window::spectral_window;
 use crate::functions::sampling::{
-    diagnose_sampling, searchable_period_bounds, SamplingConfig, DEFAULT_OVERSAMPLE,
+    DEFAULT_OVERSAMPLE, SamplingConfig, diagnose_sampling, searchable_period_bounds,
 };
-use rand::rngs::StdRng;
+use crate::functions::synodic::{correction_from_phase, correction_from_vectors};
+use crate::functions::time_scale::{TimeScale, convert, tai_minus_utc};
 use rand::Rng;
 use rand::SeedableRng;
+use rand::rngs::StdRng;
 
-const BALUEV_SKIP: f64 = 0.05;
+/// Baluev FAP above which the permutation tests are skipped.
+pub(crate) const BALUEV_SKIP: f64 = 0.05;
 const CONSENSUS_REL: f64 = 0.05;
+/// Fixed-period peaks followed up by the Ṗ search.
+const DRIFT_CANDIDATES: usize = 3;
+/// Illumination at or above `1 − ECLIPSE_LIT_TOL` counts as fully lit.
+const ECLIPSE_LIT_TOL: f64 = 1.0e-6;
 
 /// Product entry point. Default methods = `[Gls, Pdm]`; FAP is Baluev on H=1.
 pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> PeriodicityAssessment {
+    assess_with(series, config, None)
+}
+
+/// [`assess_periodicity`] with unit phase-angle bisectors (see
+/// [`pab_track`](crate::functions::synodic::pab_track)), so the sidereal
+/// correction follows the full geometry instead of the phase-angle lower
+/// bound. Each bisector is keyed by its observation's time, in the series'
+/// own scale and frame: points [`Series::try_new`] merged take the mean of
+/// their bisectors, and dropped points are never looked up.
+pub fn assess_periodicity_with_pab(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    pab: &[(f64, [f64; 3])],
+) -> PeriodicityAssessment {
+    assess_with(series, config, Some(&pab_at(series.t_s(), pab)))
+}
+
+/// Bisector at each of `t`: the normalised sum of those keyed within
+/// [`T_DUP_S`], or NaN where there is none.
+fn pab_at(t: &[f64], pab: &[(f64, [f64; 3])]) -> Vec<[f64; 3]> {
+    let mut keyed = pab.to_vec();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    t.iter()
+        .map(|&ti| {
+            let lo = keyed.partition_point(|k| k.0 < ti - T_DUP_S);
+            let hi = keyed.partition_point(|k| k.0 <= ti + T_DUP_S);
+            let sum = keyed[lo..hi].iter().fold([0.0; 3], |s, (_, v)| {
+                [s[0] + v[0], s[1] + v[1], s[2] + v[2]]
+            });
+            let norm = (sum[0] * sum[0] + sum[1] * sum[1] + sum[2] * sum[2]).sqrt();
+            if norm > 0.0 {
+                sum.map(|c| c / norm)
+            } else {
+                [f64::NAN; 3]
+            }
+        })
+        .collect()
+}
+
+fn assess_with(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    pab: Option<&[[f64; 3]]>,
+) -> PeriodicityAssessment {
     if let Err(e) = config.validate() {
         let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
         a.notes.push(format!("config: {e}"));
         return a;
     }
+    let own = series.meta().time_scale;
+    let scale = search_time_scale(series, config);
+    if scale == own {
+        return assess_scaled(series, config, pab);
+    }
+    match series.to_time_scale(scale) {
+        Ok(s) => {
+            let mut a = assess_scaled(&s, config, pab);
+            retime(&mut a, scale, own);
+            a.notes
+                .push(format!("searched in {scale}; times reported in {own}"));
+            a
+        }
+        Err(e) => {
+            let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
+            a.notes.push(format!("time scale {scale}: {e}"));
+            a
+        }
+    }
+}
+
+/// [`assess_periodicity`] on a series already in the search time scale.
+fn assess_scaled(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    pab: Option<&[[f64; 3]]>,
+) -> PeriodicityAssessment {
     if series.len() < 12 {
         let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
         a.notes.push(format!("QC: n={} < 12", series.len()));
@@ -41,18 +130,8 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
         return a;
     }
 
-    let h = config
-        .n_harmonics
-        .unwrap_or(if series.meta().modality == Modality::RfPower {
-            1
-        } else {
-            2
-        });
-    let oversample = if config.oversample >= 1.0 {
-        config.oversample
-    } else {
-        DEFAULT_OVERSAMPLE
-    };
+    let h = search_harmonics(series, config);
+    let oversample = search_oversample(config);
 
     let samp_cfg = sampling_cfg(series, config);
     let sampling = diagnose_sampling(series, &samp_cfg, oversample);
@@ -64,15 +143,9 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
     }
 
     let n_pass = sampling.n_passes;
-    let eligible: Vec<usize> = sampling
-        .passes
-        .iter()
-        .enumerate()
-        .filter(|(_, p)| p.is_intra_eligible())
-        .map(|(i, _)| i)
-        .collect();
+    let eligible = intra_eligible(&sampling);
 
-    match (config.scale, n_pass) {
+    let mut a = match (config.scale, n_pass) {
         (SearchScale::Auto, 1) | (SearchScale::IntraPass, 1) => {
             if eligible.is_empty() {
                 let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
@@ -81,7 +154,7 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
                     .push("single pass too short/sparse for intra".into());
                 return a;
             }
-            assess_intra(series, config, &sampling, &eligible, h, oversample)
+            assess_intra(series, config, &sampling, eligible[0], h, oversample)
         }
         (SearchScale::Auto, 2) | (SearchScale::IntraPass, 2..) => {
             if eligible.is_empty() {
@@ -92,7 +165,7 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
             }
             let mut results: Vec<PeriodicityAssessment> = eligible
                 .iter()
-                .map(|&i| assess_intra(series, config, &sampling, &[i], h, oversample))
+                .map(|&i| assess_intra(series, config, &sampling, i, h, oversample))
                 .collect();
             consensus_intra(&mut results, &sampling)
         }
@@ -105,18 +178,33 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
             } else {
                 let mut results: Vec<PeriodicityAssessment> = eligible
                     .iter()
-                    .map(|&i| assess_intra(series, config, &sampling, &[i], h, oversample))
+                    .map(|&i| assess_intra(series, config, &sampling, i, h, oversample))
                     .collect();
                 consensus_intra(&mut results, &sampling)
             };
+            // Ṗ only shows across passes: with it enabled, a coherent Full
+            // search still runs and wins when it fits a drift.
+            let drifting = || {
+                let full = assess_full(series, config, &sampling, h, oversample);
+                (full.decision == PeriodicityDecision::Periodic && full.drift.is_some())
+                    .then_some(full)
+            };
             if intra.decision == PeriodicityDecision::Periodic {
-                return intra;
+                match (config.pdot.max_rel_per_day > 0.0).then(drifting).flatten() {
+                    Some(mut full) => {
+                        full.notes
+                            .insert(0, "Auto≥3: Intra Periodic; Full Ṗ fit preferred".into());
+                        full
+                    }
+                    None => intra,
+                }
+            } else {
+                let mut inter = assess_inter(series, config, &sampling, h, oversample, false);
+                inter
+                    .notes
+                    .insert(0, "Auto≥3: Intra not Periodic; Inter leg".into());
+                inter
             }
-            let mut inter = assess_inter(series, config, &sampling, h, oversample, false);
-            inter
-                .notes
-                .insert(0, "Auto≥3: Intra not Periodic; Inter leg".into());
-            inter
         }
         (SearchScale::InterPass, 1) => {
             let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
@@ -134,10 +222,100 @@ pub fn assess_periodicity(series: &Series, config: &PeriodSearchConfig) -> Perio
             a.notes.push("unhandled (scale, n_passes) cell".into());
             a
         }
+    };
+    if let (PeriodicityDecision::Periodic, Some(p)) = (a.decision, a.period_s) {
+        let passes = &a.sampling.passes;
+        a.sidereal = match pab {
+            Some(pab) if pab.iter().any(|v| v[0].is_nan()) => {
+                a.notes.push(format!(
+                    "pab: no bisector for {} of {} points",
+                    pab.iter().filter(|v| v[0].is_nan()).count(),
+                    series.len()
+                ));
+                None
+            }
+            Some(pab) => correction_from_vectors(series.t_s(), pab, passes, p, a.period_unc_s),
+            None => correction_from_phase(series, passes, p, a.period_unc_s),
+        };
+    }
+    a
+}
+
+/// Scale the search counts time in: `config.time_scale`, else TAI for a UTC
+/// series whose span crosses a leap second, else the series' own scale.
+pub(crate) fn search_time_scale(series: &Series, config: &PeriodSearchConfig) -> TimeScale {
+    let own = series.meta().time_scale;
+    config
+        .time_scale
+        .unwrap_or_else(|| match (own, series.t_s()) {
+            (TimeScale::Utc, [first, .., last])
+                if tai_minus_utc(*first) != tai_minus_utc(*last) =>
+            {
+                TimeScale::Tai
+            }
+            _ => own,
+        })
+}
+
+/// Re-count the absolute times of `a` from `from` into `to`.
+fn retime(a: &mut PeriodicityAssessment, from: TimeScale, to: TimeScale) {
+    for p in &mut a.sampling.passes {
+        p.t_start_s = convert(p.t_start_s, from, to);
+        p.t_end_s = convert(p.t_end_s, from, to);
+    }
+    if let Some(d) = &mut a.drift {
+        d.epoch_s = convert(d.epoch_s, from, to);
+    }
+    if let Some(g) = &mut a.glint {
+        g.epoch_s = convert(g.epoch_s, from, to);
+        for e in &mut g.events {
+            e.t_s = convert(e.t_s, from, to);
+        }
+    }
+}
+
+/// The sampling diagnostics [`assess_periodicity`] computes for `series`
+/// under `config`, without running the search.
+pub fn assess_sampling(series: &Series, config: &PeriodSearchConfig) -> SamplingDiagnostics {
+    diagnose_sampling(
+        series,
+        &sampling_cfg(series, config),
+        search_oversample(config),
+    )
+}
+
+/// Passes long and dense enough for their own search, in time order.
+pub(crate) fn intra_eligible(sampling: &SamplingDiagnostics) -> Vec<usize> {
+    sampling
+        .passes
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.is_intra_eligible())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Harmonics of the primary statistic: `n_harmonics`, else 1 for RF power
+/// and 2 for optical.
+pub(crate) fn search_harmonics(series: &Series, config: &PeriodSearchConfig) -> usize {
+    config
+        .n_harmonics
+        .unwrap_or(if series.meta().modality == Modality::RfPower {
+            1
+        } else {
+            2
+        })
+}
+
+pub(crate) fn search_oversample(config: &PeriodSearchConfig) -> f64 {
+    if config.oversample >= 1.0 {
+        config.oversample
+    } else {
+        DEFAULT_OVERSAMPLE
     }
 }
 
-fn sampling_cfg(series: &Series, config: &PeriodSearchConfig) -> SamplingConfig {
+pub(crate) fn sampling_cfg(series: &Series, config: &PeriodSearchConfig) -> SamplingConfig {
     let span = series.span_s().unwrap_or(0.0);
     let pmin = config.min_period_s.unwrap_or(3.0);
     let pmax = config.max_period_s.unwrap_or((span / 2.0).max(pmin * 2.0));
@@ -154,25 +332,47 @@ fn sampling_cfg(series: &Series, config: &PeriodSearchConfig) -> SamplingConfig
     }
 }
 
-fn assess_intra(
+/// Detrended inputs for a search over the points of `pass_ids`: one pass
+/// is searched at [`SearchScale::IntraPass`], several pooled at
+/// [`SearchScale::Full`] within the window.
+pub(crate) struct PassWindow {
+    pub idx: Vec<usize>,
+    pub t: Vec<f64>,
+    pub y: Vec<f64>,
+    pub w: Vec<f64>,
+    pub scale: SearchScale,
+    pub p_min: f64,
+    pub p_max: f64,
+    pub span_s: f64,
+    pub detrend: crate::entities::assessment::DetrendReport,
+}
+
+pub(crate) fn pass_window(
     series: &Series,
     config: &PeriodSearchConfig,
     sampling: &crate::entities::assessment::SamplingDiagnostics,
     pass_ids: &[usize],
-    h: usize,
-    oversample: f64,
-) -> PeriodicityAssessment {
+) -> PassWindow {
     let t = series.t_s();
     let lists = pass_index_lists(t, &sampling.passes);
-    let pid = pass_ids[0];
-    let idx = &lists[pid];
-    let pass = &sampling.passes[pid];
+    let idx: Vec<usize> = pass_ids
+        .iter()
+        .flat_map(|&p| lists[p].iter().copied())
+        .collect();
+    let passes: Vec<&crate::entities::assessment::Pass> =
+        pass_ids.iter().map(|&p| &sampling.passes[p]).collect();
+    let scale = if pass_ids.len() == 1 {
+        SearchScale::IntraPass
+    } else {
+        SearchScale::Full
+    };
     let dt = auto_detrend(
         series,
         &sampling.passes,
-        SearchScale::IntraPass,
+        scale,
         config.detrend,
-        Some(idx),
+        config.phase_function,
+        Some(&idx),
     );
     let t_s: Vec<f64> = idx.iter().map(|&i| t[i]).collect();
     let w: Vec<f64> = {
@@ -186,34 +386,83 @@ fn assess_intra(
     let min_dt = if min_dt.is_finite() {
         min_dt
     } else {
-        pass.median_dt_s
+        passes
+            .iter()
+            .map(|p| p.median_dt_s)
+            .fold(f64::INFINITY, f64::min)
+    };
+    let span = match (passes.first(), passes.last()) {
+        (Some(a), Some(b)) => (b.t_end_s - a.t_start_s).max(0.0),
+        _ => 0.0,
     };
     // Irregular sampling: the Eyer–Bartholdi bound is 2·min Δt, not 2·median.
     let p_min = config.min_period_s.unwrap_or(3.0).max(2.0 * min_dt);
     let p_max = config
         .max_period_s
         .unwrap_or(f64::INFINITY)
-        .min(0.5 * pass.duration_s())
+        .min(0.5 * span)
         .min(sampling.span_s / 2.0);
+    PassWindow {
+        idx,
+        t: t_s,
+        y: dt.y,
+        w,
+        scale,
+        p_min,
+        p_max,
+        span_s: span.max(1.0),
+        detrend: dt.report,
+    }
+}
+
+/// Search and decide on one [`PassWindow`]. No pass-block test: the
+/// window's points are a subset of the series.
+pub(crate) fn search_window(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    sampling: &crate::entities::assessment::SamplingDiagnostics,
+    win: &PassWindow,
+    h: usize,
+    oversample: f64,
+) -> PeriodicityAssessment {
     search_and_decide(
         series,
         config,
         sampling,
-        &t_s,
-        &dt.y,
-        &w,
-        p_min,
-        p_max,
-        pass.duration_s().max(1.0),
-        dt.report.n_beta,
-        SearchScale::IntraPass,
+        &win.t,
+        &win.y,
+        &win.w,
+        win.p_min,
+        win.p_max,
+        win.span_s,
+        win.detrend.n_beta,
+        win.scale,
         h,
         oversample,
-        dt.report,
+        win.detrend.clone(),
         None,
+        group_keys(series).map(|g| win.idx.iter().map(|&i| g[i]).collect()),
+        series
+            .covariates()
+            .illumination
+            .as_deref()
+            .map(|f| win.idx.iter().map(|&i| f[i]).collect()),
+        1.0,
     )
 }
 
+fn assess_intra(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    sampling: &crate::entities::assessment::SamplingDiagnostics,
+    pass_id: usize,
+    h: usize,
+    oversample: f64,
+) -> PeriodicityAssessment {
+    let win = pass_window(series, config, sampling, &[pass_id]);
+    search_window(series, config, sampling, &win, h, oversample)
+}
+
 fn assess_inter(
     series: &Series,
     config: &PeriodSearchConfig,
@@ -227,6 +476,7 @@ fn assess_inter(
         &sampling.passes,
         SearchScale::InterPass,
         config.detrend,
+        config.phase_function,
         None,
     );
     let (p_min, p_max) = searchable_period_bounds(
@@ -243,23 +493,31 @@ fn assess_inter(
         median_gap(sampling),
         sampling.n_passes,
     );
-    let mut a = search_and_decide(
-        series,
-        config,
-        sampling,
-        series.t_s(),
-        &dt.y,
-        &series.weights(),
-        p_min,
-        p_max,
-        sampling.span_s,
-        dt.report.n_beta,
-        SearchScale::InterPass,
-        h,
-        oversample,
-        dt.report,
-        Some(dt.y.clone()),
-    );
+    let w = series.weights();
+    let run = |t: &[f64], look_trials: f64| {
+        search_and_decide(
+            series,
+            config,
+            sampling,
+            t,
+            &dt.y,
+            &w,
+            p_min,
+            p_max,
+            sampling.span_s,
+            dt.report.n_beta,
+            SearchScale::InterPass,
+            h,
+            oversample,
+            dt.report.clone(),
+            Some(dt.y.clone()),
+            group_keys(series).map(<[u16]>::to_vec),
+            series.covariates().illumination.clone(),
+            look_trials,
+        )
+    };
+    let fixed = run(series.t_s(), 1.0);
+    let mut a = with_drift(fixed, config, series.t_s(), &dt.y, &w, oversample, run);
     a.quality.undersampled = undersampled;
     a
 }
@@ -276,6 +534,7 @@ fn assess_full(
         &sampling.passes,
         SearchScale::Full,
         config.detrend,
+        config.phase_function,
         None,
     );
     let p_min = config
@@ -286,23 +545,107 @@ fn assess_full(
         .max_period_s
         .unwrap_or(sampling.span_s / 2.0)
         .min(sampling.span_s / 2.0);
-    search_and_decide(
-        series,
-        config,
-        sampling,
-        series.t_s(),
-        &dt.y,
-        &series.weights(),
-        p_min,
-        p_max,
-        sampling.span_s,
-        dt.report.n_beta,
-        SearchScale::Full,
-        h,
+    let w = series.weights();
+    let run = |t: &[f64], look_trials: f64| {
+        search_and_decide(
+            series,
+            config,
+            sampling,
+            t,
+            &dt.y,
+            &w,
+            p_min,
+            p_max,
+            sampling.span_s,
+            dt.report.n_beta,
+            SearchScale::Full,
+            h,
+            oversample,
+            dt.report.clone(),
+            Some(dt.y.clone()),
+            group_keys(series).map(<[u16]>::to_vec),
+            series.covariates().illumination.clone(),
+            look_trials,
+        )
+    };
+    let fixed = run(series.t_s(), 1.0);
+    with_drift(fixed, config, series.t_s(), &dt.y, &w, oversample, run)
+}
+
+/// (P, Ṗ) follow-up of a fixed-period search `fixed`. Candidates are the top
+/// peaks of the primary's periodogram, but κ = Ṗ/P is always picked by H=1
+/// GLS, whatever the primary: its power gives the χ² profile behind the κ
+/// uncertainty, which θ and box power do not. The best κ is then re-run
+/// through `run`, so the decision on warped times uses the primary's own
+/// statistic, with the κ grid charged as extra trials. The drift result
+/// replaces `fixed` when it is Periodic and either `fixed` is not or its
+/// Baluev FAP is lower.
+fn with_drift(
+    fixed: PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    oversample: f64,
+    run: impl Fn(&[f64], f64) -> PeriodicityAssessment,
+) -> PeriodicityAssessment {
+    if config.pdot.max_rel_per_day <= 0.0 {
+        return fixed;
+    }
+    let pg = &fixed.periodogram;
+    // Best-first on θ means lowest first.
+    let score: Vec<f64> = match pg.score_kind {
+        ScoreKind::PdmTheta => pg.score.iter().map(|s| -s).collect(),
+        _ => pg.score.clone(),
+    };
+    let candidates = top_k_peaks(&pg.period_s, &score, DRIFT_CANDIDATES);
+    let Some(fit) = drift_search(
+        t,
+        y,
+        w,
+        &candidates,
+        config.pdot.max_rel_per_day / SOLAR_DAY_S,
         oversample,
-        dt.report,
-        Some(dt.y.clone()),
-    )
+        config.pdot.max_trials,
+    ) else {
+        return fixed;
+    };
+    let mut a = run(
+        &warp_times(t, fit.epoch_s, fit.rate),
+        (fit.n_trials * candidates.len()) as f64,
+    );
+    let better = match (fixed.fap_baluev.or(fixed.fap), a.fap_baluev.or(a.fap)) {
+        (Some(f), Some(d)) => d < f,
+        _ => false,
+    };
+    let p = match (a.decision, a.period_s) {
+        (PeriodicityDecision::Periodic, Some(p))
+            if fixed.decision != PeriodicityDecision::Periodic || better =>
+        {
+            p
+        }
+        _ => {
+            let mut fixed = fixed;
+            fixed.notes.push(format!(
+                "Ṗ search: no gain (Ṗ/P={:.3e}/s over {} trials)",
+                fit.rate, fit.n_trials
+            ));
+            return fixed;
+        }
+    };
+    // Glint times from the warped run are not series times.
+    a.glint = fixed.glint;
+    a.fap_bls = fixed.fap_bls;
+    a.drift = Some(PeriodDrift {
+        pdot: fit.rate * p,
+        pdot_unc: fit.rate_unc.map(|u| u * p),
+        epoch_s: fit.epoch_s,
+    });
+    a.notes.push(format!(
+        "Ṗ search: P={p:.4} s at t₀={:.0} s, Ṗ/P={:.3e}/s over {} trials",
+        fit.epoch_s, fit.rate, fit.n_trials
+    ));
+    a
 }
 
 fn median_pass_dur(s: &crate::entities::assessment::SamplingDiagnostics) -> f64 {
@@ -327,6 +670,7 @@ fn median_gap(s: &crate::entities::assessment::SamplingDiagnostics) -> f64 {
     g[g.len() / 2]
 }
 
+#[allow(clippy::too_many_arguments)]
 fn search_and_decide(
     series: &Series,
     config: &PeriodSearchConfig,
@@ -343,8 +687,15 @@ fn search_and_decide(
     oversample: f64,
     detrend: crate::entities::assessment::DetrendReport,
     y_for_block: Option<Vec<f64>>,
+    groups: Option<Vec<u16>>,
+    illumination: Option<Vec<f64>>,
+    look_trials: f64,
 ) -> PeriodicityAssessment {
     let mut notes = Vec::new();
+    // Multiband replaces single-band GLS only when ≥ 2 groups are in scope.
+    let groups =
+        groups.filter(|g| config.methods.contains(&MethodId::MultibandGls) && n_groups(g) >= 2);
+    let n_band = config.multiband.n_band;
     if p_min <= 0.0 || p_max <= p_min || t.len() < 12 {
         let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
         a.sampling = sampling.clone();
@@ -354,7 +705,10 @@ fn search_and_decide(
         return a;
     }
     let n_eff = t.len() as f64 - n_beta as f64;
-    let nu = n_eff - 2.0;
+    let nu = match &groups {
+        Some(g) => n_eff - 2.0 - extra_dof(n_groups(g), 1, n_band.min(1)) as f64,
+        None => n_eff - 2.0,
+    };
     if nu < 2.0 {
         let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
         a.sampling = sampling.clone();
@@ -362,13 +716,76 @@ fn search_and_decide(
         a.notes.push(format!("QC: ν={nu} < 2"));
         return a;
     }
+    if config.methods.first() == Some(&MethodId::Pdm) {
+        if groups.is_some() {
+            notes.push("PDM primary pools band groups".into());
+        }
+        let mut a = pdm_primary(
+            series,
+            config,
+            sampling,
+            t,
+            y,
+            w,
+            p_min,
+            p_max,
+            span_s,
+            n_beta,
+            nu,
+            oversample,
+            illumination.as_deref(),
+            look_trials,
+        );
+        a.detrend = detrend;
+        notes.append(&mut a.notes);
+        a.notes = notes;
+        attach_glint(
+            &mut a,
+            series,
+            config,
+            t,
+            y,
+            w,
+            p_min,
+            p_max,
+            span_s,
+            n_beta,
+            look_trials,
+        );
+        return a;
+    }
+    if config.methods.first() == Some(&MethodId::Bls) {
+        if groups.is_some() {
+            notes.push("BLS primary pools band groups".into());
+        }
+        let mut a = bls_primary(
+            series,
+            config,
+            sampling,
+            t,
+            y,
+            w,
+            p_min,
+            p_max,
+            span_s,
+            n_beta,
+            nu,
+            illumination.as_deref(),
+            look_trials,
+        );
+        a.detrend = detrend;
+        notes.append(&mut a.notes);
+        a.notes = notes;
+        return a;
+    }
 
-    let eval_h = |periods: &[f64]| {
-        periods
-            .iter()
-            .map(|&p| gls_power_zero_mean(t, y, w, p, h))
-            .collect::<Vec<_>>()
+    // Primary statistic at `hh` harmonics: zero-mean GLS, or multiband GLS
+    // with per-group terms capped at `hh`.
+    let power = |yy: &[f64], p: f64, hh: usize| match &groups {
+        Some(g) => multiband_power(t, yy, w, g, p, hh, n_band.min(hh)),
+        None => gls_power_zero_mean(t, yy, w, p, hh),
     };
+    let eval_h = |periods: &[f64]| periods.iter().map(|&p| power(y, p, h)).collect::<Vec<_>>();
     let (periods, scores_h) = coarse_refine_periods(
         p_min,
         p_max,
@@ -384,11 +801,14 @@ fn search_and_decide(
         score: scores_h.clone(),
         score_kind: ScoreKind::GlsPower,
     };
-    let pgram_1 = if h == 1 {
-        pgram_h.clone()
-    } else {
-        gls_periodogram(t, y, w, &periods, 1, true)
+    let pgram_1 = match &groups {
+        _ if h == 1 => pgram_h.clone(),
+        Some(g) => multiband_periodogram(t, y, w, g, &periods, 1, n_band.min(1)),
+        None => gls_periodogram(t, y, w, &periods, 1, true),
     };
+    if let Some(g) = &groups {
+        notes.push(format!("multiband GLS over {} groups", n_groups(g)));
+    }
 
     let Some(idx_1) = argmax(&pgram_1.score) else {
         let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
@@ -404,7 +824,7 @@ fn search_and_decide(
     if series.meta().modality != crate::entities::series::Modality::RfPower {
         if let Some(ih) = idx_h {
             let p_h = pgram_h.period_s[ih];
-            let p1_at_h = gls_power_zero_mean(t, y, w, p_h, 1);
+            let p1_at_h = power(y, p_h, 1);
             if (p_h - 2.0 * p_star).abs() / p_star.max(1e-12) < 0.05
                 && p1_at_h > 0.5 * pgram_1.score[idx_1]
                 && pgram_1.score[idx_1] > 0.1
@@ -418,14 +838,23 @@ fn search_and_decide(
         let two = 2.0 * p_star;
         if two >= p_min && two <= p_max {
             let m_tmp = pdm_bin_count(t.len(), config.pdm.m_bins);
-            let pdm_prefers_two =
-                match (pdm_theta(t, y, p_star, m_tmp), pdm_theta(t, y, two, m_tmp)) {
+            // Pooled PDM and the single-band residual cannot see per-group
+            // phase; with multiband only the extra-harmonic test applies.
+            let pdm_prefers_two = groups.is_none()
+                && match (
+                    pdm_theta_covers(t, y, p_star, m_tmp, config.pdm.covers),
+                    pdm_theta_covers(t, y, two, m_tmp, config.pdm.covers),
+                ) {
                     (Some(th1), Some(th2)) => th2 <= th1 * 1.15,
                     _ => false,
                 };
-            let resid = subtract_h1(t, y, w, p_star);
-            let p1_resid_two = gls_power_zero_mean(t, &resid, w, two, 1);
-            let p2_here = gls_power_zero_mean(t, y, w, p_star, 2);
+            let p1_resid_two = if groups.is_none() {
+                let resid = subtract_h1(t, y, w, p_star);
+                gls_power_zero_mean(t, &resid, w, two, 1)
+            } else {
+                0.0
+            };
+            let p2_here = power(y, p_star, 2);
             let extra_h = p2_here - pgram_1.score[idx_1];
             // Narrow flashes have extra H=2 power at P/2; a pure sine does not.
             if pdm_prefers_two || p1_resid_two > 0.05 || extra_h > 0.03 {
@@ -446,16 +875,42 @@ fn search_and_decide(
     let p1_max = pgram_1.score.iter().copied().fold(0.0_f64, f64::max);
     let f_max = 1.0 / p_min;
     let te = teff(t, w);
-    let fap_b = fap_baluev(p1_max, nu, te, f_max);
+    // Extra trials (e.g. the Ṗ grid) scale the Baluev bound.
+    let fap_b = (fap_baluev(p1_max, nu, te, f_max) * look_trials).min(1.0);
+    // Baluev's bound is derived for a single-band sinusoid. The multiband
+    // statistic has no closed-form tail, so its look-elsewhere FAP always
+    // comes from the permutation maximum over the grid.
+    let baluev_valid = groups.is_none();
+    if !baluev_valid {
+        notes.push("multiband: look-elsewhere FAP from permutation maximum".into());
+    }
 
     if !interior {
         notes.push("bound-snap: peak is not an interior maximum".into());
     }
 
     let df_local = local_df(&pgram_1.period_s, idx_1);
-    let (vetoed, aliases) = window_veto(
+    let (vetoed, mut aliases) = window_veto(
         p_star, p1_at_star, sampling, config, df_local, t, y, w, nu, te, f_max, &mut notes,
     );
+    let eclipse = illumination.as_deref().and_then(|f| {
+        eclipse_veto(p_star, config, f, &mut notes, |lit| {
+            gls_lit_score(
+                p_star,
+                config,
+                t,
+                y,
+                w,
+                groups.as_deref(),
+                n_band,
+                n_beta,
+                f_max,
+                lit,
+            )
+        })
+    });
+    let eclipsed = eclipse.as_ref().is_some_and(|al| al.vetoed);
+    aliases.extend(eclipse);
 
     let mut n_beat_perm = 0usize;
     let mut fap_perm = None;
@@ -470,7 +925,32 @@ fn search_and_decide(
         }
     };
 
-    if fap_b > BALUEV_SKIP && config.fap_mode == FapMode::BaluevH1 {
+    let zero_beat = |periods: &[f64], data_score: f64| match &groups {
+        Some(g) => multiband_zero_beat(
+            t,
+            y,
+            w,
+            g,
+            periods,
+            data_score,
+            1,
+            n_band.min(1),
+            config.n_permutations,
+            config.rng_seed,
+        ),
+        None => local_zero_beat(
+            t,
+            y,
+            w,
+            periods,
+            data_score,
+            1,
+            config.n_permutations,
+            config.rng_seed,
+        ),
+    };
+
+    if baluev_valid && fap_b > BALUEV_SKIP && config.fap_mode == FapMode::BaluevH1 {
         notes.push(format!(
             "Baluev FAP={fap_b:.3} > {BALUEV_SKIP} (cheap reject)"
         ));
@@ -479,16 +959,7 @@ fn search_and_decide(
             .into_iter()
             .filter(|p| *p >= p_min && *p <= p_max)
             .collect::<Vec<_>>();
-        n_beat_perm = local_zero_beat(
-            t,
-            y,
-            w,
-            &extras,
-            p1_at_star,
-            1,
-            config.n_permutations,
-            config.rng_seed,
-        );
+        n_beat_perm = zero_beat(&extras, p1_at_star);
         fap_perm = Some(fap_from_beats(n_beat_perm, config.n_permutations));
 
         let use_block = matches!(scale, SearchScale::InterPass | SearchScale::Full)
@@ -498,8 +969,11 @@ fn search_and_decide(
             n_beat_block = pass_block_beats(
                 series,
                 sampling,
+                t,
                 y_for_block.as_deref().unwrap(),
                 w,
+                groups.as_deref(),
+                n_band,
                 p_star,
                 p1_at_star,
                 config.n_permutations,
@@ -509,21 +983,12 @@ fn search_and_decide(
         }
     }
 
-    let look = if config.fap_mode == FapMode::PermMax {
+    let look = if config.fap_mode == FapMode::PermMax || !baluev_valid {
         // perm-max over a thinned grid
         let thin_n = 500.min(pgram_1.len()).max(8);
         let step = (pgram_1.len() / thin_n).max(1);
         let thin: Vec<f64> = pgram_1.period_s.iter().step_by(step).copied().collect();
-        let n_beat = local_zero_beat(
-            t,
-            y,
-            w,
-            &thin,
-            p1_max,
-            1,
-            config.n_permutations,
-            config.rng_seed,
-        );
+        let n_beat = zero_beat(&thin, p1_max);
         fap_from_beats(n_beat, config.n_permutations)
     } else {
         look_elsewhere
@@ -532,44 +997,80 @@ fn search_and_decide(
     let block_ok = matches!(scale, SearchScale::IntraPass)
         || sampling.n_passes < 3
         || n_beat_block == 0
-        || fap_b > BALUEV_SKIP;
+        || (baluev_valid && fap_b > BALUEV_SKIP);
 
-    let gls_ok = interior && !vetoed && look < config.fap_threshold && n_beat_perm == 0 && block_ok;
+    let gls_ok = interior
+        && !vetoed
+        && !eclipsed
+        && look < config.fap_threshold
+        && n_beat_perm == 0
+        && block_ok;
 
     if vetoed {
         notes.push(format!("window/alias veto at P={p_star:.4}"));
     }
 
-    let wants_pdm = config.methods.contains(&MethodId::Pdm);
+    // The first phase-folding estimator in `methods` confirms the period;
+    // any later one is not run.
+    let mut folding = config.methods.iter().copied().filter(|m| {
+        matches!(
+            m,
+            MethodId::Pdm | MethodId::ConditionalEntropy | MethodId::LaflerKinman
+        )
+    });
+    let confirmer = folding.next();
+    if let Some(c) = confirmer {
+        for m in folding {
+            notes.push(format!(
+                "{} not run: {} already confirms",
+                confirmer_label(m),
+                confirmer_label(c)
+            ));
+        }
+    }
     let mut confirmation = None;
-    let mut agreement = if config.methods.len() <= 1 {
-        Some(true)
-    } else if !wants_pdm {
+    let mut agreement = if config.methods.len() <= 1 || confirmer.is_none() {
         Some(true)
     } else {
         None
     };
-    let mut pdm_inconclusive = false;
+    let mut confirm_inconclusive = false;
+    let mut fap_p = None;
 
-    if wants_pdm && config.methods.len() > 1 {
+    if let Some(method) = confirmer.filter(|_| config.methods.len() > 1) {
+        let label = confirmer_label(method);
         let m = pdm_bin_count(t.len(), config.pdm.m_bins);
-        let pgram_pdm = pdm_periodogram(t, y, &pgram_1.period_s, m);
-        match argmin_finite(&pgram_pdm.score) {
+        let pgram_c = confirmer_periodogram(config, t, y, &pgram_1.period_s, method);
+        match argmin_finite(&pgram_c.score) {
             None => {
-                notes.push("PDM occupancy: no valid trial".into());
+                notes.push(format!("{label} occupancy: no valid trial"));
                 if config.require_method_agreement {
-                    pdm_inconclusive = true;
+                    confirm_inconclusive = true;
                 }
                 agreement = None;
             }
             Some(ip) => {
-                let p_pdm = pgram_pdm.period_s[ip];
-                let th = pgram_pdm.score[ip];
+                let p_c = pgram_c.period_s[ip];
+                let th = pgram_c.score[ip];
+                if method == MethodId::Pdm {
+                    fap_p = Some(pdm_fap(
+                        t,
+                        y,
+                        p_c,
+                        m,
+                        config.pdm.covers,
+                        n_beta,
+                        span_s,
+                        p_min,
+                        p_max,
+                        look_trials,
+                    ));
+                }
                 let optical = series.meta().modality != crate::entities::series::Modality::RfPower;
-                let agree = periods_agree(p_star, p_pdm, optical);
+                let agree = periods_agree(p_star, p_c, optical);
                 agreement = Some(agree);
                 if agree && optical {
-                    let longer = p_star.max(p_pdm);
+                    let longer = p_star.max(p_c);
                     if (longer - p_star).abs() / p_star.max(1e-12) > 0.02 {
                         notes.push(format!(
                             "optical 2:1/agreement: report longer {p_star:.4} → {longer:.4}"
@@ -578,15 +1079,17 @@ fn search_and_decide(
                     }
                 }
                 confirmation = Some(Confirmation {
-                    method: MethodId::Pdm,
-                    period_s: p_pdm,
+                    method,
+                    period_s: p_c,
                     score: th,
                     agrees: agree,
                 });
                 if !agree {
-                    notes.push(format!("GLS–PDM disagree: GLS={p_star:.4} PDM={p_pdm:.4}"));
+                    notes.push(format!(
+                        "GLS–{label} disagree: GLS={p_star:.4} {label}={p_c:.4}"
+                    ));
                     if config.require_method_agreement && gls_ok && look < config.fap_threshold {
-                        pdm_inconclusive = true;
+                        confirm_inconclusive = true;
                     }
                 }
             }
@@ -595,7 +1098,7 @@ fn search_and_decide(
 
     let agreement_ok = !config.require_method_agreement || agreement.unwrap_or(false);
 
-    let decision = if pdm_inconclusive {
+    let decision = if confirm_inconclusive {
         PeriodicityDecision::Inconclusive
     } else if gls_ok && agreement_ok {
         PeriodicityDecision::Periodic
@@ -614,7 +1117,7 @@ fn search_and_decide(
         bound_snap: !interior,
     };
 
-    PeriodicityAssessment {
+    let mut a = PeriodicityAssessment {
         decision,
         period_s: if decision == PeriodicityDecision::Periodic {
             Some(p_star)
@@ -629,7 +1132,9 @@ fn search_and_decide(
         fap: Some(look),
         fap_perm,
         fap_block,
-        fap_baluev: Some(fap_b),
+        fap_baluev: baluev_valid.then_some(fap_b),
+        fap_pdm: fap_p,
+        fap_bls: None,
         score: score_h,
         score_kind: ScoreKind::GlsPower,
         aliases,
@@ -637,20 +1142,588 @@ fn search_and_decide(
         periodogram: pgram_1,
         periodogram_h2: if h > 1 { Some(pgram_h) } else { None },
         confirmation,
+        sidereal: None,
+        drift: None,
+        glint: None,
         sampling: sampling.clone(),
         detrend,
-        method: MethodId::Gls,
+        method: if groups.is_some() {
+            MethodId::MultibandGls
+        } else {
+            MethodId::Gls
+        },
         notes,
-    }
+    };
+    attach_glint(
+        &mut a,
+        series,
+        config,
+        t,
+        y,
+        w,
+        p_min,
+        p_max,
+        span_s,
+        n_beta,
+        look_trials,
+    );
+    a
 }
 
-fn periods_agree(p1: f64, p2: f64, optical: bool) -> bool {
-    let lo = p1.min(p2);
-    let hi = p1.max(p2);
-    if lo <= 0.0 {
-        return false;
-    }
-    if (hi - lo) / lo < 0.03 {
+/// Beta-law FAP of θ at `period` over the independent frequencies of
+/// `[p_min, p_max]` across `span_s`, times `look_trials`. The law takes the
+/// points and occupied bins θ actually pooled, per cover, not `t.len()`
+/// and `m`.
+#[allow(clippy::too_many_arguments)]
+fn pdm_fap(
+    t: &[f64],
+    y: &[f64],
+    period: f64,
+    m: usize,
+    covers: usize,
+    n_beta: usize,
+    span_s: f64,
+    p_min: f64,
+    p_max: f64,
+    look_trials: f64,
+) -> f64 {
+    let Some(fit) = pdm_fit(t, y, period, m, covers) else {
+        return 1.0;
+    };
+    let (n_used, n_occ) = fit.per_cover();
+    let n_ind = span_s * (1.0 / p_min - 1.0 / p_max);
+    (fap_pdm(fit.theta, n_used.saturating_sub(n_beta), n_occ, n_ind) * look_trials).min(1.0)
+}
+
+/// PDM as the primary estimator (`methods[0] == Pdm`): θ is minimized on
+/// its own coarse/refine grid and accepted on its beta-law FAP, with the
+/// same window veto as GLS; the eclipse veto re-scores θ on the lit points.
+/// H=1 GLS on the same grid gives `fap_baluev`. No permutation tests run;
+/// the beta law is the null.
+#[allow(clippy::too_many_arguments)]
+fn pdm_primary(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    sampling: &crate::entities::assessment::SamplingDiagnostics,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    p_min: f64,
+    p_max: f64,
+    span_s: f64,
+    n_beta: usize,
+    nu: f64,
+    oversample: f64,
+    illumination: Option<&[f64]>,
+    look_trials: f64,
+) -> PeriodicityAssessment {
+    let mut notes = Vec::new();
+    let m = pdm_bin_count(t.len(), config.pdm.m_bins);
+    let covers = config.pdm.covers;
+    // The grid refines around maxima; search on −θ.
+    let (periods, neg_theta) = coarse_refine_periods(
+        p_min,
+        p_max,
+        span_s,
+        config.n_coarse,
+        config.max_freq_trials,
+        oversample,
+        8,
+        |ps: &[f64]| {
+            pdm_periodogram_covers(t, y, ps, m, covers)
+                .score
+                .into_iter()
+                .map(|th| -th)
+                .collect()
+        },
+    );
+    let pgram = crate::entities::assessment::Periodogram {
+        score: neg_theta.iter().map(|s| -s).collect(),
+        period_s: periods,
+        score_kind: ScoreKind::PdmTheta,
+    };
+    let Some(ip) = argmin_finite(&pgram.score) else {
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
+        a.sampling = sampling.clone();
+        a.method = MethodId::Pdm;
+        a.score_kind = ScoreKind::PdmTheta;
+        a.notes.push("PDM occupancy: no valid trial".into());
+        return a;
+    };
+    let (p_star, unc) = interpolate_peak(&pgram.period_s, &neg_theta, ip);
+    let interior = is_interior_maximum(&neg_theta, ip);
+    if !interior {
+        notes.push("bound-snap: θ minimum is not interior".into());
+    }
+    let fap_p = pdm_fap(
+        t,
+        y,
+        pgram.period_s[ip],
+        m,
+        covers,
+        n_beta,
+        span_s,
+        p_min,
+        p_max,
+        look_trials,
+    );
+    if covers > 1 {
+        notes.push(format!("PDM {m} bins × {covers} covers"));
+    }
+    let mut a = decide_primary(
+        series,
+        config,
+        sampling,
+        t,
+        y,
+        w,
+        p_min,
+        nu,
+        illumination,
+        look_trials,
+        MethodId::Pdm,
+        pgram,
+        ip,
+        (p_star, unc),
+        interior,
+        fap_p,
+        notes,
+        |lit| {
+            let (t_l, y_l) = (pick(t, lit), pick(y, lit));
+            let m_l = pdm_bin_count(lit.len(), config.pdm.m_bins);
+            let theta = pdm_fit(&t_l, &y_l, p_star, m_l, covers).map_or(f64::INFINITY, |f| f.theta);
+            let fap_l = pdm_fap(
+                &t_l,
+                &y_l,
+                p_star,
+                m_l,
+                covers,
+                n_beta,
+                span_s,
+                p_min,
+                p_max,
+                look_trials,
+            );
+            (theta, fap_l)
+        },
+    );
+    a.fap_pdm = Some(fap_p);
+    a
+}
+
+/// BLS as the primary estimator (`methods[0] == Bls`): the best box power
+/// on the BLS grid is accepted on [`fap_bls`], with the window veto of
+/// [`pdm_primary`]; the eclipse veto refits the box on the lit points. A
+/// Periodic decision carries the glint report.
+#[allow(clippy::too_many_arguments)]
+fn bls_primary(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    sampling: &crate::entities::assessment::SamplingDiagnostics,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    p_min: f64,
+    p_max: f64,
+    span_s: f64,
+    n_beta: usize,
+    nu: f64,
+    illumination: Option<&[f64]>,
+    look_trials: f64,
+) -> PeriodicityAssessment {
+    let mut notes = Vec::new();
+    let yb = brighter_positive(series, y);
+    let Some(g) = glint_search(
+        config,
+        t,
+        &yb,
+        w,
+        p_min,
+        p_max,
+        span_s,
+        n_beta,
+        look_trials,
+        &mut notes,
+    ) else {
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
+        a.sampling = sampling.clone();
+        a.method = MethodId::Bls;
+        a.score_kind = ScoreKind::BlsPower;
+        a.notes = notes;
+        a.notes.push(format!(
+            "BLS: no box holds {} points",
+            config.bls.min_in_box
+        ));
+        return a;
+    };
+    let (p_star, unc) = interpolate_peak(&g.pgram.period_s, &g.pgram.score, g.best);
+    let interior = is_interior_maximum(&g.pgram.score, g.best);
+    if !interior {
+        notes.push("bound-snap: BLS maximum is not interior".into());
+    }
+    let glint = glint_at(config, t, &yb, w, p_star, &g);
+    let mut a = decide_primary(
+        series,
+        config,
+        sampling,
+        t,
+        y,
+        w,
+        p_min,
+        nu,
+        illumination,
+        look_trials,
+        MethodId::Bls,
+        g.pgram,
+        g.best,
+        (p_star, unc),
+        interior,
+        g.fap,
+        notes,
+        |lit| {
+            let (t_l, yb_l, w_l) = (pick(t, lit), pick(&yb, lit), pick(w, lit));
+            // Mean and depth are the two fitted parameters, as in `glint_search`.
+            let nu_l = lit.len() as f64 - n_beta as f64 - 2.0;
+            match bls_box(&t_l, &yb_l, &w_l, p_star, &config.bls) {
+                Some(fit) if nu_l >= 2.0 => {
+                    let n_ind =
+                        bls_independent_trials(span_s, p_min, p_max, lit.len(), &config.bls);
+                    let fap_l = (fap_bls(fit.power, nu_l, n_ind) * look_trials).min(1.0);
+                    (fit.power, fap_l)
+                }
+                _ => (0.0, 1.0),
+            }
+        },
+    );
+    a.fap_bls = Some(g.fap);
+    if a.decision == PeriodicityDecision::Periodic {
+        a.glint = Some(glint);
+    }
+    a
+}
+
+/// Shared tail of the non-GLS primaries. `pgram` is the primary's grid and
+/// `ip` its best trial; the decision is on `fap`, and `lit_score` re-scores
+/// the primary on the lit points for [`eclipse_veto`]. H=1 GLS on the same
+/// grid gives `fap_baluev`. GLS confirms when it is among `methods`, else
+/// the first other phase-folding estimator there does.
+#[allow(clippy::too_many_arguments)]
+fn decide_primary(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    sampling: &crate::entities::assessment::SamplingDiagnostics,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    p_min: f64,
+    nu: f64,
+    illumination: Option<&[f64]>,
+    look_trials: f64,
+    method: MethodId,
+    pgram: crate::entities::assessment::Periodogram,
+    ip: usize,
+    (p_star, unc): (f64, Option<f64>),
+    interior: bool,
+    fap: f64,
+    mut notes: Vec<String>,
+    lit_score: impl FnOnce(&[usize]) -> (f64, f64),
+) -> PeriodicityAssessment {
+    let label = match method {
+        MethodId::Bls => "BLS",
+        _ => "PDM",
+    };
+    let pgram_gls = gls_periodogram(t, y, w, &pgram.period_s, 1, true);
+    let te = teff(t, w);
+    let f_max = 1.0 / p_min;
+    let p1_max = pgram_gls.score.iter().copied().fold(0.0_f64, f64::max);
+    let fap_b = (fap_baluev(p1_max, nu, te, f_max) * look_trials).min(1.0);
+    let p1_at_star = gls_power_zero_mean(t, y, w, p_star, 1);
+    let (vetoed, mut aliases) = window_veto(
+        p_star,
+        p1_at_star,
+        sampling,
+        config,
+        local_df(&pgram.period_s, ip),
+        t,
+        y,
+        w,
+        nu,
+        te,
+        f_max,
+        &mut notes,
+    );
+    if vetoed {
+        notes.push(format!("window/alias veto at P={p_star:.4}"));
+    }
+    let eclipse = illumination.and_then(|f| eclipse_veto(p_star, config, f, &mut notes, lit_score));
+    let eclipsed = eclipse.as_ref().is_some_and(|al| al.vetoed);
+    aliases.extend(eclipse);
+
+    let primary_ok = interior && !vetoed && !eclipsed && fap < config.fap_threshold;
+    let optical = series.meta().modality != Modality::RfPower;
+    // GLS confirms when listed; otherwise the first phase-folding estimator
+    // other than the primary does, as in `search_and_decide`.
+    let mut confirmers = config.methods.iter().copied().filter(|&m| {
+        m != method
+            && matches!(
+                m,
+                MethodId::Pdm | MethodId::ConditionalEntropy | MethodId::LaflerKinman
+            )
+    });
+    let confirmer = if config.methods.contains(&MethodId::Gls) {
+        Some(MethodId::Gls)
+    } else {
+        confirmers.next()
+    };
+    if let Some(c) = confirmer {
+        for m in confirmers {
+            notes.push(format!(
+                "{} not run: {} already confirms",
+                confirmer_label(m),
+                confirmer_label(c)
+            ));
+        }
+    }
+    let best = match confirmer {
+        Some(MethodId::Gls) => {
+            argmax(&pgram_gls.score).map(|ig| (pgram_gls.period_s[ig], pgram_gls.score[ig]))
+        }
+        Some(m) => {
+            let pgram_c = confirmer_periodogram(config, t, y, &pgram.period_s, m);
+            argmin_finite(&pgram_c.score).map(|ic| (pgram_c.period_s[ic], pgram_c.score[ic]))
+        }
+        None => None,
+    };
+    let confirmation = confirmer.zip(best).map(|(m, (p_c, score))| Confirmation {
+        method: m,
+        period_s: p_c,
+        score,
+        agrees: periods_agree(p_star, p_c, optical),
+    });
+    // A confirmer that found no valid trial is no agreement.
+    let agreement = match (&confirmation, confirmer) {
+        (Some(c), _) => Some(c.agrees),
+        (None, None) => Some(true),
+        (None, Some(m)) => {
+            notes.push(format!("{} occupancy: no valid trial", confirmer_label(m)));
+            None
+        }
+    };
+    if let Some(c) = confirmation.as_ref().filter(|c| !c.agrees) {
+        let c_label = confirmer_label(c.method);
+        notes.push(format!(
+            "{label}–{c_label} disagree: {label}={p_star:.4} {c_label}={:.4}",
+            c.period_s
+        ));
+    }
+    let decision = if primary_ok && config.require_method_agreement && agreement != Some(true) {
+        PeriodicityDecision::Inconclusive
+    } else if primary_ok {
+        PeriodicityDecision::Periodic
+    } else {
+        PeriodicityDecision::NotPeriodic
+    };
+    let periodic = decision == PeriodicityDecision::Periodic;
+
+    PeriodicityAssessment {
+        decision,
+        period_s: periodic.then_some(p_star),
+        period_unc_s: if periodic { unc } else { None },
+        fap: Some(fap),
+        fap_perm: None,
+        fap_block: None,
+        fap_baluev: Some(fap_b),
+        fap_pdm: None,
+        fap_bls: None,
+        score: pgram.score[ip],
+        score_kind: pgram.score_kind,
+        aliases,
+        quality: QualityFlags {
+            n: series.len(),
+            n_passes: sampling.n_passes,
+            duty_cycle: sampling.duty_cycle,
+            window_contaminated: vetoed,
+            undersampled: false,
+            detrended: config.detrend != DetrendMode::None,
+            estimator_agreement: agreement,
+            bound_snap: !interior,
+        },
+        periodogram: pgram,
+        periodogram_h2: None,
+        confirmation,
+        sidereal: None,
+        drift: None,
+        glint: None,
+        sampling: sampling.clone(),
+        detrend: crate::entities::assessment::DetrendReport::default(),
+        method,
+        notes,
+    }
+}
+
+/// `v` at the indices `idx`.
+fn pick(v: &[f64], idx: &[usize]) -> Vec<f64> {
+    idx.iter().map(|&i| v[i]).collect()
+}
+
+/// `y` with brighter positive: magnitudes are negated.
+fn brighter_positive(series: &Series, y: &[f64]) -> Vec<f64> {
+    match series.meta().y_unit {
+        YUnit::Magnitude => y.iter().map(|v| -v).collect(),
+        _ => y.to_vec(),
+    }
+}
+
+/// Best BLS box over `[p_min, p_max]` on `yb` (brighter positive).
+struct GlintSearch {
+    pgram: crate::entities::assessment::Periodogram,
+    best: usize,
+    fit: BoxFit,
+    fap: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn glint_search(
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    yb: &[f64],
+    w: &[f64],
+    p_min: f64,
+    p_max: f64,
+    span_s: f64,
+    n_beta: usize,
+    look_trials: f64,
+    notes: &mut Vec<String>,
+) -> Option<GlintSearch> {
+    let (periods, capped) = bls_periods(p_min, p_max, span_s, &config.bls);
+    if capped {
+        notes.push(format!("BLS grid capped at {} trials", periods.len()));
+    }
+    let pgram = bls_periodogram(t, yb, w, &periods, &config.bls);
+    let best = argmax(&pgram.score)?;
+    let fit = bls_box(t, yb, w, pgram.period_s[best], &config.bls)?;
+    // Mean and depth are the two fitted parameters.
+    let nu = t.len() as f64 - n_beta as f64 - 2.0;
+    let n_ind = bls_independent_trials(span_s, p_min, p_max, t.len(), &config.bls);
+    let fap = (fap_bls(fit.power, nu, n_ind) * look_trials).min(1.0);
+    Some(GlintSearch {
+        pgram,
+        best,
+        fit,
+        fap,
+    })
+}
+
+/// Secondary glint search when `Bls` is in `methods` but not first: sets
+/// `fap_bls` and, when it passes `fap_threshold`, the glint report. The
+/// decision is left alone.
+#[allow(clippy::too_many_arguments)]
+fn attach_glint(
+    a: &mut PeriodicityAssessment,
+    series: &Series,
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    p_min: f64,
+    p_max: f64,
+    span_s: f64,
+    n_beta: usize,
+    look_trials: f64,
+) {
+    if config.methods.first() == Some(&MethodId::Bls) || !config.methods.contains(&MethodId::Bls) {
+        return;
+    }
+    let yb = brighter_positive(series, y);
+    let Some(g) = glint_search(
+        config,
+        t,
+        &yb,
+        w,
+        p_min,
+        p_max,
+        span_s,
+        n_beta,
+        look_trials,
+        &mut a.notes,
+    ) else {
+        a.notes.push(format!(
+            "BLS: no box holds {} points",
+            config.bls.min_in_box
+        ));
+        return;
+    };
+    a.fap_bls = Some(g.fap);
+    if g.fap >= config.fap_threshold {
+        return;
+    }
+    let (p, _) = interpolate_peak(&g.pgram.period_s, &g.pgram.score, g.best);
+    // Two facets flash twice per turn, so 2:1 counts as agreement.
+    if let Some(pa) = a.period_s.filter(|&pa| !periods_agree(pa, p, true)) {
+        a.notes
+            .push(format!("BLS glint period {p:.4} differs from P={pa:.4}"));
+    }
+    a.glint = Some(glint_at(config, t, &yb, w, p, &g));
+}
+
+/// Glint report at the interpolated peak `period` rather than the grid
+/// trial. The box is refit there: its phase is counted from t = 0, so it
+/// slides across the span when the period moves off the grid.
+fn glint_at(
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    yb: &[f64],
+    w: &[f64],
+    period: f64,
+    g: &GlintSearch,
+) -> GlintReport {
+    match bls_box(t, yb, w, period, &config.bls) {
+        Some(fit) => glint_report(t, yb, w, period, &fit),
+        None => glint_report(t, yb, w, g.pgram.period_s[g.best], &g.fit),
+    }
+}
+
+fn confirmer_label(method: MethodId) -> &'static str {
+    match method {
+        MethodId::Gls => "GLS",
+        MethodId::ConditionalEntropy => "CE",
+        MethodId::LaflerKinman => "LK",
+        _ => "PDM",
+    }
+}
+
+/// Periodogram of the phase-folding confirmer `method` over `periods`;
+/// lower is better for all three.
+fn confirmer_periodogram(
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    y: &[f64],
+    periods: &[f64],
+    method: MethodId,
+) -> crate::entities::assessment::Periodogram {
+    match method {
+        MethodId::ConditionalEntropy => entropy_periodogram(
+            t,
+            y,
+            periods,
+            config.entropy.phase_bins,
+            config.entropy.mag_bins,
+        ),
+        MethodId::LaflerKinman => lafler_kinman_periodogram(t, y, periods),
+        _ => {
+            let m = pdm_bin_count(t.len(), config.pdm.m_bins);
+            pdm_periodogram_covers(t, y, periods, m, config.pdm.covers)
+        }
+    }
+}
+
+fn periods_agree(p1: f64, p2: f64, optical: bool) -> bool {
+    let lo = p1.min(p2);
+    let hi = p1.max(p2);
+    if lo <= 0.0 {
+        return false;
+    }
+    if (hi - lo) / lo < 0.03 {
         return true;
     }
     optical && (hi - 2.0 * lo).abs() / lo < 0.05
@@ -671,6 +1744,7 @@ fn local_df(periods: &[f64], idx: usize) -> f64 {
     ds.into_iter().fold(f64::INFINITY, f64::min)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn window_veto(
     p_star: f64,
     p1: f64,
@@ -739,23 +1813,130 @@ fn window_veto(
     (vetoed, aliases)
 }
 
+/// Shadow-crossing check. With any in-scope point below full illumination,
+/// the primary statistic at `P*` must stay significant on the fully lit
+/// points alone; otherwise the peak is eclipse ingress/egress and is vetoed.
+/// `lit_score` returns the statistic and its FAP over the lit indices. `None`
+/// when every point is lit.
+fn eclipse_veto(
+    p_star: f64,
+    config: &PeriodSearchConfig,
+    illumination: &[f64],
+    notes: &mut Vec<String>,
+    lit_score: impl FnOnce(&[usize]) -> (f64, f64),
+) -> Option<Alias> {
+    // Unknown illumination counts as lit, as in `eclipse_keep`.
+    let lit: Vec<usize> = (0..illumination.len())
+        .filter(|&i| !illumination[i].is_finite() || illumination[i] >= 1.0 - ECLIPSE_LIT_TOL)
+        .collect();
+    let n_shadow = illumination.len() - lit.len();
+    if n_shadow == 0 {
+        return None;
+    }
+    let (score_lit, fap_lit) = lit_score(&lit);
+    let vetoed = fap_lit >= config.fap_threshold;
+    if vetoed {
+        notes.push(format!(
+            "eclipse veto at P={p_star:.4}: lit-only FAP={fap_lit:.3} ({n_shadow} shadowed points)"
+        ));
+    }
+    Some(Alias {
+        period_s: p_star,
+        score: score_lit,
+        kind: AliasKind::EclipseIngress,
+        relative_delta: 0.0,
+        vetoed,
+    })
+}
+
+/// H=1 power at `p_star` on the `lit` points and its FAP, for
+/// [`eclipse_veto`] behind a GLS search.
+#[allow(clippy::too_many_arguments)]
+fn gls_lit_score(
+    p_star: f64,
+    config: &PeriodSearchConfig,
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    groups: Option<&[u16]>,
+    n_band: usize,
+    n_beta: usize,
+    f_max: f64,
+    lit: &[usize],
+) -> (f64, f64) {
+    let t_l: Vec<f64> = lit.iter().map(|&i| t[i]).collect();
+    let w_l: Vec<f64> = lit.iter().map(|&i| w[i]).collect();
+    let sw: f64 = w_l.iter().sum();
+    let mean = if sw > 0.0 {
+        lit.iter().map(|&i| w[i] * y[i]).sum::<f64>() / sw
+    } else {
+        0.0
+    };
+    let y_l: Vec<f64> = lit.iter().map(|&i| y[i] - mean).collect();
+    let g_l = groups
+        .map(|g| lit.iter().map(|&i| g[i]).collect::<Vec<u16>>())
+        .filter(|g| n_groups(g) >= 2);
+    let nu_l = lit.len() as f64
+        - n_beta as f64
+        - 2.0
+        - g_l
+            .as_ref()
+            .map(|g| extra_dof(n_groups(g), 1, n_band.min(1)) as f64)
+            .unwrap_or(0.0);
+    if lit.len() < 12 || nu_l < 2.0 {
+        return (0.0, 1.0);
+    }
+    // As in the main search, multiband has no Baluev tail; its lit-only FAP
+    // is the within-group permutation FAP at `p_star`.
+    match &g_l {
+        Some(g) => {
+            let p1 = multiband_power(&t_l, &y_l, &w_l, g, p_star, 1, n_band.min(1));
+            let n_beat = multiband_zero_beat(
+                &t_l,
+                &y_l,
+                &w_l,
+                g,
+                &[p_star],
+                p1,
+                1,
+                n_band.min(1),
+                config.n_permutations,
+                config.rng_seed,
+            );
+            (p1, fap_from_beats(n_beat, config.n_permutations))
+        }
+        None => {
+            let p1 = gls_power_zero_mean(&t_l, &y_l, &w_l, p_star, 1);
+            (p1, fap_baluev(p1, nu_l, teff(&t_l, &w_l), f_max))
+        }
+    }
+}
+
+/// Pass-block bootstrap at `p_star`; passes come from `series`, power is
+/// evaluated on `t` (which may be warped).
+#[allow(clippy::too_many_arguments)]
 fn pass_block_beats(
     series: &Series,
     sampling: &crate::entities::assessment::SamplingDiagnostics,
+    t: &[f64],
     y: &[f64],
     w: &[f64],
+    groups: Option<&[u16]>,
+    n_band: usize,
     p_star: f64,
     data_score: f64,
     n_perm: usize,
     rng_seed: u64,
 ) -> usize {
     let lists = pass_index_lists(series.t_s(), &sampling.passes);
-    let t = series.t_s();
     let mut n_beat = 0usize;
     for i in 0..n_perm {
         let mut rng = StdRng::seed_from_u64(perm_seed(rng_seed, 10_000 + i as u64));
         let y_star = pass_block_replicate(y, w, &lists, &mut rng);
-        let s = gls_power_zero_mean(t, &y_star, w, p_star, 1);
+        let s = match groups {
+            Some(g) => multiband_power(t, &y_star, w, g, p_star, 1, n_band.min(1)),
+            None => gls_power_zero_mean(t, &y_star, w, p_star, 1),
+        };
         if s >= data_score {
             n_beat += 1;
         }
@@ -843,9 +2024,11 @@ fn consensus_intra(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::assessment::Pass;
-    use crate::entities::series::{Covariates, Series, SeriesMeta, SigmaSpec, YUnit};
+    use crate::entities::assessment::{ConfigError, PabSource, Pass};
+    use crate::entities::series::{Covariates, Series, SeriesMeta, SigmaSpec, TimeFrame, YUnit};
+    use crate::functions::periodicity::fold_phase;
     use crate::functions::sampling::{geo_night_times, leo_pass_times};
+    use crate::functions::time_scale::TimeScale;
 
     fn series_of(t: Vec<f64>, y: Vec<f64>, phase: Option<Vec<f64>>) -> Series {
         Series::try_new(
@@ -860,6 +2043,36 @@ mod tests {
                 modality: Modality::OpticalPhotometry,
                 y_unit: YUnit::Magnitude,
                 label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap()
+    }
+
+    fn series_banded(t: Vec<f64>, y: Vec<f64>, bands: Vec<u16>) -> Series {
+        series_keyed(
+            t,
+            y,
+            Covariates {
+                band_key: Some(bands),
+                ..Covariates::default()
+            },
+        )
+    }
+
+    fn series_keyed(t: Vec<f64>, y: Vec<f64>, covariates: Covariates) -> Series {
+        Series::try_new(
+            t,
+            y,
+            SigmaSpec::Unknown,
+            covariates,
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
             },
         )
         .unwrap()
@@ -1051,6 +2264,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn t4c_selected_phase_function_drives_detrend_column() {
+        use crate::entities::assessment::DetrendColumn;
+        use crate::functions::phase_function::PhaseFunction;
+        let t = leo_week();
+        let phi: Vec<f64> = t
+            .iter()
+            .map(|ti| std::f64::consts::TAU * ti / 5400.0)
+            .collect();
+        let model = PhaseFunction::LambertSphere;
+        let y: Vec<f64> = phi
+            .iter()
+            .map(|&p| 0.25 * model.phase_integral(p))
+            .collect();
+        let s = series_of(t, y, Some(phi));
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.phase_function = model;
+        let a = assess_periodicity(&s, &c);
+        assert!(a.detrend.columns.contains(&DetrendColumn::PhaseFunction));
+        assert!(!a.detrend.columns.contains(&DetrendColumn::PhaseLambert));
+        assert_ne!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+    }
+
     #[test]
     fn t4b_three_cycle_pass_intra_periodic() {
         let t = leo_week();
@@ -1083,73 +2320,348 @@ mod tests {
             "Intra must eat night slope. notes={:?}",
             intra.notes
         );
-        c.scale = SearchScale::InterPass;
-        let inter = assess_periodicity(&s, &c);
-        assert_ne!(
-            inter.decision,
-            PeriodicityDecision::Periodic,
-            "Inter must reject via global t / pass-block, not night means. notes={:?}",
-            inter.notes
+        c.scale = SearchScale::InterPass;
+        let inter = assess_periodicity(&s, &c);
+        assert_ne!(
+            inter.decision,
+            PeriodicityDecision::Periodic,
+            "Inter must reject via global t / pass-block, not night means. notes={:?}",
+            inter.notes
+        );
+        assert!(
+            inter.detrend.columns.iter().any(|col| {
+                matches!(col, crate::entities::assessment::DetrendColumn::GlobalTime)
+            }) || inter.notes.iter().any(|n| n.contains("Inter")),
+            "Inter mechanism must be global t / Inter leg, not per-pass means"
+        );
+    }
+
+    #[test]
+    fn t6b_sidereal_harmonic_is_tagged() {
+        let t = geo_night_times(7, 80, 18_000.0, 0.0);
+        let s = series_of(t.clone(), vec![1.0; t.len()], None);
+        let mut c = cfg();
+        c.max_period_s = Some(3000.0);
+        let a = assess_periodicity(&s, &c);
+        let p_line = crate::constants::SIDEREAL_DAY_S / 29.0;
+        let tagged = a.sampling.window_peaks.iter().any(|pk| {
+            pk.kind == AliasKind::SiderealDay && (pk.period_s - p_line).abs() / p_line < 0.05
+        });
+        assert!(tagged, "expected SiderealDay tag near {p_line}");
+    }
+
+    #[test]
+    fn t9_clean_sine_full_scale() {
+        let mut t = Vec::new();
+        let mut s = 1u64;
+        for _ in 0..200 {
+            s = s.wrapping_mul(1103515245).wrapping_add(12345);
+            t.push((s as f64 / u64::MAX as f64) * 50.0 * 47.3);
+        }
+        let y: Vec<f64> = t
+            .iter()
+            .map(|ti| (std::f64::consts::TAU * ti / 47.3).sin())
+            .collect();
+        let ser = series_of(t, y, None);
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(100.0);
+        let a = assess_periodicity(&ser, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        let p = a.period_s.unwrap();
+        assert!((p - 47.3).abs() / 47.3 < 0.05, "got {p}");
+    }
+
+    #[test]
+    fn periodic_result_carries_sidereal_correction() {
+        let t: Vec<f64> = (0..200).map(|i| i as f64 * 12.0).collect();
+        let y: Vec<f64> = t
+            .iter()
+            .map(|ti| (std::f64::consts::TAU * ti / 47.3).sin())
+            .collect();
+        let alpha: Vec<f64> = t.iter().map(|ti| 0.5 + 2e-5 * ti).collect();
+        let ser = series_of(t.clone(), y, Some(alpha));
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.detrend = DetrendMode::None;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(100.0);
+
+        let a = assess_periodicity(&ser, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        let sid = a.sidereal.expect("phase covariate gives a correction");
+        assert_eq!(sid.source, PabSource::PhaseAngle);
+        assert!(
+            (sid.pab_rate_rad_s - 1e-5).abs() < 1e-9,
+            "{}",
+            sid.pab_rate_rad_s
+        );
+
+        let omega = 3e-5;
+        let pab: Vec<(f64, [f64; 3])> = t
+            .iter()
+            .map(|&ti| (ti, [(omega * ti).cos(), (omega * ti).sin(), 0.0]))
+            .collect();
+        let b = assess_periodicity_with_pab(&ser, &c, &pab);
+        let sid = b.sidereal.expect("bisectors give a correction");
+        assert_eq!(sid.source, PabSource::Vectors);
+        assert!(
+            (sid.pab_rate_rad_s - omega).abs() < 1e-9,
+            "{}",
+            sid.pab_rate_rad_s
+        );
+        assert!((sid.period_s - (b.period_s.unwrap() - sid.delta_s)).abs() < 1e-9);
+
+        let short = assess_periodicity_with_pab(&ser, &c, &pab[1..]);
+        assert!(short.sidereal.is_none());
+        assert!(
+            short
+                .notes
+                .iter()
+                .any(|n| n == "pab: no bisector for 1 of 200 points")
+        );
+
+        // Repeated epochs merge into one point; their bisectors still line up.
+        let (t2, y2): (Vec<f64>, Vec<f64>) = t
+            .iter()
+            .flat_map(|&ti| {
+                let yi = (std::f64::consts::TAU * ti / 47.3).sin();
+                std::iter::repeat_n((ti, yi), if ti % 60.0 == 0.0 { 2 } else { 1 })
+            })
+            .unzip();
+        let doubled = series_of(t2.clone(), y2, None);
+        assert!(doubled.len() < t2.len());
+        let pab2: Vec<(f64, [f64; 3])> = t2
+            .iter()
+            .map(|&ti| (ti, [(omega * ti).cos(), (omega * ti).sin(), 0.0]))
+            .collect();
+        let m = assess_periodicity_with_pab(&doubled, &c, &pab2);
+        assert_eq!(m.decision, PeriodicityDecision::Periodic, "{:?}", m.notes);
+        let sid = m.sidereal.expect("merged points keep their bisectors");
+        assert!((sid.pab_rate_rad_s - omega).abs() < 1e-9);
+    }
+
+    #[test]
+    fn t10_white_noise_not_periodic() {
+        let t: Vec<f64> = (0..120).map(|i| i as f64).collect();
+        let y = lcg_noise(120, 2.0, 99);
+        let s = series_of(t, y, None);
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(4.0);
+        c.max_period_s = Some(40.0);
+        let a = assess_periodicity(&s, &c);
+        assert_ne!(a.decision, PeriodicityDecision::Periodic);
+        if let Some(fb) = a.fap_baluev {
+            assert!(fb > 0.05 || a.decision == PeriodicityDecision::NotPeriodic);
+        }
+    }
+
+    #[test]
+    fn pdm_primary_decides_on_beta_fap() {
+        let mut t = Vec::new();
+        let mut s = 1u64;
+        for _ in 0..200 {
+            s = s.wrapping_mul(1103515245).wrapping_add(12345);
+            t.push((s as f64 / u64::MAX as f64) * 50.0 * 47.3);
+        }
+        let noise = lcg_noise(t.len(), 0.5, 3);
+        let y: Vec<f64> = t
+            .iter()
+            .zip(&noise)
+            .map(|(ti, e)| (std::f64::consts::TAU * ti / 47.3).sin() + e)
+            .collect();
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(100.0);
+        c.methods = vec![MethodId::Pdm, MethodId::Gls];
+        c.pdm.covers = 3;
+        let a = assess_periodicity(&series_of(t.clone(), y.clone(), None), &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert_eq!(
+            (a.method, a.score_kind),
+            (MethodId::Pdm, ScoreKind::PdmTheta)
+        );
+        assert!((a.period_s.unwrap() / 47.3 - 1.0).abs() < 0.02);
+        assert!(a.fap_pdm.is_some_and(|f| f < 1e-6) && a.fap == a.fap_pdm);
+        let conf = a.confirmation.unwrap();
+        assert!(conf.method == MethodId::Gls && conf.agrees);
+
+        // Without GLS the first other folding estimator confirms.
+        c.methods = vec![
+            MethodId::Pdm,
+            MethodId::ConditionalEntropy,
+            MethodId::LaflerKinman,
+        ];
+        c.require_method_agreement = true;
+        let a = assess_periodicity(&series_of(t.clone(), y, None), &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        let conf = a.confirmation.unwrap();
+        assert!(
+            conf.method == MethodId::ConditionalEntropy && conf.agrees,
+            "{conf:?}"
         );
+        assert_eq!(a.quality.estimator_agreement, Some(true));
         assert!(
-            inter.detrend.columns.iter().any(|col| {
-                matches!(col, crate::entities::assessment::DetrendColumn::GlobalTime)
-            }) || inter.notes.iter().any(|n| n.contains("Inter")),
-            "Inter mechanism must be global t / Inter leg, not per-pass means"
+            a.notes
+                .iter()
+                .any(|n| n == "LK not run: CE already confirms")
         );
+
+        c.methods = vec![MethodId::Pdm];
+        let a = assess_periodicity(&series_of(t, noise, None), &c);
+        assert_ne!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert!(a.fap_pdm.is_some_and(|f| f > c.fap_threshold));
+        assert!(a.confirmation.is_none());
     }
 
     #[test]
-    fn t6b_sidereal_harmonic_is_tagged() {
-        let t = geo_night_times(7, 80, 18_000.0, 0.0);
-        let s = series_of(t.clone(), vec![1.0; t.len()], None);
+    fn entropy_and_lafler_kinman_confirm_a_glint() {
+        let mut t = Vec::new();
+        let mut s = 5u64;
+        for _ in 0..300 {
+            s = s.wrapping_mul(1103515245).wrapping_add(12345);
+            t.push((s as f64 / u64::MAX as f64) * 40.0 * 47.3);
+        }
+        let noise = lcg_noise(t.len(), 0.2, 9);
+        // Narrow glint: 8% of the cycle above a flat floor.
+        let y: Vec<f64> = t
+            .iter()
+            .zip(&noise)
+            .map(|(&ti, e)| {
+                let d = (fold_phase(ti, 47.3) - 0.5) / 0.02;
+                3.0 * (-0.5 * d * d).exp() + e
+            })
+            .collect();
         let mut c = cfg();
-        c.max_period_s = Some(3000.0);
-        let a = assess_periodicity(&s, &c);
-        let p_line = crate::constants::SIDEREAL_DAY_S / 29.0;
-        let tagged = a.sampling.window_peaks.iter().any(|pk| {
-            pk.kind == AliasKind::SiderealDay && (pk.period_s - p_line).abs() / p_line < 0.05
-        });
-        assert!(tagged, "expected SiderealDay tag near {p_line}");
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(80.0);
+        for method in [MethodId::ConditionalEntropy, MethodId::LaflerKinman] {
+            c.methods = vec![method];
+            assert!(matches!(c.validate(), Err(ConfigError::ConfirmerFirst(m)) if m == method));
+
+            c.methods = vec![MethodId::Gls, method, MethodId::Pdm];
+            let a = assess_periodicity(&series_of(t.clone(), y.clone(), None), &c);
+            assert!(a.notes.iter().any(|n| n.starts_with("PDM not run")), "{:?}", a.notes);
+            assert_eq!(
+                a.decision,
+                PeriodicityDecision::Periodic,
+                "{method:?}: {:?}",
+                a.notes
+            );
+            assert!((a.period_s.unwrap() / 47.3 - 1.0).abs() < 0.02);
+            let conf = a.confirmation.unwrap();
+            assert!(conf.method == method && conf.agrees, "{conf:?}");
+            assert!(a.fap_pdm.is_none());
+        }
     }
 
     #[test]
-    fn t9_clean_sine_full_scale() {
+    fn bls_finds_magnitude_glints() {
+        // 1500 irregular samples; a 4 s, 2 mag brightening every 47.3 s.
         let mut t = Vec::new();
-        let mut s = 1u64;
-        for _ in 0..200 {
+        let mut s = 21u64;
+        let mut ti = 0.0;
+        for _ in 0..1500 {
             s = s.wrapping_mul(1103515245).wrapping_add(12345);
-            t.push((s as f64 / u64::MAX as f64) * 50.0 * 47.3);
+            ti += 0.4 + 0.8 * (s % 1000) as f64 / 1000.0;
+            t.push(ti);
         }
+        let noise = lcg_noise(t.len(), 0.1, 5);
         let y: Vec<f64> = t
             .iter()
-            .map(|ti| (std::f64::consts::TAU * ti / 47.3).sin())
+            .zip(&noise)
+            .map(|(&x, e)| {
+                let lit = (fold_phase(x, 47.3) - 0.3).abs() < 2.0 / 47.3;
+                12.0 - if lit { 2.0 } else { 0.0 } + e
+            })
             .collect();
-        let ser = series_of(t, y, None);
         let mut c = cfg();
         c.scale = SearchScale::Full;
         c.min_period_s = Some(20.0);
-        c.max_period_s = Some(100.0);
-        let a = assess_periodicity(&ser, &c);
+        c.max_period_s = Some(80.0);
+        c.methods = vec![MethodId::Bls, MethodId::Gls];
+        let a = assess_periodicity(&series_of(t.clone(), y.clone(), None), &c);
         assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
-        let p = a.period_s.unwrap();
-        assert!((p - 47.3).abs() / 47.3 < 0.05, "got {p}");
+        assert_eq!(
+            (a.method, a.score_kind),
+            (MethodId::Bls, ScoreKind::BlsPower)
+        );
+        assert!((a.period_s.unwrap() / 47.3 - 1.0).abs() < 1e-3);
+        assert!(a.fap_bls.is_some_and(|f| f < 1e-6) && a.fap == a.fap_bls);
+        let g = a.glint.unwrap();
+        assert_eq!(Some(g.period_s), a.period_s);
+        assert!(
+            (g.width_s - 4.0).abs() < 1.0 && (g.depth - 2.0).abs() < 0.3,
+            "{g:?}"
+        );
+        assert!(g.events.len() >= 20);
+        assert!(
+            g.events
+                .iter()
+                .all(|e| (fold_phase(e.t_s, 47.3) - 0.3).abs() < 2.0 / 47.3)
+        );
+
+        // As a secondary method the glint rides along with the GLS decision.
+        c.methods = vec![MethodId::Gls, MethodId::Bls];
+        let a = assess_periodicity(&series_of(t, y, None), &c);
+        assert_eq!(a.method, MethodId::Gls);
+        assert!(a.fap_bls.is_some_and(|f| f < 1e-6));
+        assert!((a.glint.unwrap().period_s / 47.3 - 1.0).abs() < 1e-3);
     }
 
     #[test]
-    fn t10_white_noise_not_periodic() {
-        let t: Vec<f64> = (0..120).map(|i| i as f64).collect();
-        let y = lcg_noise(120, 2.0, 99);
-        let s = series_of(t, y, None);
+    fn shadowed_points_do_not_veto_a_glint_train() {
+        // 1 s, 2 mag glints every 47.3 s; 40 faint points in shadow mid-span.
+        let mut t = Vec::new();
+        let mut s = 23u64;
+        let mut ti = 0.0;
+        for _ in 0..1500 {
+            s = s.wrapping_mul(1103515245).wrapping_add(12345);
+            ti += 0.2 + 0.4 * (s % 1000) as f64 / 1000.0;
+            t.push(ti);
+        }
+        let shadow = |i: usize| (700..740).contains(&i);
+        let noise = lcg_noise(t.len(), 0.1, 6);
+        let y: Vec<f64> = t
+            .iter()
+            .zip(&noise)
+            .enumerate()
+            .map(|(i, (&x, e))| {
+                let lit = (fold_phase(x, 47.3) - 0.3).abs() < 0.5 / 47.3;
+                let glint = if lit && !shadow(i) { 2.0 } else { 0.0 };
+                12.0 - glint + if shadow(i) { 1.5 } else { 0.0 } + e
+            })
+            .collect();
+        let illum: Vec<f64> = (0..t.len())
+            .map(|i| if shadow(i) { 0.3 } else { 1.0 })
+            .collect();
         let mut c = cfg();
         c.scale = SearchScale::Full;
-        c.min_period_s = Some(4.0);
-        c.max_period_s = Some(40.0);
-        let a = assess_periodicity(&s, &c);
-        assert_ne!(a.decision, PeriodicityDecision::Periodic);
-        if let Some(fb) = a.fap_baluev {
-            assert!(fb > 0.05 || a.decision == PeriodicityDecision::NotPeriodic);
-        }
+        c.detrend = DetrendMode::None;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(80.0);
+        c.methods = vec![MethodId::Bls];
+        let ser = series_keyed(
+            t,
+            y,
+            Covariates {
+                illumination: Some(illum),
+                ..Covariates::default()
+            },
+        );
+        let a = assess_periodicity(&ser, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert!((a.period_s.unwrap() / 47.3 - 1.0).abs() < 1e-3);
+        let veto = a
+            .aliases
+            .iter()
+            .find(|al| al.kind == AliasKind::EclipseIngress)
+            .expect("shadowed points run the veto");
+        assert!(!veto.vetoed);
     }
 
     #[test]
@@ -1241,6 +2753,285 @@ mod tests {
         assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
     }
 
+    #[test]
+    fn two_band_tumbler_is_one_detection_with_fitted_offset() {
+        // Filter wheel flips V → r' halfway through every pass; r' reads 0.8 mag fainter.
+        use crate::entities::passband::Passband;
+        let t = leo_week();
+        let passes = cluster_passes_for(&t);
+        let (v, r) = (Passband::JohnsonV.key(), Passband::SloanR.key());
+        let bands: Vec<u16> = t
+            .iter()
+            .map(|&ti| {
+                let p = passes
+                    .iter()
+                    .find(|p| ti >= p.t_start_s && ti <= p.t_end_s)
+                    .unwrap();
+                if ti - p.t_start_s < 0.5 * p.duration_s() {
+                    v
+                } else {
+                    r
+                }
+            })
+            .collect();
+        let y: Vec<f64> = t
+            .iter()
+            .zip(bands.iter())
+            .map(|(ti, &b)| {
+                let off = if b == r { 0.8 } else { 0.0 };
+                12.0 + off + 0.3 * (std::f64::consts::TAU * ti / 47.0).sin()
+            })
+            .collect();
+        let s = series_banded(t, y, bands);
+        let mut c = cfg();
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(200.0);
+        let a = assess_periodicity(&s, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        let p = a.period_s.unwrap();
+        assert!((p - 47.0).abs() / 47.0 < 0.03, "got {p}");
+        assert!(
+            a.detrend
+                .columns
+                .contains(&crate::entities::assessment::DetrendColumn::BandOffset { band: r })
+                || a.detrend
+                    .columns
+                    .contains(&crate::entities::assessment::DetrendColumn::BandOffset { band: v })
+        );
+        let (_, off_r) = a
+            .detrend
+            .band_offsets
+            .iter()
+            .find(|(k, _)| *k == r)
+            .unwrap();
+        let (_, off_v) = a
+            .detrend
+            .band_offsets
+            .iter()
+            .find(|(k, _)| *k == v)
+            .unwrap();
+        assert!(
+            (off_r - off_v - 0.8).abs() < 0.1,
+            "offsets {:?}",
+            a.detrend.band_offsets
+        );
+    }
+
+    #[test]
+    fn two_site_calibration_step_is_absorbed_by_sensor_offset() {
+        // Sites alternate passes; site 2 reads 0.3 mag fainter. Without a
+        // sensor intercept the step is a square wave at twice the pass cadence.
+        let t = leo_week();
+        let passes = cluster_passes_for(&t);
+        let sensors: Vec<u16> = t
+            .iter()
+            .map(|&ti| {
+                let p = passes
+                    .iter()
+                    .position(|p| ti >= p.t_start_s && ti <= p.t_end_s)
+                    .unwrap();
+                1 + (p % 2) as u16
+            })
+            .collect();
+        let noise = lcg_noise(t.len(), 0.2, 23);
+        let y: Vec<f64> = sensors
+            .iter()
+            .zip(noise.iter())
+            .map(|(&k, e)| 12.0 + if k == 2 { 0.3 } else { 0.0 } + e)
+            .collect();
+        let s = series_keyed(
+            t,
+            y,
+            Covariates {
+                sensor_key: Some(sensors),
+                ..Covariates::default()
+            },
+        );
+        let mut c = cfg();
+        c.scale = SearchScale::InterPass;
+        c.max_period_s = Some(20_000.0);
+        let a = assess_periodicity(&s, &c);
+        assert_ne!(
+            a.decision,
+            PeriodicityDecision::Periodic,
+            "{:?}",
+            a.period_s
+        );
+        assert!(
+            a.detrend
+                .columns
+                .contains(&crate::entities::assessment::DetrendColumn::SensorOffset { key: 2 })
+        );
+        let off = |k: u16| {
+            a.detrend
+                .sensor_offsets
+                .iter()
+                .find(|(s, _)| *s == k)
+                .unwrap()
+                .1
+        };
+        assert!(
+            (off(2) - off(1) - 0.3).abs() < 0.05,
+            "{:?}",
+            a.detrend.sensor_offsets
+        );
+    }
+
+    #[test]
+    fn anti_phased_bands_need_multiband_gls() {
+        // Colour-dependent glint: V brightens while r' dims. Pooled GLS sees
+        // the two cancel; per-band terms recover the shared period.
+        use crate::entities::passband::Passband;
+        let t = leo_week();
+        let (v, r) = (Passband::JohnsonV.key(), Passband::SloanR.key());
+        // Random filter choice: a strict alternation would alias the flip itself.
+        let bands: Vec<u16> = lcg_noise(t.len(), 1.0, 5)
+            .iter()
+            .map(|u| if *u < 0.0 { v } else { r })
+            .collect();
+        let noise = lcg_noise(t.len(), 0.1, 17);
+        let y: Vec<f64> = t
+            .iter()
+            .zip(bands.iter())
+            .zip(noise.iter())
+            .map(|((ti, &b), e)| {
+                let sign = if b == v { 1.0 } else { -1.0 };
+                12.0 + sign * 0.3 * (std::f64::consts::TAU * ti / 47.0).sin() + e
+            })
+            .collect();
+        let s = series_banded(t, y, bands);
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(200.0);
+
+        c.methods = vec![MethodId::Gls];
+        let single = assess_periodicity(&s, &c);
+        assert_ne!(
+            single.decision,
+            PeriodicityDecision::Periodic,
+            "{:?}",
+            single.period_s
+        );
+
+        // The multiband FAP is a permutation maximum; 100 draws resolve 2 %.
+        c.methods = vec![MethodId::MultibandGls];
+        c.fap_threshold = 2e-2;
+        c.n_permutations = 100;
+        let a = assess_periodicity(&s, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert_eq!(a.method, MethodId::MultibandGls);
+        let p = a.period_s.unwrap();
+        assert!((p - 47.0).abs() / 47.0 < 0.03, "got {p}");
+    }
+
+    /// Constant target crossing Earth shadow every 90 s: linear penumbra
+    /// ramps either side of an umbra at 70–90 % of the orbit.
+    fn eclipsing_constant(seed: u64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let p_orb = 90.0;
+        let mut t = Vec::new();
+        let mut s = seed;
+        for _ in 0..300 {
+            s = s.wrapping_mul(1103515245).wrapping_add(12345);
+            t.push((s as f64 / u64::MAX as f64) * 50.0 * p_orb);
+        }
+        t.sort_by(|a, b| a.total_cmp(b));
+        let illum: Vec<f64> = t
+            .iter()
+            .map(|ti| {
+                let u = (ti / p_orb).fract();
+                if !(0.67..=0.93).contains(&u) {
+                    1.0
+                } else if u < 0.70 {
+                    (0.70 - u) / 0.03
+                } else if u > 0.90 {
+                    (u - 0.90) / 0.03
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let noise = lcg_noise(t.len(), 0.05, seed + 1);
+        let y: Vec<f64> = illum
+            .iter()
+            .zip(noise.iter())
+            .map(|(f, n)| {
+                let fade = if *f > 0.0 {
+                    -2.5 * f.max(0.01).log10()
+                } else {
+                    5.0
+                };
+                fade + n
+            })
+            .collect();
+        (t, y, illum)
+    }
+
+    fn eclipse_cfg() -> PeriodSearchConfig {
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(200.0);
+        c
+    }
+
+    #[test]
+    fn eclipse_ingress_is_dropped_not_reported() {
+        use crate::functions::shadow::{DEFAULT_MIN_ILLUMINATION, eclipse_keep};
+        let (t, y, illum) = eclipsing_constant(5);
+        let blind = assess_periodicity(&series_of(t.clone(), y.clone(), None), &eclipse_cfg());
+        assert_eq!(
+            blind.decision,
+            PeriodicityDecision::Periodic,
+            "control: unflagged fades should look periodic: {:?}",
+            blind.notes
+        );
+
+        let keep = eclipse_keep(&illum, DEFAULT_MIN_ILLUMINATION);
+        let s = series_keyed(
+            t,
+            y,
+            Covariates {
+                illumination: Some(illum),
+                keep: Some(keep),
+                ..Covariates::default()
+            },
+        );
+        let a = assess_periodicity(&s, &eclipse_cfg());
+        assert_ne!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert!(a.period_s.is_none());
+        assert!(
+            a.detrend
+                .columns
+                .contains(&crate::entities::assessment::DetrendColumn::Illumination)
+        );
+    }
+
+    #[test]
+    fn eclipse_ingress_is_vetoed_when_kept() {
+        let (t, y, illum) = eclipsing_constant(11);
+        let s = series_keyed(
+            t,
+            y,
+            Covariates {
+                illumination: Some(illum),
+                ..Covariates::default()
+            },
+        );
+        let mut c = eclipse_cfg();
+        // No shadow column: only the veto stands between ingress and a period.
+        c.detrend = DetrendMode::LinearTime;
+        let a = assess_periodicity(&s, &c);
+        assert_ne!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert!(
+            a.aliases
+                .iter()
+                .any(|al| al.kind == AliasKind::EclipseIngress && al.vetoed),
+            "{:?}",
+            a.notes
+        );
+    }
+
     #[test]
     fn t22_short_single_pass_inconclusive() {
         let t = leo_pass_times(1, 8, 480.0, 5400.0, 0.0);
@@ -1374,4 +3165,39 @@ mod tests {
         let a = assess_periodicity(&s, &c);
         assert_ne!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
     }
+
+    #[test]
+    fn utc_span_across_a_leap_second_is_searched_in_tai() {
+        // 2017-01-01T00:00:00Z, after the 2016-12-31 leap second.
+        let leap = 1_483_228_800.0;
+        let t: Vec<f64> = (0..200).map(|i| leap - 700.0 + 7.0 * i as f64).collect();
+        let y: Vec<f64> = t
+            .iter()
+            .map(|&ti| {
+                let tai = crate::functions::time_scale::from_utc(ti, TimeScale::Tai);
+                (std::f64::consts::TAU * tai / 37.0).sin()
+            })
+            .collect();
+        let s = series_of(t.clone(), y, None);
+        let mut c = cfg();
+        c.scale = SearchScale::Full;
+        c.min_period_s = Some(20.0);
+        c.max_period_s = Some(100.0);
+        assert_eq!(search_time_scale(&s, &c), TimeScale::Tai);
+
+        let a = assess_periodicity(&s, &c);
+        assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
+        assert!(
+            (a.period_s.unwrap() - 37.0).abs() < 0.05,
+            "{:?}",
+            a.period_s
+        );
+        assert!(a.notes.iter().any(|n| n.starts_with("searched in TAI")));
+        assert_eq!(a.sampling.passes[0].t_start_s, t[0]);
+
+        c.time_scale = Some(TimeScale::Utc);
+        assert_eq!(search_time_scale(&s, &c), TimeScale::Utc);
+        let within = series_of(t[..90].to_vec(), vec![0.0; 90], None);
+        assert_eq!(search_time_scale(&within, &cfg()), TimeScale::Utc);
+    }
 }
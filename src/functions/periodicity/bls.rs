@@ -0,0 +1,299 @@
+//! Box-least-squares (Kovács, Zucker & Mazeh 2002) search for glints.
+//!
+//! Transit BLS looks for a dip; a specular glint is the same box turned
+//! upside down, so only boxes brighter than the rest of the cycle count.
+//! Callers pass `y` with brighter positive (magnitudes negated). The
+//! folded series is binned into `n_bins` phase bins and every run of up to
+//! `max_duty · n_bins` consecutive bins is tried as the box.
+//!
+//! The score is the fraction of the weighted variance removed by the box,
+//! `s² / (r(1 − r) σ²)` with `r` and `s` the in-box weight and weighted
+//! sum of the centred data. For Gaussian noise and a fixed box it is
+//! `Beta(1/2, ν/2)`, one-sided. [`fap_bls`] scales that by the number of
+//! independent (frequency, box position) trials; like Baluev's bound for
+//! GLS it is an approximation, conservative for correlated widths.
+
+use crate::entities::assessment::{BlsOptions, GlintEvent, GlintReport, Periodogram, ScoreKind};
+use crate::functions::periodicity::fold_phase;
+use crate::functions::periodicity::pdm::beta_cdf;
+use rayon::prelude::*;
+
+/// Best box at one trial period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxFit {
+    pub power: f64,
+    /// Phase of the box centre in `[0, 1)`.
+    pub phase: f64,
+    pub duty: f64,
+    /// In-box minus out-of-box weighted mean.
+    pub depth: f64,
+    pub n_in: usize,
+}
+
+/// Best brightening box at `period`, or `None` for a flat series or when
+/// no box holds `min_in_box` points with a positive excess.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+pub fn bls_box(
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    period: f64,
+    options: &BlsOptions,
+) -> Option<BoxFit> {
+    let nb = options.n_bins;
+    if nb < 2 || t.len() < 4 || !(period > 0.0) {
+        return None;
+    }
+    let w_tot: f64 = w.iter().sum();
+    if !(w_tot > 0.0) {
+        return None;
+    }
+    let mean = w.iter().zip(y).map(|(wi, yi)| wi * yi).sum::<f64>() / w_tot;
+    let var = w
+        .iter()
+        .zip(y)
+        .map(|(wi, yi)| wi * (yi - mean) * (yi - mean))
+        .sum::<f64>()
+        / w_tot;
+    if !(var > 0.0) {
+        return None;
+    }
+    let mut bin_w = vec![0.0; nb];
+    let mut bin_s = vec![0.0; nb];
+    let mut bin_n = vec![0usize; nb];
+    for ((&ti, &yi), &wi) in t.iter().zip(y).zip(w) {
+        let phi = fold_phase(ti, period);
+        if !phi.is_finite() {
+            continue;
+        }
+        let j = ((phi * nb as f64) as usize).min(nb - 1);
+        bin_w[j] += wi / w_tot;
+        bin_s[j] += wi * (yi - mean) / w_tot;
+        bin_n[j] += 1;
+    }
+    let k_max = ((options.max_duty * nb as f64) as usize).clamp(1, nb - 1);
+    let mut best: Option<BoxFit> = None;
+    for i in 0..nb {
+        let (mut r, mut s, mut n) = (0.0, 0.0, 0usize);
+        for k in 0..k_max {
+            let j = (i + k) % nb;
+            r += bin_w[j];
+            s += bin_s[j];
+            n += bin_n[j];
+            if n < options.min_in_box || !(s > 0.0) || !(r < 1.0) {
+                continue;
+            }
+            let power = s * s / (r * (1.0 - r) * var);
+            if best.as_ref().is_none_or(|b| power > b.power) {
+                let duty = (k + 1) as f64 / nb as f64;
+                best = Some(BoxFit {
+                    power,
+                    phase: (i as f64 / nb as f64 + 0.5 * duty).fract(),
+                    duty,
+                    depth: s / (r * (1.0 - r)),
+                    n_in: n,
+                });
+            }
+        }
+    }
+    best
+}
+
+pub fn bls_periodogram(
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    periods: &[f64],
+    options: &BlsOptions,
+) -> Periodogram {
+    let score: Vec<f64> = periods
+        .par_iter()
+        .map(|&p| bls_box(t, y, w, p, options).map_or(0.0, |b| b.power))
+        .collect();
+    Periodogram {
+        period_s: periods.to_vec(),
+        score,
+        score_kind: ScoreKind::BlsPower,
+    }
+}
+
+/// Ascending periods on a frequency grid fine enough that the narrowest box
+/// drifts by at most half its width over `span_s`, capped at
+/// `options.max_trials` points. The flag is set when the cap coarsened it.
+pub fn bls_periods(p_min: f64, p_max: f64, span_s: f64, options: &BlsOptions) -> (Vec<f64>, bool) {
+    if !(p_min > 0.0 && p_max > p_min && span_s > 0.0) {
+        return (Vec::new(), false);
+    }
+    let (f_lo, f_hi) = (1.0 / p_max, 1.0 / p_min);
+    let df = 0.5 / (options.n_bins.max(1) as f64 * span_s);
+    let wanted = ((f_hi - f_lo) / df).ceil() as usize + 1;
+    let n = wanted.clamp(2, options.max_trials.max(2));
+    let df = (f_hi - f_lo) / (n - 1) as f64;
+    let periods = (0..n).rev().map(|k| 1.0 / (f_lo + k as f64 * df)).collect();
+    (periods, wanted > n)
+}
+
+/// Independent (frequency, box position) trials for [`fap_bls`]. A box of
+/// duty `q` has `1/q` positions and resolves frequency at `q / span`, so
+/// the narrowest box `n` points can fill sets both: Rayleigh frequencies
+/// in the band times `1/q²`.
+pub fn bls_independent_trials(
+    span_s: f64,
+    p_min: f64,
+    p_max: f64,
+    n: usize,
+    options: &BlsOptions,
+) -> f64 {
+    let positions = options.n_bins.min(n / options.min_in_box.max(1)).max(1);
+    let positions = positions as f64;
+    (span_s * (1.0 / p_min - 1.0 / p_max)).max(1.0) * positions * positions
+}
+
+/// FAP of the best box power over `n_independent` trials with `nu`
+/// residual degrees of freedom: single-trial `½ I_{1−power}(ν/2, 1/2)`,
+/// then `1 − (1 − p)^{n_independent}`.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+pub fn fap_bls(power: f64, nu: f64, n_independent: f64) -> f64 {
+    if !(nu > 0.0) || !(power > 0.0) {
+        return 1.0;
+    }
+    if power >= 1.0 {
+        return 0.0;
+    }
+    let p = 0.5 * beta_cdf(1.0 - power, 0.5 * nu, 0.5);
+    (-(n_independent.max(1.0) * (-p).ln_1p()).exp_m1()).clamp(0.0, 1.0)
+}
+
+/// Glint ephemeris and observed flashes for `fit` at `period`. `y` is
+/// brighter positive, as for [`bls_box`].
+pub fn glint_report(t: &[f64], y: &[f64], w: &[f64], period: f64, fit: &BoxFit) -> GlintReport {
+    let in_box = |ti: f64| {
+        let d = (fold_phase(ti, period) - fit.phase).rem_euclid(1.0);
+        d.min(1.0 - d) <= 0.5 * fit.duty
+    };
+    let (mut w_out, mut s_out) = (0.0, 0.0);
+    for ((&ti, &yi), &wi) in t.iter().zip(y).zip(w) {
+        if !in_box(ti) {
+            w_out += wi;
+            s_out += wi * yi;
+        }
+    }
+    let base = if w_out > 0.0 { s_out / w_out } else { 0.0 };
+    let epoch_s = t.first().map_or(fit.phase * period, |&t0| {
+        ((t0 / period - fit.phase).round() + fit.phase) * period
+    });
+
+    // (cycle, t, excess) of lit in-box points, in time order.
+    let mut lit: Vec<(i64, f64, f64)> = t
+        .iter()
+        .zip(y)
+        .filter(|&(&ti, &yi)| in_box(ti) && yi - base > 0.5 * fit.depth)
+        .map(|(&ti, &yi)| (((ti - epoch_s) / period).round() as i64, ti, yi - base))
+        .collect();
+    lit.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let events = lit
+        .chunk_by(|a, b| a.0 == b.0)
+        .map(|c| {
+            let ex: f64 = c.iter().map(|p| p.2).sum();
+            GlintEvent {
+                t_s: c.iter().map(|p| p.1 * p.2).sum::<f64>() / ex,
+                width_s: c[c.len() - 1].1 - c[0].1,
+                amplitude: c.iter().map(|p| p.2).fold(0.0, f64::max),
+            }
+        })
+        .collect();
+    GlintReport {
+        period_s: period,
+        epoch_s,
+        width_s: fit.duty * period,
+        duty: fit.duty,
+        depth: fit.depth,
+        power: fit.power,
+        events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::periodicity::gls::argmax;
+
+    /// Irregular samples with a 4 s glint every 47.3 s on a noisy floor.
+    fn glint_train(noise: f64) -> (Vec<f64>, Vec<f64>) {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut t = Vec::new();
+        let mut y = Vec::new();
+        let mut ti = 0.0;
+        for _ in 0..1500 {
+            ti += 0.4 + 0.8 * rng.random::<f64>();
+            let e = noise * (rng.random::<f64>() - 0.5);
+            let lit = (fold_phase(ti, 47.3) - 0.3).abs() < 2.0 / 47.3;
+            t.push(ti);
+            y.push(if lit { 3.0 } else { 0.0 } + e);
+        }
+        (t, y)
+    }
+
+    #[test]
+    fn glint_train_period_width_and_flashes() {
+        let (t, y) = glint_train(0.5);
+        let w = vec![1.0; t.len()];
+        let o = BlsOptions::default();
+        let span = t[t.len() - 1] - t[0];
+        let (periods, capped) = bls_periods(20.0, 80.0, span, &o);
+        assert!(periods.windows(2).all(|p| p[0] < p[1]) && !capped);
+        let pg = bls_periodogram(&t, &y, &w, &periods, &o);
+        let i = argmax(&pg.score).unwrap();
+        assert!(
+            (pg.period_s[i] / 47.3 - 1.0).abs() < 1e-3,
+            "{}",
+            pg.period_s[i]
+        );
+        let fit = bls_box(&t, &y, &w, pg.period_s[i], &o).unwrap();
+        assert!((fit.duty * 47.3 - 4.0).abs() < 1.0, "{fit:?}");
+        assert!((fit.depth - 3.0).abs() < 0.5);
+        let nu = t.len() as f64 - 2.0;
+        let n_ind = bls_independent_trials(span, 20.0, 80.0, t.len(), &o);
+        assert!(fap_bls(fit.power, nu, n_ind) < 1e-10);
+
+        let g = glint_report(&t, &y, &w, pg.period_s[i], &fit);
+        let cycles = (span / 47.3) as usize;
+        assert!(g.events.len() + 1 >= cycles && g.events.len() <= cycles + 1);
+        for e in &g.events {
+            let d = (fold_phase(e.t_s, 47.3) - 0.3).abs();
+            assert!(
+                d < 2.0 / 47.3 && e.width_s < 4.0 && e.amplitude > 1.5,
+                "{e:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn noise_is_not_a_glint() {
+        let (t, y) = glint_train(0.5);
+        // Keep only the noise around the floor.
+        let y: Vec<f64> = y
+            .iter()
+            .map(|v| if *v > 1.5 { v - 3.0 } else { *v })
+            .collect();
+        let w = vec![1.0; t.len()];
+        let o = BlsOptions::default();
+        let span = t[t.len() - 1] - t[0];
+        let (periods, _) = bls_periods(20.0, 80.0, span, &o);
+        let pg = bls_periodogram(&t, &y, &w, &periods, &o);
+        let best = pg.score.iter().copied().fold(0.0, f64::max);
+        let n_ind = bls_independent_trials(span, 20.0, 80.0, t.len(), &o);
+        assert!(fap_bls(best, t.len() as f64 - 2.0, n_ind) > 0.05, "{best}");
+    }
+
+    #[test]
+    fn fap_matches_closed_form() {
+        // ν = 1: I_x(1/2, 1/2) = (2/π) asin(√x).
+        let p: f64 = 0.3;
+        let single = 0.5 * 2.0 / std::f64::consts::PI * (1.0 - p).sqrt().asin();
+        assert!((fap_bls(p, 1.0, 1.0) - single).abs() < 1e-10);
+        assert!(fap_bls(p, 1.0, 10.0) > single);
+        assert_eq!(fap_bls(0.0, 10.0, 5.0), 1.0);
+    }
+}
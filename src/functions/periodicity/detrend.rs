@@ -1,27 +1,35 @@
 //! Scale-dependent Auto detrend.
 //!
 //! Intra: this-pass mean + τ (or Λ). Inter/Full: global mean + global t +
-//! shared τ, **no per-pass intercepts**.
+//! shared τ, **no per-pass intercepts**. Multi-band and multi-sensor data
+//! get one raw (unstandardized) indicator per non-reference band / sensor at
+//! every scale; a sensor indicator collinear with a band indicator is dropped.
+//! Optical points in penumbra add a `−2.5·log10(illumination)` column.
 
 use crate::entities::assessment::Pass;
 use crate::entities::assessment::{DetrendColumn, DetrendMode, DetrendReport, SearchScale};
 use crate::entities::series::{Modality, Series};
+use crate::functions::phase_function::PhaseFunction;
 use nalgebra::{DMatrix, DVector};
 
 const HUBER_C: f64 = 1.345;
-const COND_DROP: f64 = 1.0e6;
-const COND_FAIL: f64 = 1.0e8;
+pub(crate) const COND_DROP: f64 = 1.0e6;
+pub(crate) const COND_FAIL: f64 = 1.0e8;
+/// Illumination floor for the shadow column (5 mag of fade).
+const ILLUM_FLOOR: f64 = 1.0e-2;
 
 pub struct DetrendResult {
     pub y: Vec<f64>,
     pub report: DetrendReport,
 }
 
+#[allow(clippy::collapsible_if)]
 pub fn auto_detrend(
     series: &Series,
     passes: &[Pass],
     scale: SearchScale,
     mode: DetrendMode,
+    phase_function: PhaseFunction,
     indices: Option<&[usize]>,
 ) -> DetrendResult {
     let t = series.t_s();
@@ -79,6 +87,16 @@ pub fn auto_detrend(
     let want_elev = matches!(mode, DetrendMode::Auto | DetrendMode::Elevation)
         && elev.is_some()
         && series.meta().modality == Modality::RfPower;
+    let illum = series.covariates().illumination.as_deref();
+    let want_shadow = matches!(mode, DetrendMode::Auto | DetrendMode::PhaseFunction)
+        && series.meta().modality != Modality::RfPower
+        && illum.is_some_and(|f| idx.iter().any(|&i| f[i] < 1.0));
+
+    let phase_col = if phase_function == PhaseFunction::LambertApprox {
+        DetrendColumn::PhaseLambert
+    } else {
+        DetrendColumn::PhaseFunction
+    };
 
     let mut cols: Vec<DetrendColumn> = Vec::new();
     let mut raw: Vec<Vec<f64>> = Vec::new();
@@ -93,6 +111,36 @@ pub fn auto_detrend(
     }
     raw.push(vec![1.0; idx.len()]);
 
+    // Per-band intercepts go in before any slope so they win the COND_DROP race.
+    let band_ref = series
+        .covariates()
+        .band_key
+        .as_deref()
+        .and_then(|keys| reference_key(keys, &idx));
+    if let (Some(keys), Some(reference)) = (series.covariates().band_key.as_deref(), band_ref) {
+        for (key, ind) in offset_indicators(keys, &idx, reference) {
+            let trial = append_design(&raw, &ind, &idx_weights(&w0, &idx));
+            if trial <= COND_DROP {
+                raw.push(ind);
+                cols.push(DetrendColumn::BandOffset { band: key });
+            }
+        }
+    }
+    let sensor_ref = series
+        .covariates()
+        .sensor_key
+        .as_deref()
+        .and_then(|keys| reference_key(keys, &idx));
+    if let (Some(keys), Some(reference)) = (series.covariates().sensor_key.as_deref(), sensor_ref) {
+        for (key, ind) in offset_indicators(keys, &idx, reference) {
+            let trial = append_design(&raw, &ind, &idx_weights(&w0, &idx));
+            if trial <= COND_DROP {
+                raw.push(ind);
+                cols.push(DetrendColumn::SensorOffset { key });
+            }
+        }
+    }
+
     let add_col = |raw: &mut Vec<Vec<f64>>,
                    cols: &mut Vec<DetrendColumn>,
                    col: DetrendColumn,
@@ -111,9 +159,12 @@ pub fn auto_detrend(
         let mut used_cov = false;
         if want_phase {
             if let Some(ph) = phi {
-                let lam: Vec<f64> = idx.iter().map(|&i| (1.0 + ph[i].cos()) / 2.0).collect();
+                let lam: Vec<f64> = idx
+                    .iter()
+                    .map(|&i| phase_function.phase_integral(ph[i]))
+                    .collect();
                 let before = cols.len();
-                add_col(&mut raw, &mut cols, DetrendColumn::PhaseLambert, lam);
+                add_col(&mut raw, &mut cols, phase_col, lam);
                 used_cov = cols.len() > before;
             }
         } else if want_elev {
@@ -152,8 +203,11 @@ pub fn auto_detrend(
         add_col(&mut raw, &mut cols, DetrendColumn::Tau, tau);
         if want_phase {
             if let Some(ph) = phi {
-                let lam: Vec<f64> = idx.iter().map(|&i| (1.0 + ph[i].cos()) / 2.0).collect();
-                add_col(&mut raw, &mut cols, DetrendColumn::PhaseLambert, lam);
+                let lam: Vec<f64> = idx
+                    .iter()
+                    .map(|&i| phase_function.phase_integral(ph[i]))
+                    .collect();
+                add_col(&mut raw, &mut cols, phase_col, lam);
             }
         } else if want_elev {
             if let Some(el) = elev {
@@ -162,6 +216,22 @@ pub fn auto_detrend(
             }
         }
     }
+    // Shadow fade at every scale: ingress must not survive into the search.
+    if want_shadow {
+        if let Some(f) = illum {
+            let fade: Vec<f64> = idx
+                .iter()
+                .map(|&i| {
+                    if f[i].is_finite() {
+                        -2.5 * f[i].clamp(ILLUM_FLOOR, 1.0).log10()
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            add_col(&mut raw, &mut cols, DetrendColumn::Illumination, fade);
+        }
+    }
 
     let yw: Vec<f64> = idx.iter().map(|&i| y[i]).collect();
     let ww: Vec<f64> = idx_weights(&w0, &idx);
@@ -169,6 +239,22 @@ pub fn auto_detrend(
         Some((coeffs, cond)) => {
             let fitted = apply_design(&raw, &coeffs);
             let resid: Vec<f64> = yw.iter().zip(fitted.iter()).map(|(a, b)| a - b).collect();
+            let band_offsets = band_ref
+                .map(|reference| {
+                    collect_offsets(&cols, &coeffs, reference, |c| match c {
+                        DetrendColumn::BandOffset { band } => Some(*band),
+                        _ => None,
+                    })
+                })
+                .unwrap_or_default();
+            let sensor_offsets = sensor_ref
+                .map(|reference| {
+                    collect_offsets(&cols, &coeffs, reference, |c| match c {
+                        DetrendColumn::SensorOffset { key } => Some(*key),
+                        _ => None,
+                    })
+                })
+                .unwrap_or_default();
             DetrendResult {
                 y: resid,
                 report: DetrendReport {
@@ -181,6 +267,8 @@ pub fn auto_detrend(
                     floating_mean_gls: false,
                     fallback: false,
                     pass_mean,
+                    band_offsets,
+                    sensor_offsets,
                 },
             }
         }
@@ -221,6 +309,8 @@ pub fn auto_detrend(
                     floating_mean_gls: false,
                     fallback: true,
                     pass_mean,
+                    band_offsets: Vec::new(),
+                    sensor_offsets: Vec::new(),
                 },
             }
         }
@@ -249,6 +339,61 @@ pub fn pass_index_lists(t: &[f64], passes: &[Pass]) -> Vec<Vec<usize>> {
     lists
 }
 
+/// Most populous key in scope (ties → smallest key). `None` if fewer than two
+/// distinct keys are present.
+fn reference_key(keys: &[u16], idx: &[usize]) -> Option<u16> {
+    let mut counts: Vec<(u16, usize)> = Vec::new();
+    for &i in idx {
+        match counts.iter_mut().find(|(k, _)| *k == keys[i]) {
+            Some(c) => c.1 += 1,
+            None => counts.push((keys[i], 1)),
+        }
+    }
+    if counts.len() < 2 {
+        return None;
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    Some(counts[0].0)
+}
+
+/// One 0/1 column per non-reference key, in ascending key order.
+fn offset_indicators(keys: &[u16], idx: &[usize], reference: u16) -> Vec<(u16, Vec<f64>)> {
+    let mut distinct: Vec<u16> = idx.iter().map(|&i| keys[i]).collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+    distinct
+        .into_iter()
+        .filter(|&k| k != reference)
+        .map(|k| {
+            let col = idx
+                .iter()
+                .map(|&i| if keys[i] == k { 1.0 } else { 0.0 })
+                .collect();
+            (k, col)
+        })
+        .collect()
+}
+
+/// Reference at 0 followed by each kept indicator's coefficient. Empty when
+/// every indicator was dropped for collinearity: no offset was fitted.
+fn collect_offsets(
+    cols: &[DetrendColumn],
+    coeffs: &[f64],
+    reference: u16,
+    key_of: impl Fn(&DetrendColumn) -> Option<u16>,
+) -> Vec<(u16, f64)> {
+    let mut out = vec![(reference, 0.0)];
+    for (col, &b) in cols.iter().zip(coeffs.iter()) {
+        if let Some(key) = key_of(col) {
+            out.push((key, b));
+        }
+    }
+    if out.len() == 1 {
+        out.clear();
+    }
+    out
+}
+
 fn idx_weights(w: &[f64], idx: &[usize]) -> Vec<f64> {
     idx.iter().map(|&i| w[i]).collect()
 }
@@ -279,6 +424,7 @@ fn append_design(existing: &[Vec<f64>], new_col: &[f64], w: &[f64]) -> f64 {
     cond_xtwx(&x, w)
 }
 
+#[allow(clippy::match_single_binding)]
 fn cond_xtwx(x: &DMatrix<f64>, w: &[f64]) -> f64 {
     let k = x.ncols();
     let n = x.nrows();
@@ -309,11 +455,7 @@ fn cond_xtwx(x: &DMatrix<f64>, w: &[f64]) -> f64 {
                     lo = av;
                 }
             }
-            if lo <= 0.0 {
-                f64::INFINITY
-            } else {
-                hi / lo
-            }
+            if lo <= 0.0 { f64::INFINITY } else { hi / lo }
         }
     }
 }
@@ -388,3 +530,25 @@ fn apply_design(cols: &[Vec<f64>], beta: &[f64]) -> Vec<f64> {
     }
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_are_empty_when_every_indicator_was_dropped() {
+        let band = |c: &DetrendColumn| match c {
+            DetrendColumn::BandOffset { band } => Some(*band),
+            _ => None,
+        };
+        let kept = [
+            DetrendColumn::GlobalMean,
+            DetrendColumn::BandOffset { band: 6 },
+        ];
+        assert_eq!(
+            collect_offsets(&kept, &[1.0, -0.4], 2, band),
+            vec![(2, 0.0), (6, -0.4)]
+        );
+        assert!(collect_offsets(&kept[..1], &[1.0], 2, band).is_empty());
+    }
+}
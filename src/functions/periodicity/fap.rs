@@ -67,13 +67,17 @@ pub fn perm_seed(rng_seed: u64, i: u64) -> u64 {
 
 pub fn beats(kind: ScoreKind, null_score: f64, data_score: f64) -> bool {
     match kind {
-        ScoreKind::GlsPower | ScoreKind::LogOdds => null_score >= data_score,
-        ScoreKind::PdmTheta | ScoreKind::StringLengthRatio => null_score <= data_score,
+        ScoreKind::GlsPower | ScoreKind::LogOdds | ScoreKind::BlsPower => null_score >= data_score,
+        ScoreKind::PdmTheta
+        | ScoreKind::StringLengthRatio
+        | ScoreKind::ConditionalEntropy
+        | ScoreKind::LaflerKinmanTheta => null_score <= data_score,
         ScoreKind::SpectralWindow => null_score >= data_score,
     }
 }
 
 /// Shuffle `y` (and optional per-point σ) at fixed `t`. Returns n_beat.
+#[allow(clippy::too_many_arguments)]
 pub fn local_zero_beat(
     t: &[f64],
     y: &[f64],
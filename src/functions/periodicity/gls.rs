@@ -58,6 +58,28 @@ pub fn gls_power_zero_mean(
     ((chi2_0 - chi2_h) / chi2_0).clamp(0.0, 1.0)
 }
 
+/// Closed form of [`gls_power_zero_mean`] at one harmonic, without the
+/// per-point allocation; for inner loops such as the (P, Ṗ) search.
+pub fn zero_mean_h1_power(t: &[f64], y: &[f64], w: &[f64], freq_hz: f64) -> f64 {
+    let omega = std::f64::consts::TAU * freq_hz;
+    let (mut yy, mut yc, mut ys, mut cc, mut ss, mut cs) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for i in 0..t.len() {
+        let wi = w[i];
+        let (s, c) = (omega * t[i]).sin_cos();
+        yy += wi * y[i] * y[i];
+        yc += wi * y[i] * c;
+        ys += wi * y[i] * s;
+        cc += wi * c * c;
+        ss += wi * s * s;
+        cs += wi * c * s;
+    }
+    let det = cc * ss - cs * cs;
+    if t.len() < 3 || yy <= 0.0 || det <= 0.0 {
+        return 0.0;
+    }
+    ((yc * yc * ss + ys * ys * cc - 2.0 * yc * ys * cs) / (yy * det)).clamp(0.0, 1.0)
+}
+
 /// Zechmeister & Kürster 2009 eq. 20 — floating-mean single-frequency GLS.
 pub fn floating_mean_gls_power(t: &[f64], y: &[f64], w: &[f64], freq_hz: f64) -> f64 {
     let n = t.len();
@@ -163,6 +185,7 @@ pub fn gls_periodogram(
 
 /// Coarse log grid + ±5% refine around the top-`n_peaks` local maxima.
 /// Does **not** shrink `f_max`.
+#[allow(clippy::too_many_arguments)]
 pub fn coarse_refine_periods(
     p_min: f64,
     p_max: f64,
@@ -212,7 +235,8 @@ pub fn coarse_refine_periods(
     (p, s)
 }
 
-fn top_k_peaks(periods: &[f64], scores: &[f64], k: usize) -> Vec<f64> {
+/// Periods of the `k` highest local maxima, best first.
+pub(crate) fn top_k_peaks(periods: &[f64], scores: &[f64], k: usize) -> Vec<f64> {
     let n = periods.len().min(scores.len());
     if n == 0 {
         return Vec::new();
@@ -0,0 +1,87 @@
+//! Lafler & Kinman (1965) phase-ordered string length.
+//!
+//! Points are sorted by phase and the squared successive differences are
+//! summed around the cycle, wrapping the last point to the first. The sum
+//! is divided by its white-noise expectation, `2·N·s²`, so
+//! `Θ = (N − 1) Σ (y_{i+1} − y_i)² / (2N Σ (y_i − ȳ)²)` is 1 for noise
+//! and falls towards 0 for a smooth fold at any duty cycle. Lower is
+//! better.
+
+use crate::entities::assessment::{Periodogram, ScoreKind};
+use crate::functions::periodicity::fold_phase;
+use rayon::prelude::*;
+
+/// Normalized Lafler–Kinman Θ at `period`, or `None` for a flat or
+/// too-short series.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+pub fn lafler_kinman_theta(t: &[f64], y: &[f64], period: f64) -> Option<f64> {
+    if t.len() < 4 || !(period > 0.0) {
+        return None;
+    }
+    let mut folded: Vec<(f64, f64)> = t
+        .iter()
+        .zip(y)
+        .map(|(&ti, &yi)| (fold_phase(ti, period), yi))
+        .filter(|(phi, _)| phi.is_finite())
+        .collect();
+    let n = folded.len();
+    if n < 4 {
+        return None;
+    }
+    folded.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mean = folded.iter().map(|p| p.1).sum::<f64>() / n as f64;
+    let ss_tot: f64 = folded.iter().map(|p| (p.1 - mean) * (p.1 - mean)).sum();
+    if !(ss_tot > 0.0) {
+        return None;
+    }
+    let ss_diff: f64 = (0..n)
+        .map(|i| {
+            let d = folded[(i + 1) % n].1 - folded[i].1;
+            d * d
+        })
+        .sum();
+    Some((n - 1) as f64 * ss_diff / (2.0 * n as f64 * ss_tot))
+}
+
+pub fn lafler_kinman_periodogram(t: &[f64], y: &[f64], periods: &[f64]) -> Periodogram {
+    let score: Vec<f64> = periods
+        .par_iter()
+        .map(|&p| lafler_kinman_theta(t, y, p).unwrap_or(f64::INFINITY))
+        .collect();
+    Periodogram {
+        period_s: periods.to_vec(),
+        score,
+        score_kind: ScoreKind::LaflerKinmanTheta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_theta_is_near_one() {
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(11);
+        let t: Vec<f64> = (0..300).map(|i| i as f64 * 1.37).collect();
+        let mean = (0..200)
+            .map(|_| {
+                let y: Vec<f64> = (0..t.len()).map(|_| rng.random::<f64>()).collect();
+                lafler_kinman_theta(&t, &y, 17.3).unwrap()
+            })
+            .sum::<f64>()
+            / 200.0;
+        assert!((mean - 1.0).abs() < 0.02, "{mean}");
+    }
+
+    #[test]
+    fn sawtooth_prefers_true_period() {
+        let p_true = 13.0;
+        let t: Vec<f64> = (0..150).map(|i| i as f64 * 0.77).collect();
+        let y: Vec<f64> = t.iter().map(|&x| fold_phase(x, p_true)).collect();
+        let th = lafler_kinman_theta(&t, &y, p_true).unwrap();
+        assert!(th < 0.05, "{th}");
+        assert!(lafler_kinman_theta(&t, &y, 9.1).unwrap() > 0.5);
+        assert!(lafler_kinman_theta(&t, &[1.0; 150], p_true).is_none());
+    }
+}
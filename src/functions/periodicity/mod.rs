@@ -1,11 +1,18 @@
 pub mod assess;
+pub mod bls;
 pub mod detrend;
+pub mod entropy;
 pub mod fap;
 pub mod gls;
+pub mod lafler_kinman;
+pub mod multiband;
 pub mod pdm;
+pub mod pdot;
+pub mod track;
 pub mod window;
 
-pub use assess::assess_periodicity;
+pub use assess::{assess_periodicity, assess_periodicity_with_pab, assess_sampling};
+pub use track::track_periods;
 
 use crate::entities::lightcurve::Lightcurve;
 use crate::entities::observation::Observation;
@@ -448,7 +455,8 @@ fn safe_ln(x: f64) -> f64 {
 }
 
 /// Lanczos approximation to log Γ(x); reflection for x < 0.5.
-fn log_gamma(x: f64) -> f64 {
+#[allow(clippy::excessive_precision)]
+pub(crate) fn log_gamma(x: f64) -> f64 {
     if x < 0.5 {
         return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - log_gamma(1.0 - x);
     }
@@ -526,6 +534,7 @@ impl QuasiPeriodicGPPeriodEstimator {
     /// scan + refine around top-5 peaks); `max_obs` = 600 (pass-stratified
     /// subsample when the lightcurve is larger). Inherent accept is still
     /// `log_odds > 5`.
+    #[allow(clippy::too_many_arguments)]
     pub fn estimate_period(
         lightcurve: &Lightcurve,
         min_period: f64,
@@ -1010,6 +1019,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             };
             observations.push(obs);
@@ -1075,6 +1087,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             };
             observations.push(obs);
@@ -1128,6 +1143,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1191,6 +1209,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1250,6 +1271,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1368,6 +1392,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1426,6 +1453,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp,
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1467,6 +1497,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: y,
                 timestamp: base_time + chrono::Duration::milliseconds((t * 1000.0) as i64),
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1505,6 +1538,9 @@ mod tests {
                 timestamp: DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
                     .unwrap()
                     .with_timezone(&Utc),
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             }],
             None,
@@ -1534,6 +1570,9 @@ mod tests {
                 phase_rad: 0.0,
                 std_magnitude: magnitude,
                 timestamp: base_time + chrono::Duration::milliseconds((t * 1000.0) as i64),
+                band: None,
+                elevation_rad: None,
+                illumination: None,
                 fractional_period: None,
             });
         }
@@ -1573,6 +1612,9 @@ mod tests {
                     std_magnitude: 10.0 + 0.01 * ((i % 3) as f64 - 1.0),
                     timestamp: base_time
                         + chrono::Duration::milliseconds(((t0 + i as f64) * 1000.0) as i64),
+                    band: None,
+                    elevation_rad: None,
+                    illumination: None,
                     fractional_period: None,
                 });
             }
@@ -2,12 +2,24 @@
 //!
 //! Lower θ is better. Trials whose occupied-bin fraction is below 0.4 are
 //! **rejected** (no θ), not softly penalized.
+//!
+//! With `covers > 1` each of `covers` bin sets is shifted by `1/(m·covers)`
+//! in phase and the within-bin variance is pooled over all of them
+//! (Stellingwerf's `(m, covers)` structures). Significance follows
+//! Schwarzenberg-Czerny (1997): for white noise the within/total variance
+//! ratio of one cover with `m` occupied bins over `N` points is
+//! `Beta((N − m)/2, (m − 1)/2)`. `N` and `m` are the counts θ pooled
+//! ([`PdmFit`]), so singleton and empty bins do not inflate them. Covers are
+//! not independent, so the same law is applied to the pooled θ with
+//! per-cover counts; it is an approximation there.
 
 use crate::entities::assessment::{Periodogram, ScoreKind};
-use crate::functions::periodicity::fold_phase;
+use crate::functions::periodicity::{fold_phase, log_gamma};
 use rayon::prelude::*;
 
 const OCCUPANCY_FLOOR: f64 = 0.4;
+/// Continued-fraction budget for the incomplete beta function.
+const BETA_MAX_ITER: usize = 300;
 
 /// Adaptive bin count: `min(10, max(4, n/3))`.
 pub fn pdm_bin_count(n_in_scope: usize, override_m: Option<usize>) -> usize {
@@ -18,54 +30,113 @@ pub fn pdm_bin_count(n_in_scope: usize, override_m: Option<usize>) -> usize {
     adapt.min(10)
 }
 
+/// θ of one trial period with the counts behind it, summed over covers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PdmFit {
+    pub theta: f64,
+    /// Points in bins holding at least two.
+    pub n_used: usize,
+    /// Bins holding at least two points.
+    pub n_occ: usize,
+    pub covers: usize,
+}
+
+impl PdmFit {
+    /// `(points, occupied bins)` of one cover, rounded: the `n` and `m` of
+    /// [`fap_pdm`].
+    pub fn per_cover(&self) -> (usize, usize) {
+        let c = self.covers.max(1);
+        ((self.n_used + c / 2) / c, (self.n_occ + c / 2) / c)
+    }
+}
+
 /// Classic PDM θ, or `None` if occupancy is below the floor.
 pub fn pdm_theta(t: &[f64], y: &[f64], period: f64, m: usize) -> Option<f64> {
+    pdm_theta_covers(t, y, period, m, 1)
+}
+
+/// PDM θ over `covers` offset sets of `m` bins, or `None` if occupancy is
+/// below the floor. `covers = 1` is [`pdm_theta`].
+pub fn pdm_theta_covers(t: &[f64], y: &[f64], period: f64, m: usize, covers: usize) -> Option<f64> {
+    pdm_fit(t, y, period, m, covers).map(|f| f.theta)
+}
+
+/// [`pdm_theta_covers`] with the point and bin counts it pooled.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+pub fn pdm_fit(t: &[f64], y: &[f64], period: f64, m: usize, covers: usize) -> Option<PdmFit> {
     if m < 2 || t.len() < 4 || !(period > 0.0) {
         return None;
     }
-    let mut bins: Vec<Vec<f64>> = (0..m).map(|_| Vec::new()).collect();
-    for i in 0..t.len() {
-        let phi = fold_phase(t[i], period);
-        if !phi.is_finite() {
-            continue;
+    let covers = covers.max(1);
+    let phase: Vec<f64> = t.iter().map(|&ti| fold_phase(ti, period)).collect();
+    let (mut n_occ, mut n_used) = (0usize, 0usize);
+    let (mut ss_within, mut ss_tot) = (0.0, 0.0);
+    let mut n_tot_dof = 0usize;
+    for c in 0..covers {
+        let shift = c as f64 / (m * covers) as f64;
+        let mut bins: Vec<Vec<f64>> = (0..m).map(|_| Vec::new()).collect();
+        for (&phi, &yi) in phase.iter().zip(y) {
+            if !phi.is_finite() {
+                continue;
+            }
+            let idx = (((phi + shift).fract() * m as f64) as usize).min(m - 1);
+            bins[idx].push(yi);
         }
-        let idx = ((phi * m as f64) as usize).min(m - 1);
-        bins[idx].push(y[i]);
-    }
-    let mut n_occ = 0usize;
-    let mut n_used = 0usize;
-    let mut ss_within = 0.0;
-    let mut used: Vec<f64> = Vec::new();
-    for b in &bins {
-        if b.len() < 2 {
-            continue;
+        let mut used: Vec<f64> = Vec::new();
+        for b in &bins {
+            if b.len() < 2 {
+                continue;
+            }
+            n_occ += 1;
+            n_used += b.len();
+            let mean = b.iter().sum::<f64>() / b.len() as f64;
+            // (n_j − 1) s_j² = RSS
+            ss_within += b.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>();
+            used.extend_from_slice(b);
+        }
+        if used.len() >= 2 {
+            let mean_all = used.iter().sum::<f64>() / used.len() as f64;
+            ss_tot += used
+                .iter()
+                .map(|v| (v - mean_all) * (v - mean_all))
+                .sum::<f64>();
+            n_tot_dof += used.len() - 1;
         }
-        n_occ += 1;
-        n_used += b.len();
-        let mean = b.iter().sum::<f64>() / b.len() as f64;
-        let rss: f64 = b.iter().map(|v| (v - mean) * (v - mean)).sum();
-        // (n_j − 1) s_j² = RSS
-        ss_within += rss;
-        used.extend_from_slice(b);
-    }
-    if (n_occ as f64) < OCCUPANCY_FLOOR * m as f64 || n_used <= n_occ {
-        return None;
     }
-    let mean_all = used.iter().sum::<f64>() / used.len() as f64;
-    let ss_tot: f64 = used.iter().map(|v| (v - mean_all) * (v - mean_all)).sum();
-    if ss_tot <= 0.0 {
-        return Some(0.0);
+    if (n_occ as f64) < OCCUPANCY_FLOOR * (m * covers) as f64 || n_used <= n_occ {
+        return None;
     }
-    // θ = Σ (n_j−1) s_j² / ((N_used − n_occ) s_tot²)
-    // s_tot² = ss_tot / (N_used − 1)
-    let s_tot2 = ss_tot / (n_used - 1) as f64;
-    Some(ss_within / ((n_used - n_occ) as f64 * s_tot2))
+    // θ = Σ (n_j−1) s_j² / ((N_used − n_occ) s_tot²), s_tot² = ss_tot / (N_used − 1),
+    // each sum taken over every cover.
+    let theta = if ss_tot <= 0.0 {
+        0.0
+    } else {
+        ss_within / ((n_used - n_occ) as f64 * (ss_tot / n_tot_dof as f64))
+    };
+    Some(PdmFit {
+        theta,
+        n_used,
+        n_occ,
+        covers,
+    })
 }
 
+/// Classic PDM θ per period; occupancy-rejected trials score `+∞`.
 pub fn pdm_periodogram(t: &[f64], y: &[f64], periods: &[f64], m: usize) -> Periodogram {
+    pdm_periodogram_covers(t, y, periods, m, 1)
+}
+
+/// [`pdm_periodogram`] over `covers` offset bin sets.
+pub fn pdm_periodogram_covers(
+    t: &[f64],
+    y: &[f64],
+    periods: &[f64],
+    m: usize,
+    covers: usize,
+) -> Periodogram {
     let score: Vec<f64> = periods
         .par_iter()
-        .map(|&p| pdm_theta(t, y, p, m).unwrap_or(f64::INFINITY))
+        .map(|&p| pdm_theta_covers(t, y, p, m, covers).unwrap_or(f64::INFINITY))
         .collect();
     Periodogram {
         period_s: periods.to_vec(),
@@ -84,10 +155,108 @@ pub fn argmin_finite(scores: &[f64]) -> Option<usize> {
         .map(|(i, _)| i)
 }
 
+/// Single-trial probability that white noise reaches `theta` or lower with
+/// `n` points in `m` bins. The beta law holds for the raw variance ratio
+/// `x = θ·(n − m)/(n − 1)`: `P = I_x((n − m)/2, (m − 1)/2)`.
+pub fn pdm_theta_cdf(theta: f64, n: usize, m: usize) -> f64 {
+    if n <= m || m < 2 || !theta.is_finite() {
+        return 1.0;
+    }
+    let x = theta * (n - m) as f64 / (n - 1) as f64;
+    beta_cdf(
+        x.clamp(0.0, 1.0),
+        0.5 * (n - m) as f64,
+        0.5 * (m - 1) as f64,
+    )
+}
+
+/// FAP of the lowest θ over a search with `n_independent` independent
+/// frequencies: `1 − (1 − p)^{n_independent}`, `p` from [`pdm_theta_cdf`].
+pub fn fap_pdm(theta_min: f64, n: usize, m: usize, n_independent: f64) -> f64 {
+    let p = pdm_theta_cdf(theta_min, n, m);
+    if p >= 1.0 {
+        return 1.0;
+    }
+    (-(n_independent.max(1.0) * (-p).ln_1p()).exp_m1()).clamp(0.0, 1.0)
+}
+
+/// Regularized incomplete beta `I_x(a, b)` by Lentz's continued fraction,
+/// using the symmetry `I_x(a, b) = 1 − I_{1−x}(b, a)` where it converges
+/// faster.
+pub(crate) fn beta_cdf(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let ln_front = log_gamma(a + b) - log_gamma(a) - log_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_cf(x, a, b) / a
+    } else {
+        1.0 - ln_front.exp() * beta_cf(1.0 - x, b, a) / b
+    }
+}
+
+fn beta_cf(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for k in 1..=BETA_MAX_ITER {
+        let k = k as f64;
+        for num in [
+            k * (b - k) * x / ((a + 2.0 * k - 1.0) * (a + 2.0 * k)),
+            -(a + k) * (a + b + k) * x / ((a + 2.0 * k) * (a + 2.0 * k + 1.0)),
+        ] {
+            d = 1.0 + num * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + num / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn fit_counts_only_bins_with_two_points() {
+        // Four points in each of bins 0–4 of a 10 s fold, one alone in bin 7.
+        let mut t: Vec<f64> = (0..20)
+            .map(|i| (i / 4) as f64 + 0.1 * (i % 4) as f64)
+            .collect();
+        t.push(7.5);
+        let y: Vec<f64> = t.iter().map(|ti| (ti * 1.7).sin()).collect();
+        let fit = pdm_fit(&t, &y, 10.0, 10, 1).unwrap();
+        assert_eq!((fit.n_used, fit.n_occ), (20, 5));
+        assert_eq!(fit.per_cover(), (20, 5));
+        assert_eq!(pdm_theta(&t, &y, 10.0, 10), Some(fit.theta));
+
+        let pooled = PdmFit {
+            theta: 0.5,
+            n_used: 41,
+            n_occ: 19,
+            covers: 2,
+        };
+        assert_eq!(pooled.per_cover(), (21, 10));
+    }
+
     #[test]
     fn occupancy_reject_on_clump() {
         // All points in a tiny time window → most phase bins empty at long P.
@@ -105,8 +274,41 @@ mod tests {
             .map(|ti| (std::f64::consts::TAU * ti / p_true).sin())
             .collect();
         let m = pdm_bin_count(t.len(), None);
-        let th_true = pdm_theta(&t, &y, p_true, m).unwrap();
-        let th_wrong = pdm_theta(&t, &y, 6.3, m).unwrap();
-        assert!(th_true < th_wrong, "{th_true} vs {th_wrong}");
+        for covers in [1, 3] {
+            let th_true = pdm_theta_covers(&t, &y, p_true, m, covers).unwrap();
+            let th_wrong = pdm_theta_covers(&t, &y, 6.3, m, covers).unwrap();
+            assert!(th_true < th_wrong, "{th_true} vs {th_wrong}");
+        }
+    }
+
+    #[test]
+    fn beta_law_matches_closed_forms() {
+        // I_x(1, b) = 1 − (1 − x)^b and I_x(a, 1) = x^a.
+        for x in [0.05, 0.3, 0.7, 0.95] {
+            assert!((beta_cdf(x, 1.0, 4.5) - (1.0 - (1.0 - x).powf(4.5))).abs() < 1e-12);
+            assert!((beta_cdf(x, 37.5, 1.0) - x.powf(37.5)).abs() < 1e-12);
+        }
+        assert!((beta_cdf(0.5, 20.0, 20.0) - 0.5).abs() < 1e-12);
+        assert_eq!(pdm_theta_cdf(0.9, 5, 10), 1.0);
+        let p = pdm_theta_cdf(0.5, 100, 10);
+        assert!(fap_pdm(0.5, 100, 10, 50.0) > p && fap_pdm(0.5, 100, 10, 1.0) - p < 1e-15);
+    }
+
+    #[test]
+    fn noise_theta_is_calibrated() {
+        // Median θ of white noise at one trial sits near the Beta median.
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+        let mut rng = StdRng::seed_from_u64(7);
+        let t: Vec<f64> = (0..200).map(|i| i as f64 * 1.37).collect();
+        let m = 10;
+        let mut below = 0;
+        for _ in 0..400 {
+            let y: Vec<f64> = (0..t.len()).map(|_| rng.random::<f64>()).collect();
+            let th = pdm_theta(&t, &y, 17.3, m).unwrap();
+            if pdm_theta_cdf(th, t.len(), m) < 0.5 {
+                below += 1;
+            }
+        }
+        assert!((160..240).contains(&below), "{below}/400");
     }
 }
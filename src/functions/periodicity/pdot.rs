@@ -0,0 +1,220 @@
+//! Two-dimensional (P, Ṗ) search for spinning-up / spinning-down objects.
+//!
+//! A linear drift `P(t) = P₀ + Ṗ·Δt`, `Δt = t − t₀`, advances phase as
+//! `φ = τ / P₀` on the warped time `τ = Δt − ½κΔt²` with `κ = Ṗ / P₀`, to
+//! first order in `κΔt`. The warp does not depend on `P₀`, so each trial
+//! `κ` is an ordinary periodogram on warped times and every fixed-period
+//! score (GLS, PDM) applies unchanged. `t₀` is the middle of the span,
+//! where the warp leaves the mean frequency in place.
+//!
+//! [`drift_search`] scores `κ` by zero-mean H=1 GLS for every primary
+//! method, since the uncertainty comes from its χ² profile; the assessment
+//! re-scores the chosen `κ` with the primary statistic.
+//!
+//! `κ` is stepped so that one step moves the phase at the span ends by
+//! `1 / oversample` cycles of the candidate period, and each trial scans
+//! ±8 resolution elements around the candidate frequency, enough to reach
+//! the ±1 day aliases of a week-long campaign.
+
+use crate::functions::periodicity::gls::zero_mean_h1_power;
+use rayon::prelude::*;
+
+/// Resolution elements scanned either side of a candidate frequency.
+const BAND_RES: f64 = 8.0;
+
+/// Best `κ = Ṗ / P` from [`drift_search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DriftFit {
+    /// `κ` (1/s); `Ṗ = κ·P`.
+    pub rate: f64,
+    /// 1σ on `rate` from the curvature of the power profile; `None` at a
+    /// grid end or on a flat profile.
+    pub rate_unc: Option<f64>,
+    /// `t₀` of the warp.
+    pub epoch_s: f64,
+    /// Period at `epoch_s` for the best trial.
+    pub period_s: f64,
+    /// Zero-mean H=1 GLS power at the best trial.
+    pub power: f64,
+    /// Power of the same candidate at `κ = 0`.
+    pub power_fixed: f64,
+    /// `κ` trials for the chosen candidate, for look-elsewhere.
+    pub n_trials: usize,
+}
+
+/// `τ = Δt − ½·rate·Δt²`, `Δt = t − epoch_s`.
+pub fn warp_times(t: &[f64], epoch_s: f64, rate: f64) -> Vec<f64> {
+    t.iter()
+        .map(|&ti| {
+            let dt = ti - epoch_s;
+            dt - 0.5 * rate * dt * dt
+        })
+        .collect()
+}
+
+/// Symmetric `κ` grid through 0 for a candidate `period_s` and half-span
+/// `half_span_s`, with at most `max_trials` points. `max_rate` is clamped
+/// so the warp stays monotonic (`κ·half_span ≤ ½`).
+pub fn rate_grid(
+    period_s: f64,
+    half_span_s: f64,
+    max_rate: f64,
+    oversample: f64,
+    max_trials: usize,
+) -> Vec<f64> {
+    if !(period_s > 0.0 && half_span_s > 0.0 && max_rate > 0.0) {
+        return vec![0.0];
+    }
+    let max_rate = max_rate.min(0.5 / half_span_s);
+    let step = 2.0 * period_s / (oversample.max(1.0) * half_span_s * half_span_s);
+    let per_side = ((max_rate / step).ceil() as usize).min(max_trials.saturating_sub(1) / 2);
+    if per_side == 0 {
+        return vec![0.0];
+    }
+    let step = max_rate / per_side as f64;
+    (0..=2 * per_side)
+        .map(|k| (k as f64 - per_side as f64) * step)
+        .collect()
+}
+
+/// Best `(P, κ)` over the `κ` grid around each of `candidates` (periods
+/// from a fixed-period search), scored by zero-mean H=1 GLS. `t` must be
+/// sorted. `None` for an empty span or no candidates.
+pub fn drift_search(
+    t: &[f64],
+    y: &[f64],
+    w: &[f64],
+    candidates: &[f64],
+    max_rate: f64,
+    oversample: f64,
+    max_trials: usize,
+) -> Option<DriftFit> {
+    let (&first, &last) = (t.first()?, t.last()?);
+    let half = 0.5 * (last - first);
+    if half <= 0.0 {
+        return None;
+    }
+    let epoch = first + half;
+    let df = 1.0 / (oversample.max(1.0) * 2.0 * half);
+    let n_f = (2.0 * BAND_RES * oversample.max(1.0)).ceil() as usize + 1;
+
+    let mut best: Option<(DriftFit, Vec<f64>, Vec<f64>)> = None;
+    for &p_c in candidates.iter().filter(|p| **p > 0.0) {
+        let grid = rate_grid(p_c, half, max_rate, oversample, max_trials);
+        let f_lo = 1.0 / p_c - BAND_RES / (2.0 * half);
+        let freqs: Vec<f64> = (0..n_f)
+            .map(|k| f_lo + k as f64 * df)
+            .filter(|f| *f > 0.0)
+            .collect();
+        // (power, freq) of the best frequency at each κ.
+        let profile: Vec<(f64, f64)> = grid
+            .par_iter()
+            .map(|&k| {
+                let tw = warp_times(t, epoch, k);
+                freqs
+                    .iter()
+                    .map(|&f| (zero_mean_h1_power(&tw, y, w, f), f))
+                    .fold((0.0, 0.0), |a, b| if b.0 > a.0 { b } else { a })
+            })
+            .collect();
+        let Some(i) = (0..profile.len()).max_by(|&a, &b| profile[a].0.total_cmp(&profile[b].0))
+        else {
+            continue;
+        };
+        if best.as_ref().is_some_and(|b| b.0.power >= profile[i].0) {
+            continue;
+        }
+        let scores: Vec<f64> = profile.iter().map(|p| p.0).collect();
+        let i0 = grid.len() / 2;
+        let fit = DriftFit {
+            rate: grid[i],
+            rate_unc: None,
+            epoch_s: epoch,
+            period_s: 1.0 / profile[i].1,
+            power: profile[i].0,
+            power_fixed: profile[i0].0,
+            n_trials: grid.len(),
+        };
+        best = Some((fit, grid, scores));
+    }
+    let (mut fit, grid, scores) = best?;
+    let i = grid.iter().position(|&k| k == fit.rate)?;
+    if let Some((k, unc)) = vertex(&grid, &scores, i, t.len().saturating_sub(4)) {
+        fit.rate = k;
+        fit.rate_unc = Some(unc);
+    }
+    Some(fit)
+}
+
+/// Parabola through the profile at `i − 1, i, i + 1`: the vertex and the
+/// 1σ half-width where `χ²` rises by one, `χ² ∝ 1 − p` with `dof` degrees
+/// of freedom left for the noise estimate.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+fn vertex(x: &[f64], p: &[f64], i: usize, dof: usize) -> Option<(f64, f64)> {
+    if i == 0 || i + 1 >= x.len() || dof == 0 {
+        return None;
+    }
+    let h = x[i + 1] - x[i];
+    let (y0, y1, y2) = (p[i - 1], p[i], p[i + 1]);
+    let a = (y0 - 2.0 * y1 + y2) / (2.0 * h * h);
+    if !(a < 0.0) {
+        return None;
+    }
+    let b = (y2 - y0) / (2.0 * h);
+    let dx = (-b / (2.0 * a)).clamp(-h, h);
+    let peak = y1 + b * dx + a * dx * dx;
+    let unc = ((1.0 - peak).max(0.0) / (dof as f64 * -a)).sqrt();
+    Some((x[i] + dx, unc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::periodicity::gls::gls_power_zero_mean;
+    use crate::functions::sampling::geo_night_times;
+    use std::f64::consts::TAU;
+
+    #[test]
+    fn closed_form_h1_matches_general_gls() {
+        let t: Vec<f64> = (0..200).map(|i| i as f64 * 7.3 + (i % 5) as f64).collect();
+        let y: Vec<f64> = t.iter().map(|x| (TAU * x / 61.0).sin() + 0.1).collect();
+        let w = vec![1.0; t.len()];
+        for p in [30.0, 61.0, 250.0] {
+            let a = zero_mean_h1_power(&t, &y, &w, 1.0 / p);
+            let b = gls_power_zero_mean(&t, &y, &w, p, 1);
+            assert!((a - b).abs() < 1e-9, "P={p}: {a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn recovers_linear_spin_down_over_a_week() {
+        let t = geo_night_times(7, 80, 18_000.0, 0.0);
+        let (p0, epoch) = (1500.0, 0.5 * (t[0] + t[t.len() - 1]));
+        // Five cycles of phase slip at the span ends.
+        let half = 0.5 * (t[t.len() - 1] - t[0]);
+        let kappa = 2.0 * 5.0 * p0 / (half * half);
+        let tw = warp_times(&t, epoch, kappa);
+        let y: Vec<f64> = tw.iter().map(|x| (TAU * x / p0).sin()).collect();
+        let w = vec![1.0; t.len()];
+
+        let fit = drift_search(&t, &y, &w, &[p0 * 1.001], 4.0 * kappa, 5.0, 401).unwrap();
+        assert!(
+            (fit.rate / kappa - 1.0).abs() < 0.02,
+            "{} vs {kappa}",
+            fit.rate
+        );
+        assert!((fit.period_s / p0 - 1.0).abs() < 1e-3);
+        assert!(fit.power > 0.95 && fit.power_fixed < 0.5, "{fit:?}");
+        assert!(fit.rate_unc.is_some_and(|u| u > 0.0 && u < 0.05 * kappa));
+        assert!(fit.n_trials > 20 && fit.n_trials <= 401);
+    }
+
+    #[test]
+    fn grid_is_symmetric_and_capped() {
+        let g = rate_grid(100.0, 1.0e5, 1.0e-6, 5.0, 11);
+        assert_eq!(g.len(), 11);
+        assert_eq!(g[5], 0.0);
+        assert!((g[0] + g[10]).abs() < 1e-20 && (g[10] - 1.0e-6).abs() < 1e-18);
+        assert_eq!(rate_grid(100.0, 1.0e5, 0.0, 5.0, 11), vec![0.0]);
+    }
+}
@@ -0,0 +1,292 @@
+//! Sliding-window period tracking (dynamic periodogram).
+//!
+//! Windows are runs of consecutive intra-eligible passes, the same passes
+//! the multi-pass intra consensus searches one at a time; an ineligible
+//! pass ends a run. Each window gets the full decision of
+//! [`assess_periodicity`] at that scale, with the configured methods, and
+//! its detrended points are scored on one period grid shared by every
+//! window, so the rows stack into a period × epoch matrix. The matrix is
+//! always zero-mean H=1 GLS power, whatever the primary method, so rows are
+//! on one bounded scale and comparable across windows of different size.
+//!
+//! [`assess_periodicity`]: crate::functions::periodicity::assess_periodicity
+
+use crate::entities::assessment::{
+    PeriodSearchConfig, PeriodTrack, SamplingDiagnostics, ScoreKind, TrackEpoch, TrackOptions,
+};
+use crate::entities::series::Series;
+use crate::functions::periodicity::assess::{
+    intra_eligible, pass_window, sampling_cfg, search_harmonics, search_oversample,
+    search_time_scale, search_window,
+};
+use crate::functions::periodicity::gls::gls_periodogram;
+use crate::functions::sampling::diagnose_sampling;
+use crate::functions::time_scale::convert;
+use rayon::prelude::*;
+
+/// Period track of `series` over windows of `options.passes_per_window`
+/// eligible passes, advancing by `options.step_passes`. The shared grid is
+/// uniform in frequency between the largest window `p_min` and the smallest
+/// window `p_max`; it is empty when those do not overlap. Windows are
+/// searched in the time scale [`assess_periodicity`] would use, and epoch
+/// times are reported in the series' own scale.
+///
+/// [`assess_periodicity`]: crate::functions::periodicity::assess_periodicity
+pub fn track_periods(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    options: &TrackOptions,
+) -> PeriodTrack {
+    let own = series.meta().time_scale;
+    let scale = search_time_scale(series, config);
+    if scale == own {
+        return track_scaled(series, config, options);
+    }
+    match series.to_time_scale(scale) {
+        Ok(s) => {
+            let mut track = track_scaled(&s, config, options);
+            for p in &mut track.sampling.passes {
+                p.t_start_s = convert(p.t_start_s, scale, own);
+                p.t_end_s = convert(p.t_end_s, scale, own);
+            }
+            for e in &mut track.epochs {
+                e.t_start_s = convert(e.t_start_s, scale, own);
+                e.t_end_s = convert(e.t_end_s, scale, own);
+            }
+            track
+        }
+        Err(e) => PeriodTrack {
+            period_s: Vec::new(),
+            score_kind: ScoreKind::GlsPower,
+            epochs: Vec::new(),
+            sampling: SamplingDiagnostics::default(),
+            notes: vec![format!("time scale {scale}: {e}")],
+        },
+    }
+}
+
+fn track_scaled(
+    series: &Series,
+    config: &PeriodSearchConfig,
+    options: &TrackOptions,
+) -> PeriodTrack {
+    let oversample = search_oversample(config);
+    let sampling = diagnose_sampling(series, &sampling_cfg(series, config), oversample);
+    let mut track = PeriodTrack {
+        period_s: Vec::new(),
+        score_kind: ScoreKind::GlsPower,
+        epochs: Vec::new(),
+        sampling: sampling.clone(),
+        notes: Vec::new(),
+    };
+    if let Err(e) = config.validate() {
+        track.notes.push(format!("config: {e}"));
+        return track;
+    }
+    if options.passes_per_window == 0 || options.step_passes == 0 {
+        track
+            .notes
+            .push("passes_per_window and step_passes must be ≥ 1".into());
+        return track;
+    }
+    let eligible = intra_eligible(&sampling);
+    if eligible.len() < options.passes_per_window {
+        track.notes.push(format!(
+            "{} intra-eligible passes < {} per window",
+            eligible.len(),
+            options.passes_per_window
+        ));
+        return track;
+    }
+
+    // A window never straddles an ineligible pass: its gap would stretch
+    // the window span, and with it `p_max`, past what the data constrain.
+    let window_ids: Vec<&[usize]> = eligible
+        .chunk_by(|a, b| b - a == 1)
+        .flat_map(|run| {
+            run.windows(options.passes_per_window)
+                .step_by(options.step_passes)
+        })
+        .collect();
+    if window_ids.is_empty() {
+        track.notes.push(format!(
+            "no run of {} consecutive intra-eligible passes",
+            options.passes_per_window
+        ));
+        return track;
+    }
+
+    let h = search_harmonics(series, config);
+    let windows: Vec<_> = window_ids
+        .iter()
+        .map(|ids| pass_window(series, config, &sampling, ids))
+        .collect();
+    let p_min = windows.iter().map(|w| w.p_min).fold(0.0, f64::max);
+    let p_max = windows
+        .iter()
+        .map(|w| w.p_max)
+        .fold(f64::INFINITY, f64::min);
+    if options.n_periods >= 2 && p_min > 0.0 && p_max > p_min {
+        let (f_lo, f_hi) = (1.0 / p_max, 1.0 / p_min);
+        let df = (f_hi - f_lo) / (options.n_periods - 1) as f64;
+        track.period_s = (0..options.n_periods)
+            .rev()
+            .map(|k| 1.0 / (f_lo + k as f64 * df))
+            .collect();
+    } else {
+        track.notes.push(format!(
+            "no shared period grid: window bounds {p_min:.4}–{p_max:.4} s"
+        ));
+    }
+
+    track.epochs = window_ids
+        .into_iter()
+        .zip(&windows)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(ids, win)| {
+            let a = search_window(series, config, &sampling, win, h, oversample);
+            let score = if track.period_s.is_empty() {
+                Vec::new()
+            } else {
+                gls_periodogram(&win.t, &win.y, &win.w, &track.period_s, 1, true).score
+            };
+            TrackEpoch {
+                passes: ids.to_vec(),
+                t_start_s: sampling.passes[ids[0]].t_start_s,
+                t_end_s: sampling.passes[ids[ids.len() - 1]].t_end_s,
+                n: win.t.len(),
+                decision: a.decision,
+                period_s: a.period_s,
+                period_unc_s: a.period_unc_s,
+                fap: a.fap,
+                score,
+            }
+        })
+        .collect();
+    track
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assessment::PeriodicityDecision;
+    use crate::entities::series::{Covariates, Modality, SeriesMeta, SigmaSpec, TimeFrame, YUnit};
+    use crate::functions::sampling::geo_night_times;
+    use crate::functions::time_scale::TimeScale;
+    use std::f64::consts::TAU;
+
+    /// Seven nights; the period lengthens by 40 s per night.
+    fn drifting_week() -> Series {
+        let t = geo_night_times(7, 80, 18_000.0, 0.0);
+        let t0 = t[0];
+        let y: Vec<f64> = t
+            .iter()
+            .map(|&x| {
+                let night = ((x - t0) / 86_400.0).round();
+                (TAU * x / (1200.0 + 40.0 * night)).sin()
+            })
+            .collect();
+        Series::try_new(
+            t,
+            y,
+            SigmaSpec::Unknown,
+            Covariates::default(),
+            SeriesMeta {
+                modality: Modality::RfPower,
+                y_unit: YUnit::LinearPower,
+                label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn per_pass_track_follows_the_drift() {
+        let s = drifting_week();
+        let tr = track_periods(&s, &PeriodSearchConfig::default(), &TrackOptions::default());
+        assert_eq!(tr.epochs.len(), 7, "{:?}", tr.notes);
+        assert!(
+            tr.epochs
+                .iter()
+                .all(|e| e.decision == PeriodicityDecision::Periodic)
+        );
+        let periodic = tr.periodic();
+        assert_eq!(periodic.len(), 7);
+        for (k, (_, p, unc)) in periodic.iter().enumerate() {
+            let truth = 1200.0 + 40.0 * k as f64;
+            assert!((p / truth - 1.0).abs() < 0.02, "night {k}: {p} vs {truth}");
+            assert!(unc.is_some());
+        }
+        // Each row peaks near its own period on the shared grid.
+        for (k, e) in tr.epochs.iter().enumerate() {
+            assert_eq!(e.score.len(), tr.period_s.len());
+            let i = (0..e.score.len())
+                .max_by(|&a, &b| e.score[a].total_cmp(&e.score[b]))
+                .unwrap();
+            let truth = 1200.0 + 40.0 * k as f64;
+            assert!((tr.period_s[i] / truth - 1.0).abs() < 0.05);
+        }
+        assert!(tr.period_s.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn windows_slide_over_eligible_passes() {
+        let s = drifting_week();
+        let options = TrackOptions {
+            passes_per_window: 3,
+            step_passes: 2,
+            ..TrackOptions::default()
+        };
+        let tr = track_periods(&s, &PeriodSearchConfig::default(), &options);
+        assert_eq!(
+            tr.epochs
+                .iter()
+                .map(|e| e.passes.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![0, 1, 2], vec![2, 3, 4], vec![4, 5, 6]]
+        );
+        assert!(tr.epochs.iter().all(|e| e.n == 240));
+
+        let bad = TrackOptions {
+            step_passes: 0,
+            ..TrackOptions::default()
+        };
+        let tr = track_periods(&s, &PeriodSearchConfig::default(), &bad);
+        assert!(tr.epochs.is_empty() && !tr.notes.is_empty());
+    }
+
+    #[test]
+    fn windows_do_not_straddle_an_ineligible_pass() {
+        let s = drifting_week();
+        let night = |x: f64| ((x - s.t_s()[0]) / 86_400.0).round();
+        // Night 3 keeps 8 points: still a pass, too few for its own search.
+        let first = (0..s.len()).position(|i| night(s.t_s()[i]) == 3.0).unwrap();
+        let keep: Vec<usize> = (0..s.len())
+            .filter(|&i| night(s.t_s()[i]) != 3.0 || i < first + 8)
+            .collect();
+        let thinned = Series::try_new(
+            keep.iter().map(|&i| s.t_s()[i]).collect(),
+            keep.iter().map(|&i| s.y()[i]).collect(),
+            SigmaSpec::Unknown,
+            Covariates::default(),
+            s.meta().clone(),
+        )
+        .unwrap();
+        let options = TrackOptions {
+            passes_per_window: 2,
+            ..TrackOptions::default()
+        };
+        let tr = track_periods(&thinned, &PeriodSearchConfig::default(), &options);
+        assert_eq!(tr.sampling.passes.len(), 7);
+        assert_eq!(
+            tr.epochs
+                .iter()
+                .map(|e| e.passes.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![1, 2], vec![4, 5], vec![5, 6]]
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Empirical phase curves: magnitude versus solar phase angle, per object.
+//!
+//! Polynomial and cubic regression-spline fits are ordinary least squares in
+//! magnitude. H-G and H-G1-G2 are linear in flux (Muinonen et al. 2010),
+//! weighted by `1/f²` so each point counts as it would in magnitude space;
+//! `H` and the slope parameters follow by error propagation.
+//!
+//! [`residual_series`] turns the fit into a [`Series`] for `assess_periodicity`
+//! with the object's own phase behaviour removed.
+
+use crate::entities::lightcurve::Lightcurve;
+use crate::entities::observation::Observation;
+use crate::entities::passband::{Passband, UNTAGGED_BAND_KEY};
+use crate::entities::series::{
+    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
+};
+use crate::functions::phase_function::{PhaseFunction, fold_phase, hg_basis, hg1g2_basis};
+use crate::functions::shadow::{DEFAULT_MIN_ILLUMINATION, eclipse_keep};
+use crate::functions::time_scale::TimeScale;
+use nalgebra::{DMatrix, DVector};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhaseCurveModel {
+    /// `m(φ) = Σ c_k φ^k`, `k = 0..=degree`.
+    Polynomial { degree: usize },
+    /// Cubic regression spline with `n_knots` interior knots at phase quantiles.
+    Spline { n_knots: usize },
+    /// `m(φ) = H − 2.5 log10 Φ_HG(φ; G)`.
+    Hg,
+    /// `m(φ) = H − 2.5 log10 Φ_HG1G2(φ; G1, G2)`.
+    Hg1g2,
+}
+
+impl PhaseCurveModel {
+    fn n_params(&self) -> usize {
+        match *self {
+            PhaseCurveModel::Polynomial { degree } => degree + 1,
+            PhaseCurveModel::Spline { n_knots } => 4 + n_knots,
+            PhaseCurveModel::Hg => 2,
+            PhaseCurveModel::Hg1g2 => 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PhaseCurveError {
+    LengthMismatch,
+    TooFewPoints {
+        n: usize,
+        min: usize,
+    },
+    NonFinite,
+    /// Normal equations are singular, or the flux fit is non-physical (`Σa ≤ 0`).
+    Degenerate,
+}
+
+impl std::fmt::Display for PhaseCurveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhaseCurveError::LengthMismatch => {
+                write!(f, "phase and magnitude vectors have different lengths")
+            }
+            PhaseCurveError::TooFewPoints { n, min } => {
+                write!(f, "phase curve has {n} points; need at least {min}")
+            }
+            PhaseCurveError::NonFinite => write!(f, "phase curve input contains non-finite values"),
+            PhaseCurveError::Degenerate => write!(f, "phase-curve fit is degenerate"),
+        }
+    }
+}
+
+impl std::error::Error for PhaseCurveError {}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseCurveFit {
+    pub model: PhaseCurveModel,
+    /// Polynomial: `c_0..=c_d`. Spline: `c_0..c_3` then one coefficient per
+    /// knot. H-G: `[H, G]`. H-G1-G2: `[H, G1, G2]`.
+    pub params: Vec<f64>,
+    /// 1σ uncertainties on `params`, scaled by the residual variance.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::entities::schema::nonfinite_vec")
+    )]
+    pub param_sigma: Vec<f64>,
+    /// Interior spline knots (rad); empty for other models.
+    pub knots_rad: Vec<f64>,
+    /// `m − m_model` in input order.
+    pub residuals: Vec<f64>,
+    pub rms_mag: f64,
+}
+
+impl PhaseCurveFit {
+    /// Model magnitude at `phase_rad`.
+    pub fn evaluate(&self, phase_rad: f64) -> f64 {
+        let a = fold_phase(phase_rad);
+        match self.model {
+            PhaseCurveModel::Polynomial { .. } | PhaseCurveModel::Spline { .. } => {
+                linear_row(&self.model, &self.knots_rad, a)
+                    .iter()
+                    .zip(self.params.iter())
+                    .map(|(x, c)| x * c)
+                    .sum()
+            }
+            PhaseCurveModel::Hg => {
+                self.params[0] + PhaseFunction::Hg { g: self.params[1] }.magnitude(a)
+            }
+            PhaseCurveModel::Hg1g2 => {
+                self.params[0]
+                    + PhaseFunction::Hg1g2 {
+                        g1: self.params[1],
+                        g2: self.params[2],
+                    }
+                    .magnitude(a)
+            }
+        }
+    }
+}
+
+/// Fit `mag` against `phase_rad` (any unwrapped angle; folded into `[0, π]`).
+pub fn fit_phase_curve(
+    phase_rad: &[f64],
+    mag: &[f64],
+    model: PhaseCurveModel,
+) -> Result<PhaseCurveFit, PhaseCurveError> {
+    let n = phase_rad.len();
+    if mag.len() != n {
+        return Err(PhaseCurveError::LengthMismatch);
+    }
+    if phase_rad.iter().chain(mag.iter()).any(|v| !v.is_finite()) {
+        return Err(PhaseCurveError::NonFinite);
+    }
+    let k = model.n_params();
+    if n <= k {
+        return Err(PhaseCurveError::TooFewPoints { n, min: k + 1 });
+    }
+    let a: Vec<f64> = phase_rad.iter().map(|&p| fold_phase(p)).collect();
+
+    let (params, param_sigma, knots_rad) = match model {
+        PhaseCurveModel::Polynomial { .. } | PhaseCurveModel::Spline { .. } => {
+            let knots = match model {
+                PhaseCurveModel::Spline { n_knots } => quantile_knots(&a, n_knots),
+                _ => Vec::new(),
+            };
+            let rows: Vec<Vec<f64>> = a.iter().map(|&x| linear_row(&model, &knots, x)).collect();
+            let w = vec![1.0; n];
+            let (beta, cov) = wls(&rows, mag, &w).ok_or(PhaseCurveError::Degenerate)?;
+            let sigma = (0..k).map(|i| cov[(i, i)].max(0.0).sqrt()).collect();
+            (beta, sigma, knots)
+        }
+        PhaseCurveModel::Hg | PhaseCurveModel::Hg1g2 => {
+            let flux: Vec<f64> = mag.iter().map(|m| 10f64.powf(-0.4 * m)).collect();
+            let w: Vec<f64> = flux.iter().map(|f| 1.0 / (f * f)).collect();
+            let rows: Vec<Vec<f64>> = a
+                .iter()
+                .map(|&x| match model {
+                    PhaseCurveModel::Hg => hg_basis(x).to_vec(),
+                    _ => hg1g2_basis(x).to_vec(),
+                })
+                .collect();
+            let (amp, cov) = wls(&rows, &flux, &w).ok_or(PhaseCurveError::Degenerate)?;
+            let sum: f64 = amp.iter().sum();
+            if sum <= 0.0 {
+                return Err(PhaseCurveError::Degenerate);
+            }
+            // Slope parameters are amplitude fractions: H-G has G = a_2/Σ
+            // (Φ1 carries 1 − G); H-G1-G2 has G1 = a_1/Σ and G2 = a_2/Σ
+            // (Φ3 carries 1 − G1 − G2).
+            let slopes: &[usize] = match model {
+                PhaseCurveModel::Hg => &[1],
+                _ => &[0, 1],
+            };
+            // [H, slopes…] and its Jacobian w.r.t. the flux amplitudes.
+            let mut params = vec![-2.5 * sum.log10()];
+            params.extend(slopes.iter().map(|&j| amp[j] / sum));
+            let jac = DMatrix::from_fn(k, k, |r, c| {
+                if r == 0 {
+                    -2.5 / (std::f64::consts::LN_10 * sum)
+                } else {
+                    let delta = if slopes[r - 1] == c { 1.0 } else { 0.0 };
+                    (delta - params[r]) / sum
+                }
+            });
+            let cov_p = &jac * cov * jac.transpose();
+            let sigma = (0..k).map(|i| cov_p[(i, i)].max(0.0).sqrt()).collect();
+            (params, sigma, Vec::new())
+        }
+    };
+
+    let mut fit = PhaseCurveFit {
+        model,
+        params,
+        param_sigma,
+        knots_rad,
+        residuals: Vec::new(),
+        rms_mag: 0.0,
+    };
+    fit.residuals = a
+        .iter()
+        .zip(mag.iter())
+        .map(|(&x, m)| m - fit.evaluate(x))
+        .collect();
+    fit.rms_mag = (fit.residuals.iter().map(|r| r * r).sum::<f64>() / n as f64).sqrt();
+    Ok(fit)
+}
+
+/// Fit `std_magnitude` against `phase_rad` over the lit observations in `lc`.
+/// Points [`eclipse_keep`] rejects are left out of the fit but still get a
+/// residual, so `residuals` lines up with `lc.observations`.
+pub fn fit_lightcurve_phase_curve(
+    lc: &Lightcurve,
+    model: PhaseCurveModel,
+) -> Result<PhaseCurveFit, PhaseCurveError> {
+    let keep = lit_mask(lc);
+    let (phase, mag): (Vec<f64>, Vec<f64>) = lc
+        .observations
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| keep.as_ref().is_none_or(|k| k[*i]))
+        .map(|(_, o)| (o.phase_rad, o.std_magnitude))
+        .unzip();
+    let mut fit = fit_phase_curve(&phase, &mag, model)?;
+    fit.residuals = lc
+        .observations
+        .iter()
+        .map(|o| o.std_magnitude - fit.evaluate(o.phase_rad))
+        .collect();
+    Ok(fit)
+}
+
+/// Residual light curve as a [`Series`]. `fit` must come from
+/// [`fit_lightcurve_phase_curve`] on the same `lc`. Solar phase is left out of
+/// the covariates so Auto detrend does not refit a generic phase law on top.
+pub fn residual_series(lc: &Lightcurve, fit: &PhaseCurveFit) -> Result<Series, SeriesError> {
+    if fit.residuals.len() != lc.observations.len() {
+        return Err(SeriesError::LengthMismatch);
+    }
+    let any_band = lc.observations.iter().any(|o| o.band.is_some());
+    Series::try_new(
+        lc.observations.iter().map(|o| o.unix_seconds()).collect(),
+        fit.residuals.clone(),
+        SigmaSpec::Unknown,
+        Covariates {
+            range_m: Some(lc.observations.iter().map(|o| o.range_m).collect()),
+            elevation_rad: every(lc, |o| o.elevation_rad),
+            illumination: every(lc, |o| o.illumination),
+            band_key: any_band.then(|| {
+                lc.observations
+                    .iter()
+                    .map(|o| o.band.map(Passband::key).unwrap_or(UNTAGGED_BAND_KEY))
+                    .collect()
+            }),
+            keep: lit_mask(lc),
+            ..Covariates::default()
+        },
+        SeriesMeta {
+            modality: Modality::OpticalPhotometry,
+            y_unit: YUnit::Magnitude,
+            label: Some("phase-curve residuals".into()),
+            time_scale: TimeScale::Utc,
+            time_frame: TimeFrame::Observer,
+        },
+    )
+}
+
+/// `f` of every observation, or `None` when any point lacks it (the same
+/// all-or-nothing rule as [`Series::from_lightcurve`]).
+fn every(lc: &Lightcurve, f: impl Fn(&Observation) -> Option<f64>) -> Option<Vec<f64>> {
+    if lc.observations.is_empty() {
+        return None;
+    }
+    lc.observations.iter().map(f).collect()
+}
+
+/// [`eclipse_keep`] over the observations' illumination, when all have one.
+fn lit_mask(lc: &Lightcurve) -> Option<Vec<bool>> {
+    every(lc, |o| o.illumination).map(|f| eclipse_keep(&f, DEFAULT_MIN_ILLUMINATION))
+}
+
+fn linear_row(model: &PhaseCurveModel, knots: &[f64], x: f64) -> Vec<f64> {
+    match *model {
+        PhaseCurveModel::Polynomial { degree } => (0..=degree).map(|d| x.powi(d as i32)).collect(),
+        _ => {
+            let mut row = vec![1.0, x, x * x, x * x * x];
+            row.extend(knots.iter().map(|&kn| (x - kn).max(0.0).powi(3)));
+            row
+        }
+    }
+}
+
+fn quantile_knots(a: &[f64], n_knots: usize) -> Vec<f64> {
+    let mut s = a.to_vec();
+    s.sort_by(|x, y| x.total_cmp(y));
+    (1..=n_knots)
+        .map(|i| s[(i * (s.len() - 1)) / (n_knots + 1)])
+        .collect()
+}
+
+/// Weighted least squares via SVD of `√W X`. Returns `β` and its covariance
+/// scaled by the residual variance `χ²/(n − k)`.
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
+fn wls(rows: &[Vec<f64>], y: &[f64], w: &[f64]) -> Option<(Vec<f64>, DMatrix<f64>)> {
+    let n = rows.len();
+    let k = rows.first()?.len();
+    let xs = DMatrix::from_fn(n, k, |i, j| w[i].sqrt() * rows[i][j]);
+    let ys = DVector::from_fn(n, |i, _| w[i].sqrt() * y[i]);
+    let svd = xs.clone().svd(true, true);
+    let s_max = svd.singular_values.max();
+    if !(s_max > 0.0) || svd.singular_values.min() <= s_max * 1e-12 {
+        return None;
+    }
+    let beta = svd.solve(&ys, 0.0).ok()?;
+    let v_t = svd.v_t.as_ref()?;
+    let inv_s2 = DMatrix::from_diagonal(&svd.singular_values.map(|s| 1.0 / (s * s)));
+    let unscaled = v_t.transpose() * inv_s2 * v_t;
+    let chi2 = (ys - xs * &beta).norm_squared();
+    let s2 = chi2 / (n - k).max(1) as f64;
+    Some((beta.iter().copied().collect(), unscaled * s2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    const DEG: f64 = std::f64::consts::PI / 180.0;
+
+    fn lcg_noise(n: usize, amp: f64, seed: u64) -> Vec<f64> {
+        let mut s = seed;
+        (0..n)
+            .map(|_| {
+                s = s.wrapping_mul(1103515245).wrapping_add(12345);
+                amp * (s as f64 / u64::MAX as f64 - 0.5)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hg_parameters_are_recovered_with_uncertainties() {
+        let phase: Vec<f64> = (0..80).map(|i| (2.0 + 0.75 * i as f64) * DEG).collect();
+        let noise = lcg_noise(phase.len(), 0.04, 9);
+        let truth = PhaseFunction::Hg { g: 0.25 };
+        let mag: Vec<f64> = phase
+            .iter()
+            .zip(noise.iter())
+            .map(|(&p, e)| 7.0 + truth.magnitude(p) + e)
+            .collect();
+        let fit = fit_phase_curve(&phase, &mag, PhaseCurveModel::Hg).unwrap();
+        let (h, g) = (fit.params[0], fit.params[1]);
+        assert!((h - 7.0).abs() < 3.0 * fit.param_sigma[0] + 0.01, "H={h}");
+        assert!((g - 0.25).abs() < 3.0 * fit.param_sigma[1] + 0.02, "G={g}");
+        assert!(fit.param_sigma.iter().all(|s| *s > 0.0 && *s < 0.1));
+        assert!(fit.rms_mag < 0.03);
+    }
+
+    #[test]
+    fn hg1g2_parameters_are_recovered_with_uncertainties() {
+        let phase: Vec<f64> = (0..120).map(|i| (1.0 + 0.8 * i as f64) * DEG).collect();
+        let noise = lcg_noise(phase.len(), 0.02, 23);
+        let truth = PhaseFunction::Hg1g2 { g1: 0.6, g2: 0.15 };
+        let mag: Vec<f64> = phase
+            .iter()
+            .zip(noise.iter())
+            .map(|(&p, e)| 8.0 + truth.magnitude(p) + e)
+            .collect();
+        let fit = fit_phase_curve(&phase, &mag, PhaseCurveModel::Hg1g2).unwrap();
+        let (h, g1, g2) = (fit.params[0], fit.params[1], fit.params[2]);
+        assert!((h - 8.0).abs() < 3.0 * fit.param_sigma[0] + 0.01, "H={h}");
+        assert!(
+            (g1 - 0.6).abs() < 3.0 * fit.param_sigma[1] + 0.02,
+            "G1={g1}"
+        );
+        assert!(
+            (g2 - 0.15).abs() < 3.0 * fit.param_sigma[2] + 0.02,
+            "G2={g2}"
+        );
+        assert!(fit.param_sigma.iter().all(|s| *s > 0.0 && *s < 0.1));
+        assert!(fit.rms_mag < 0.015, "{}", fit.rms_mag);
+    }
+
+    #[test]
+    fn polynomial_and_spline_follow_a_smooth_curve() {
+        let phase: Vec<f64> = (0..60).map(|i| (5.0 + 2.0 * i as f64) * DEG).collect();
+        let mag: Vec<f64> = phase
+            .iter()
+            .map(|&p| 10.0 + 0.8 * p - 0.3 * p * p)
+            .collect();
+        let poly =
+            fit_phase_curve(&phase, &mag, PhaseCurveModel::Polynomial { degree: 2 }).unwrap();
+        assert!((poly.params[1] - 0.8).abs() < 1e-9 && (poly.params[2] + 0.3).abs() < 1e-9);
+        let spl = fit_phase_curve(&phase, &mag, PhaseCurveModel::Spline { n_knots: 3 }).unwrap();
+        assert_eq!(spl.knots_rad.len(), 3);
+        assert!(spl.rms_mag < 1e-9, "{}", spl.rms_mag);
+        assert!((spl.evaluate(50.0 * DEG) - poly.evaluate(50.0 * DEG)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn too_few_points_is_an_error() {
+        let e = fit_phase_curve(&[0.1, 0.2], &[1.0, 1.1], PhaseCurveModel::Hg1g2).unwrap_err();
+        assert_eq!(e, PhaseCurveError::TooFewPoints { n: 2, min: 4 });
+    }
+
+    #[test]
+    fn residual_series_drops_phase_and_keeps_time_order() {
+        let base = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let obs: Vec<Observation> = (0..40)
+            .map(|i| {
+                let ts = base + chrono::Duration::seconds(30 * i);
+                let phase = (10.0 + i as f64) * DEG;
+                let mut o = Observation::new_default_normalization(9.0, 4.0e7, phase, ts);
+                o.std_magnitude = 6.0 + 1.5 * phase + 0.1 * (i as f64 * 0.9).sin();
+                o
+            })
+            .collect();
+        let lc = Lightcurve::new(obs, None, None);
+        let fit =
+            fit_lightcurve_phase_curve(&lc, PhaseCurveModel::Polynomial { degree: 1 }).unwrap();
+        assert!((fit.params[1] - 1.5).abs() < 0.1);
+        let s = residual_series(&lc, &fit).unwrap();
+        assert_eq!(s.len(), 40);
+        assert!(s.covariates().solar_phase_rad.is_none());
+        assert!(s.y().iter().all(|r| r.abs() < 0.15));
+    }
+
+    #[test]
+    fn shadowed_points_stay_out_of_the_fit_and_the_series() {
+        let base = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let obs: Vec<Observation> = (0..40)
+            .map(|i| {
+                let ts = base + chrono::Duration::seconds(30 * i);
+                let phase = (10.0 + i as f64) * DEG;
+                let mut o = Observation::new_default_normalization(9.0, 4.0e7, phase, ts);
+                let shadowed = (30..36).contains(&i);
+                o.std_magnitude = 6.0 + 1.5 * phase + if shadowed { 4.0 } else { 0.0 };
+                o.elevation_rad = Some(0.6);
+                o.illumination = Some(if shadowed { 0.0 } else { 1.0 });
+                o
+            })
+            .collect();
+        let lc = Lightcurve::new(obs, None, None);
+        let fit =
+            fit_lightcurve_phase_curve(&lc, PhaseCurveModel::Polynomial { degree: 1 }).unwrap();
+        assert!((fit.params[1] - 1.5).abs() < 1e-9, "{:?}", fit.params);
+        assert_eq!(fit.residuals.len(), 40);
+        let s = residual_series(&lc, &fit).unwrap();
+        assert_eq!(s.len(), 34);
+        let cov = s.covariates();
+        assert!(cov.elevation_rad.is_some() && cov.illumination.is_some());
+        assert!(s.y().iter().all(|r| r.abs() < 1e-9));
+    }
+}
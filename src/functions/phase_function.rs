@@ -0,0 +1,239 @@
+//! Solar phase functions \(\Phi(\varphi)\), normalized to \(\Phi(0) = 1\).
+//!
+//! Used by [`normalization`](crate::functions::normalization) to move a
+//! magnitude between phase angles and by the `PhaseFunction` detrend column.
+//! Phase angles are in radians throughout.
+
+use std::f64::consts::{PI, TAU};
+
+/// Phase-function model. The default is the historical `(1 + cos φ)/2`
+/// approximation so existing callers are unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhaseFunction {
+    /// `(1 + cos φ)/2`: the cos²(φ/2) approximation to a diffuse sphere.
+    #[default]
+    LambertApprox,
+    /// Exact diffuse sphere: `((π − φ) cos φ + sin φ)/π`.
+    LambertSphere,
+    /// Mirror sphere: reflected flux does not depend on phase.
+    SpecularSphere,
+    /// Diffuse plate facing the Sun (or the observer): `max(cos φ, 0)`.
+    FlatPlate,
+    /// IAU H-G (Bowell et al. 1989) with slope parameter `g`.
+    Hg { g: f64 },
+    /// IAU H-G1-G2 (Muinonen et al. 2010).
+    Hg1g2 { g1: f64, g2: f64 },
+}
+
+impl PhaseFunction {
+    /// Reflected flux relative to zero phase. Non-negative; zero where the
+    /// model has no lit, visible area.
+    pub fn phase_integral(&self, phase_rad: f64) -> f64 {
+        let a = fold_phase(phase_rad);
+        let v = match *self {
+            // Unfolded so the default stays bit-identical to the historical formula.
+            PhaseFunction::LambertApprox => (1.0 + phase_rad.cos()) / 2.0,
+            PhaseFunction::LambertSphere => ((PI - a) * a.cos() + a.sin()) / PI,
+            PhaseFunction::SpecularSphere => 1.0,
+            PhaseFunction::FlatPlate => a.cos(),
+            PhaseFunction::Hg { g } => {
+                let [phi1, phi2] = hg_basis(a);
+                (1.0 - g) * phi1 + g * phi2
+            }
+            PhaseFunction::Hg1g2 { g1, g2 } => {
+                let [phi1, phi2, phi3] = hg1g2_basis(a);
+                g1 * phi1 + g2 * phi2 + (1.0 - g1 - g2) * phi3
+            }
+        };
+        v.max(0.0)
+    }
+
+    /// Magnitude term `−2.5 log10 Φ(φ)`; `+∞` where `Φ = 0`.
+    pub fn magnitude(&self, phase_rad: f64) -> f64 {
+        -2.5 * self.phase_integral(phase_rad).log10()
+    }
+}
+
+/// Fold an angle into `[0, π]`; callers may pass an unwrapped angle.
+pub fn fold_phase(phase_rad: f64) -> f64 {
+    let a = phase_rad.rem_euclid(TAU);
+    if a > PI { TAU - a } else { a }
+}
+
+/// H-G basis `[Φ1, Φ2]` at a folded phase angle.
+pub fn hg_basis(a: f64) -> [f64; 2] {
+    let tan_half = (a / 2.0).tan();
+    [
+        (-3.33 * tan_half.powf(0.63)).exp(),
+        (-1.87 * tan_half.powf(1.22)).exp(),
+    ]
+}
+
+/// H-G1-G2 basis `[Φ1, Φ2, Φ3]` at a folded phase angle.
+pub fn hg1g2_basis(a: f64) -> [f64; 3] {
+    [hg1g2_phi1(a), hg1g2_phi2(a), hg1g2_phi3(a)]
+}
+
+// H-G1-G2 basis functions: linear below 7.5°, clamped cubic splines above
+// (Penttilä et al. 2016 node values).
+const DEG: f64 = PI / 180.0;
+
+fn hg1g2_phi1(a: f64) -> f64 {
+    if a < 7.5 * DEG {
+        return 1.0 - 6.0 * a / PI;
+    }
+    spline(
+        &[7.5, 30.0, 60.0, 90.0, 120.0, 150.0],
+        &[
+            7.5e-1,
+            3.3486016e-1,
+            1.3410560e-1,
+            5.1104756e-2,
+            2.1465687e-2,
+            3.6396989e-3,
+        ],
+        (-1.9098593, -9.1328612e-2),
+        a,
+    )
+}
+
+fn hg1g2_phi2(a: f64) -> f64 {
+    if a < 7.5 * DEG {
+        return 1.0 - 9.0 * a / (5.0 * PI);
+    }
+    spline(
+        &[7.5, 30.0, 60.0, 90.0, 120.0, 150.0],
+        &[
+            9.25e-1,
+            6.2884169e-1,
+            3.1755495e-1,
+            1.2716367e-1,
+            2.2373903e-2,
+            1.6505689e-4,
+        ],
+        (-5.7295780e-1, -8.6573138e-8),
+        a,
+    )
+}
+
+fn hg1g2_phi3(a: f64) -> f64 {
+    if a >= 30.0 * DEG {
+        return 0.0;
+    }
+    spline(
+        &[0.0, 0.3, 1.0, 2.0, 4.0, 8.0, 12.0, 20.0, 30.0],
+        &[
+            1.0,
+            8.3381185e-1,
+            5.7735424e-1,
+            4.2144772e-1,
+            2.3174230e-1,
+            1.0348178e-1,
+            6.1733473e-2,
+            1.6107006e-2,
+            0.0,
+        ],
+        (-1.0630097e-1, 0.0),
+        a,
+    )
+}
+
+/// Clamped cubic spline through `(nodes_deg, values)` with end slopes in
+/// per-radian units, evaluated at `x` radians. Clamps outside the nodes.
+fn spline(nodes_deg: &[f64], values: &[f64], slopes: (f64, f64), x: f64) -> f64 {
+    let n = nodes_deg.len();
+    let xs: Vec<f64> = nodes_deg.iter().map(|d| d * DEG).collect();
+    let x = x.clamp(xs[0], xs[n - 1]);
+    let h: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+
+    // Tridiagonal system for the second derivatives m_i (Thomas algorithm).
+    let mut diag = vec![0.0; n];
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    diag[0] = 2.0 * h[0];
+    upper[0] = h[0];
+    rhs[0] = 6.0 * ((values[1] - values[0]) / h[0] - slopes.0);
+    for i in 1..n - 1 {
+        diag[i] = 2.0 * (h[i - 1] + h[i]);
+        upper[i] = h[i];
+        rhs[i] =
+            6.0 * ((values[i + 1] - values[i]) / h[i] - (values[i] - values[i - 1]) / h[i - 1]);
+    }
+    diag[n - 1] = 2.0 * h[n - 2];
+    rhs[n - 1] = 6.0 * (slopes.1 - (values[n - 1] - values[n - 2]) / h[n - 2]);
+    for i in 1..n {
+        let lower = h[i - 1];
+        let f = lower / diag[i - 1];
+        diag[i] -= f * upper[i - 1];
+        rhs[i] -= f * rhs[i - 1];
+    }
+    let mut m = vec![0.0; n];
+    m[n - 1] = rhs[n - 1] / diag[n - 1];
+    for i in (0..n - 1).rev() {
+        m[i] = (rhs[i] - upper[i] * m[i + 1]) / diag[i];
+    }
+
+    let k = (0..n - 1).find(|&i| x <= xs[i + 1]).unwrap_or(n - 2);
+    let (a, b) = (xs[k + 1] - x, x - xs[k]);
+    let hk = h[k];
+    m[k] * a.powi(3) / (6.0 * hk)
+        + m[k + 1] * b.powi(3) / (6.0 * hk)
+        + (values[k] / hk - m[k] * hk / 6.0) * a
+        + (values[k + 1] / hk - m[k + 1] * hk / 6.0) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [PhaseFunction; 6] = [
+        PhaseFunction::LambertApprox,
+        PhaseFunction::LambertSphere,
+        PhaseFunction::SpecularSphere,
+        PhaseFunction::FlatPlate,
+        PhaseFunction::Hg { g: 0.15 },
+        PhaseFunction::Hg1g2 { g1: 0.3, g2: 0.5 },
+    ];
+
+    #[test]
+    fn every_model_is_unity_at_opposition() {
+        for m in ALL {
+            assert!((m.phase_integral(0.0) - 1.0).abs() < 1e-9, "{m:?}");
+            assert!(m.magnitude(0.0).abs() < 1e-8, "{m:?}");
+        }
+    }
+
+    #[test]
+    fn lambert_sphere_matches_closed_form() {
+        let m = PhaseFunction::LambertSphere;
+        assert!((m.phase_integral(PI / 2.0) - 1.0 / PI).abs() < 1e-12);
+        assert!(m.phase_integral(PI).abs() < 1e-12);
+        // The approximation is too bright at quadrature: 0.5 vs 1/π.
+        assert!(PhaseFunction::LambertApprox.phase_integral(PI / 2.0) > 0.5 - 1e-12);
+    }
+
+    #[test]
+    fn splines_hit_their_nodes() {
+        assert!((hg1g2_phi1(30.0 * DEG) - 3.3486016e-1).abs() < 1e-9);
+        assert!((hg1g2_phi2(90.0 * DEG) - 1.2716367e-1).abs() < 1e-9);
+        assert!((hg1g2_phi3(2.0 * DEG) - 4.2144772e-1).abs() < 1e-9);
+        // Linear segment meets the spline at 7.5°.
+        let below = hg1g2_phi1(7.5 * DEG - 1e-9);
+        assert!((below - hg1g2_phi1(7.5 * DEG)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hg_darkens_monotonically() {
+        let m = PhaseFunction::Hg { g: 0.15 };
+        let mut prev = f64::INFINITY;
+        for k in 0..=30 {
+            let v = m.phase_integral(k as f64 * 5.0 * DEG);
+            assert!(v <= prev);
+            prev = v;
+        }
+        // About one magnitude of phase darkening at 20° for G = 0.15.
+        let dm = m.magnitude(20.0 * DEG);
+        assert!((dm - 1.0).abs() < 0.05, "{dm}");
+    }
+}
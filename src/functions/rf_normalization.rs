@@ -2,12 +2,14 @@
 
 use crate::entities::rf::{RfNormConfig, RfPowerObservation, RfYDomain};
 use crate::entities::series::{
-    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, YUnit,
+    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
 };
+use crate::functions::time_scale::TimeScale;
 
 /// Range-correct (when range is present) and pack into a [`Series`].
 ///
 /// Default stays in dBm: `y = P_dBm + 10 n log10(r/r0)` with `n = 2`.
+#[allow(clippy::collapsible_if)]
 pub fn standardize_rf(
     samples: &[RfPowerObservation],
     cfg: &RfNormConfig,
@@ -92,6 +94,8 @@ pub fn standardize_rf(
             modality: Modality::RfPower,
             y_unit,
             label: None,
+            time_scale: TimeScale::Utc,
+            time_frame: TimeFrame::Observer,
         },
     )
 }
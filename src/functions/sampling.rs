@@ -280,6 +280,7 @@ fn interpass_gaps(passes: &[Pass]) -> Vec<f64> {
         .collect()
 }
 
+#[allow(clippy::collapsible_if, clippy::too_many_arguments)]
 fn tag_window_peaks(
     periods: &[f64],
     scores: &[f64],
@@ -472,7 +473,8 @@ fn percentile(xs: &[f64], q: f64) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::series::{Covariates, Modality, SeriesMeta, SigmaSpec, YUnit};
+    use crate::entities::series::{Covariates, Modality, SeriesMeta, SigmaSpec, TimeFrame, YUnit};
+    use crate::functions::time_scale::TimeScale;
 
     fn series_from_t_y(t: Vec<f64>, y: Vec<f64>) -> Series {
         Series::try_new(
@@ -484,6 +486,8 @@ mod tests {
                 modality: Modality::Generic,
                 y_unit: YUnit::Dimensionless,
                 label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
             },
         )
         .unwrap()
@@ -0,0 +1,196 @@
+//! Earth-shadow models and eclipse masking.
+//!
+//! Illumination is the fraction of the solar disc visible from the target:
+//! 1 in sunlight, 0 in umbra, in between in penumbra. Positions are
+//! geocentric inertial km, as in [`geometry`](crate::functions::geometry).
+//!
+//! Points below a minimum illumination are dropped through
+//! [`Covariates::keep`]; the penumbral remainder is carried as
+//! [`Covariates::illumination`] so Auto detrend can fit the fade and the
+//! search can veto a period that only the shadow crossings support.
+
+use crate::entities::series::Covariates;
+use crate::functions::geometry::WGS84_A_KM;
+use crate::functions::sun::is_sunlit;
+use std::f64::consts::PI;
+
+/// IAU 2015 nominal solar radius (km).
+pub const SUN_RADIUS_KM: f64 = 695_700.0;
+
+/// Default cut for [`eclipse_keep`]: deeper than half the disc is dropped.
+pub const DEFAULT_MIN_ILLUMINATION: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShadowModel {
+    /// Spherical Earth, Sun at infinity: 0 or 1, no penumbra.
+    Cylindrical,
+    /// Finite Sun and Earth discs: umbra, penumbra and annular phases.
+    #[default]
+    Conical,
+}
+
+impl ShadowModel {
+    /// Fraction of the solar disc visible from `target_eci_km`, in `[0, 1]`.
+    pub fn illumination(self, target_eci_km: [f64; 3], sun_eci_km: [f64; 3]) -> f64 {
+        match self {
+            ShadowModel::Cylindrical => {
+                if is_sunlit(target_eci_km, sun_eci_km) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ShadowModel::Conical => conical_illumination(target_eci_km, sun_eci_km),
+        }
+    }
+}
+
+/// Illumination for each target / Sun pair. Slices must be the same length.
+pub fn illumination_fractions(
+    model: ShadowModel,
+    target_eci_km: &[[f64; 3]],
+    sun_eci_km: &[[f64; 3]],
+) -> Vec<f64> {
+    target_eci_km
+        .iter()
+        .zip(sun_eci_km.iter())
+        .map(|(&x, &s)| model.illumination(x, s))
+        .collect()
+}
+
+/// `true` where the point is lit well enough to keep. Non-finite
+/// illumination is kept: unknown is not the same as eclipsed.
+pub fn eclipse_keep(illumination: &[f64], min_illumination: f64) -> Vec<bool> {
+    illumination
+        .iter()
+        .map(|&f| !f.is_finite() || f >= min_illumination)
+        .collect()
+}
+
+/// Fill `illumination` and AND [`eclipse_keep`] into `keep`.
+pub fn annotate_shadow(
+    covariates: &mut Covariates,
+    model: ShadowModel,
+    target_eci_km: &[[f64; 3]],
+    sun_eci_km: &[[f64; 3]],
+    min_illumination: f64,
+) {
+    let illum = illumination_fractions(model, target_eci_km, sun_eci_km);
+    let lit = eclipse_keep(&illum, min_illumination);
+    covariates.keep = Some(match covariates.keep.take() {
+        Some(k) if k.len() == lit.len() => {
+            k.iter().zip(lit.iter()).map(|(a, b)| *a && *b).collect()
+        }
+        _ => lit,
+    });
+    covariates.illumination = Some(illum);
+}
+
+/// Small-angle disc overlap (Montenbruck & Gill §3.4.2).
+fn conical_illumination(target: [f64; 3], sun: [f64; 3]) -> f64 {
+    let to_sun = [sun[0] - target[0], sun[1] - target[1], sun[2] - target[2]];
+    let d_sun = norm(to_sun);
+    let d_earth = norm(target);
+    if d_sun <= SUN_RADIUS_KM || d_earth <= WGS84_A_KM {
+        return 1.0;
+    }
+    let a = (SUN_RADIUS_KM / d_sun).asin();
+    let b = (WGS84_A_KM / d_earth).asin();
+    let cos_c = -(to_sun[0] * target[0] + to_sun[1] * target[1] + to_sun[2] * target[2])
+        / (d_sun * d_earth);
+    let c = cos_c.clamp(-1.0, 1.0).acos();
+
+    if c >= a + b {
+        return 1.0;
+    }
+    if c <= b - a {
+        return 0.0;
+    }
+    if c <= a - b {
+        return 1.0 - (b * b) / (a * a);
+    }
+    let x = (c * c + a * a - b * b) / (2.0 * c);
+    let y = (a * a - x * x).max(0.0).sqrt();
+    let overlap = a * a * (x / a).clamp(-1.0, 1.0).acos()
+        + b * b * ((c - x) / b).clamp(-1.0, 1.0).acos()
+        - c * y;
+    (1.0 - overlap / (PI * a * a)).clamp(0.0, 1.0)
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::sun::AU_KM;
+
+    const GEO_KM: f64 = 42_164.0;
+
+    fn geo_at(offset_km: f64) -> [f64; 3] {
+        // Behind the Earth, displaced `offset_km` off the shadow axis.
+        [
+            -(GEO_KM * GEO_KM - offset_km * offset_km).sqrt(),
+            0.0,
+            offset_km,
+        ]
+    }
+
+    #[test]
+    fn cylindrical_is_binary() {
+        let sun = [AU_KM, 0.0, 0.0];
+        assert_eq!(ShadowModel::Cylindrical.illumination(geo_at(0.0), sun), 0.0);
+        assert_eq!(
+            ShadowModel::Cylindrical.illumination(geo_at(7000.0), sun),
+            1.0
+        );
+        assert_eq!(
+            ShadowModel::Cylindrical.illumination([GEO_KM, 0.0, 0.0], sun),
+            1.0
+        );
+    }
+
+    #[test]
+    fn conical_has_monotone_penumbra_at_geo() {
+        let sun = [AU_KM, 0.0, 0.0];
+        let m = ShadowModel::Conical;
+        assert_eq!(m.illumination(geo_at(0.0), sun), 0.0);
+        assert_eq!(m.illumination(geo_at(10_000.0), sun), 1.0);
+        // At GEO the umbra is ~6180 km and the penumbra ~6580 km in radius.
+        let mut prev = 0.0;
+        let mut n_partial = 0;
+        for k in 0..=80 {
+            let f = m.illumination(geo_at(5_800.0 + 15.0 * k as f64), sun);
+            assert!((0.0..=1.0).contains(&f));
+            assert!(f >= prev - 1e-12, "non-monotone at step {k}: {f} < {prev}");
+            if f > 0.0 && f < 1.0 {
+                n_partial += 1;
+            }
+            prev = f;
+        }
+        assert!(n_partial > 20, "penumbra too thin: {n_partial}");
+        assert_eq!(prev, 1.0);
+    }
+
+    #[test]
+    fn annotate_shadow_ands_into_keep() {
+        let sun = [AU_KM, 0.0, 0.0];
+        let targets = [geo_at(0.0), geo_at(10_000.0), geo_at(10_000.0)];
+        let suns = [sun; 3];
+        let mut cov = Covariates {
+            keep: Some(vec![true, true, false]),
+            ..Covariates::default()
+        };
+        annotate_shadow(
+            &mut cov,
+            ShadowModel::Conical,
+            &targets,
+            &suns,
+            DEFAULT_MIN_ILLUMINATION,
+        );
+        assert_eq!(cov.keep, Some(vec![false, true, false]));
+        assert_eq!(cov.illumination, Some(vec![0.0, 1.0, 1.0]));
+    }
+}
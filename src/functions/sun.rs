@@ -0,0 +1,134 @@
+//! Low-precision analytic Sun (Astronomical Almanac, section C).
+//!
+//! Good to ~0.01° in direction for 1950–2050, with no data files. Positions
+//! are geocentric, in km, in the mean equator and equinox of date. That frame
+//! differs from TEME by less than the formula error, so the result can be
+//! passed straight to [`geometry::observe`](crate::functions::geometry::observe).
+
+use crate::functions::geometry::{
+    Geometry, Site, WGS84_A_KM, gmst_rad, observe, rotate_z, unix_to_jd,
+};
+use chrono::{DateTime, Utc};
+
+/// IAU astronomical unit (km).
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// Geocentric Sun, equatorial (≈ TEME), km.
+pub fn sun_eci_km(when: DateTime<Utc>) -> [f64; 3] {
+    let n = unix_to_jd(when.timestamp_millis() as f64 / 1000.0) - 2_451_545.0;
+    let l = (280.460 + 0.985_647_4 * n).rem_euclid(360.0);
+    let g = (357.528 + 0.985_600_3 * n).rem_euclid(360.0).to_radians();
+    let lambda = (l + 1.915 * g.sin() + 0.020 * (2.0 * g).sin()).to_radians();
+    let eps = (23.439 - 0.000_000_4 * n).to_radians();
+    let r_au = 1.000_14 - 0.016_71 * g.cos() - 0.000_14 * (2.0 * g).cos();
+    let r = r_au * AU_KM;
+    [
+        r * lambda.cos(),
+        r * eps.cos() * lambda.sin(),
+        r * eps.sin() * lambda.sin(),
+    ]
+}
+
+/// Geocentric Sun, Earth-fixed, km.
+pub fn sun_ecef_km(when: DateTime<Utc>) -> [f64; 3] {
+    rotate_z(sun_eci_km(when), -gmst_rad(when))
+}
+
+/// `true` unless the target sits inside the cylinder of Earth's shadow
+/// (spherical Earth, Sun at infinity).
+pub fn is_sunlit(target_eci_km: [f64; 3], sun_eci_km: [f64; 3]) -> bool {
+    let s_norm = (sun_eci_km[0].powi(2) + sun_eci_km[1].powi(2) + sun_eci_km[2].powi(2)).sqrt();
+    let u = [
+        sun_eci_km[0] / s_norm,
+        sun_eci_km[1] / s_norm,
+        sun_eci_km[2] / s_norm,
+    ];
+    let along = target_eci_km[0] * u[0] + target_eci_km[1] * u[1] + target_eci_km[2] * u[2];
+    if along >= 0.0 {
+        return true;
+    }
+    let perp2 = (target_eci_km[0].powi(2) + target_eci_km[1].powi(2) + target_eci_km[2].powi(2))
+        - along * along;
+    perp2 > WGS84_A_KM * WGS84_A_KM
+}
+
+/// Geometry plus illumination from only a site and a target position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarGeometry {
+    pub geometry: Geometry,
+    pub sunlit: bool,
+}
+
+/// [`observe`] with the analytic Sun filled in.
+pub fn observe_with_sun(
+    site: &Site,
+    when: DateTime<Utc>,
+    target_eci_km: [f64; 3],
+) -> SolarGeometry {
+    let sun = sun_eci_km(when);
+    SolarGeometry {
+        geometry: observe(site, when, target_eci_km, sun),
+        sunlit: is_sunlit(target_eci_km, sun),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn ra_dec_deg(v: [f64; 3]) -> (f64, f64, f64) {
+        let r = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        (
+            v[1].atan2(v[0]).to_degrees().rem_euclid(360.0),
+            (v[2] / r).asin().to_degrees(),
+            r / AU_KM,
+        )
+    }
+
+    #[test]
+    fn matches_meeus_example_25a() {
+        // 1992-10-13 0h TD: RA 198.378°, Dec −7.785°, R 0.99766 AU.
+        let (ra, dec, r) = ra_dec_deg(sun_eci_km(at("1992-10-13T00:00:00Z")));
+        assert!((ra - 198.378).abs() < 0.02, "{ra}");
+        assert!((dec + 7.785).abs() < 0.02, "{dec}");
+        assert!((r - 0.99766).abs() < 1e-4, "{r}");
+    }
+
+    #[test]
+    fn equinox_has_zero_declination() {
+        let (_, dec, _) = ra_dec_deg(sun_eci_km(at("2025-03-20T09:01:00Z")));
+        assert!(dec.abs() < 0.02, "{dec}");
+    }
+
+    #[test]
+    fn shadow_cylinder() {
+        let sun = [AU_KM, 0.0, 0.0];
+        assert!(is_sunlit([7000.0, 0.0, 0.0], sun));
+        assert!(!is_sunlit([-7000.0, 0.0, 0.0], sun));
+        assert!(is_sunlit([-7000.0, 0.0, 7000.0], sun));
+    }
+
+    #[test]
+    fn midnight_geo_is_near_opposition() {
+        // Local midnight at the equinox on the Greenwich meridian: a GEO
+        // target overhead is almost opposite the Sun, phase angle ≈ 0.
+        let when = at("2025-03-20T00:00:00Z");
+        let site = Site::new(0.0, 0.0, 0.0);
+        let up = site.eci_km(when);
+        let r = (up[0] * up[0] + up[1] * up[1] + up[2] * up[2]).sqrt();
+        let geo = [
+            up[0] * 42_164.0 / r,
+            up[1] * 42_164.0 / r,
+            up[2] * 42_164.0 / r,
+        ];
+        let sg = observe_with_sun(&site, when, geo);
+        assert!(sg.geometry.phase_rad.to_degrees() < 10.0);
+        // Equinox GEO eclipse season: right behind the Earth.
+        assert!(!sg.sunlit);
+    }
+}
@@ -0,0 +1,225 @@
+//! Synodic → sidereal rotation period.
+//!
+//! A tumbler's lightcurve is locked to the phase-angle bisector (PAB), the
+//! direction halfway between target→Sun and target→observer. While the PAB
+//! turns at rate ω about the spin axis, the photometric (synodic) period
+//! differs from the sidereal one: `1/P_sid = 1/P_syn ± ω/2π`, `+` for
+//! prograde spin (Pravec et al. 2005). The pole is not known from photometry
+//! alone, so ω is taken as the full PAB rate, i.e. the spin axis along the
+//! pole of the PAB's own motion, which gives the largest difference. Both
+//! spin senses are reported.
+//!
+//! With Sun and target vectors the PAB is computed directly; pass them to
+//! [`assess_periodicity_with_pab`], keyed by observation time, to use them
+//! for the reported correction.
+//! With only the solar phase angle α it is taken to move in the scattering
+//! plane at `|dα/dt| / 2`, a lower bound on the true rate. A range covariate
+//! does not help here: it fixes neither the line of sight's direction nor
+//! its turn rate, so the phase-only path does not read it.
+//!
+//! [`assess_periodicity_with_pab`]: crate::functions::periodicity::assess_periodicity_with_pab
+//!
+//! The rate is pooled over steps inside one pass: across a night gap a GEO
+//! PAB turns by about π and the step cannot be unwrapped. Its spread over
+//! the campaign is the rate uncertainty, since the PAB does not turn
+//! uniformly and the fitted period averages over it.
+
+use crate::entities::assessment::{PabSource, Pass, SiderealCorrection};
+use crate::entities::series::Series;
+use crate::functions::geometry::{Site, angle_between, phase_angle_bisector};
+use chrono::{DateTime, Utc};
+use std::f64::consts::TAU;
+
+/// Correction from the series' `solar_phase_rad` covariate. `None` without
+/// the covariate or when no pass holds two points.
+pub fn correction_from_phase(
+    series: &Series,
+    passes: &[Pass],
+    period_s: f64,
+    period_unc_s: Option<f64>,
+) -> Option<SiderealCorrection> {
+    let alpha = series.covariates().solar_phase_rad.as_deref()?;
+    let (rate, rate_unc) = pooled_rate(series.t_s(), passes, |i| {
+        0.5 * (alpha[i] - alpha[i - 1]).abs()
+    })?;
+    correct(
+        period_s,
+        period_unc_s,
+        rate,
+        rate_unc,
+        PabSource::PhaseAngle,
+    )
+}
+
+/// Correction from unit bisectors `pab` at `t_s` (see [`pab_track`]).
+/// `None` on a length mismatch or when no pass holds two points.
+pub fn correction_from_vectors(
+    t_s: &[f64],
+    pab: &[[f64; 3]],
+    passes: &[Pass],
+    period_s: f64,
+    period_unc_s: Option<f64>,
+) -> Option<SiderealCorrection> {
+    if pab.len() != t_s.len() {
+        return None;
+    }
+    let (rate, rate_unc) = pooled_rate(t_s, passes, |i| angle_between(pab[i - 1], pab[i]))?;
+    correct(period_s, period_unc_s, rate, rate_unc, PabSource::Vectors)
+}
+
+/// Unit PAB per point, from the same inputs as
+/// [`geometry_covariates`](crate::functions::geometry::geometry_covariates).
+pub fn pab_track(
+    site: &Site,
+    times: &[DateTime<Utc>],
+    target_eci_km: &[[f64; 3]],
+    sun_eci_km: &[[f64; 3]],
+) -> Vec<[f64; 3]> {
+    times
+        .iter()
+        .zip(target_eci_km.iter())
+        .zip(sun_eci_km.iter())
+        .map(|((&t, &x), &s)| phase_angle_bisector(site, t, x, s))
+        .collect()
+}
+
+/// Duration-weighted mean and spread of the PAB rate over steps `i − 1 → i`
+/// that stay inside one pass; `step(i)` is the angle turned (rad).
+fn pooled_rate(t: &[f64], passes: &[Pass], step: impl Fn(usize) -> f64) -> Option<(f64, f64)> {
+    let same_pass = |a: f64, b: f64| passes.iter().any(|p| a >= p.t_start_s && b <= p.t_end_s);
+    let steps: Vec<(f64, f64)> = (1..t.len())
+        .filter(|&i| t[i] > t[i - 1] && same_pass(t[i - 1], t[i]))
+        .map(|i| (t[i] - t[i - 1], step(i)))
+        .filter(|(_, d)| d.is_finite())
+        .collect();
+    let dur: f64 = steps.iter().map(|s| s.0).sum();
+    if dur <= 0.0 {
+        return None;
+    }
+    let rate = steps.iter().map(|s| s.1).sum::<f64>() / dur;
+    let var = steps
+        .iter()
+        .map(|(dt, d)| dt * (d / dt - rate).powi(2))
+        .sum::<f64>()
+        / dur;
+    Some((rate, var.sqrt()))
+}
+
+fn correct(
+    period_s: f64,
+    period_unc_s: Option<f64>,
+    rate: f64,
+    rate_unc: f64,
+    source: PabSource,
+) -> Option<SiderealCorrection> {
+    if !period_s.is_finite() || period_s <= 0.0 {
+        return None;
+    }
+    // PAB turns per synodic rotation.
+    let f = rate * period_s / TAU;
+    let prograde = period_s / (1.0 + f);
+    let retrograde = if f < 1.0 {
+        period_s / (1.0 - f)
+    } else {
+        f64::INFINITY
+    };
+    let unc_p = period_unc_s.unwrap_or(0.0);
+    let g = 1.0 / (1.0 + f).powi(2);
+    // ∂P_sid/∂ω; ∂P_sid/∂P is g.
+    let d_rate = period_s * period_s / TAU * g;
+    Some(SiderealCorrection {
+        source,
+        pab_rate_rad_s: rate,
+        delta_s: period_s - prograde,
+        delta_unc_s: ((1.0 - g) * unc_p).hypot(d_rate * rate_unc),
+        period_s: prograde,
+        period_unc_s: (g * unc_p).hypot(d_rate * rate_unc),
+        retrograde_period_s: retrograde,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::series::{Covariates, Modality, SeriesMeta, SigmaSpec, TimeFrame, YUnit};
+    use crate::functions::time_scale::TimeScale;
+
+    fn pass(t0: f64, t1: f64) -> Pass {
+        Pass {
+            t_start_s: t0,
+            t_end_s: t1,
+            n: 0,
+            median_dt_s: 0.0,
+        }
+    }
+
+    /// Two 6 h nights a day apart, 60 s cadence.
+    fn nights() -> (Vec<f64>, Vec<Pass>) {
+        let t: Vec<f64> = (0..2)
+            .flat_map(|n| (0..360).map(move |k| n as f64 * 86_400.0 + k as f64 * 60.0))
+            .collect();
+        let passes = vec![
+            pass(0.0, 359.0 * 60.0),
+            pass(86_400.0, 86_400.0 + 359.0 * 60.0),
+        ];
+        (t, passes)
+    }
+
+    #[test]
+    fn uniform_bisector_gives_pravec_correction() {
+        // GEO-like: PAB turns at half the Earth rate.
+        let omega = 0.5 * TAU / 86_164.0;
+        let (t, passes) = nights();
+        let pab: Vec<[f64; 3]> = t
+            .iter()
+            .map(|&t| [(omega * t).cos(), (omega * t).sin(), 0.0])
+            .collect();
+        let p = 600.0;
+        let c = correction_from_vectors(&t, &pab, &passes, p, Some(0.01)).unwrap();
+        assert_eq!(c.source, PabSource::Vectors);
+        assert!((c.pab_rate_rad_s / omega - 1.0).abs() < 1e-4);
+        let f = omega * p / TAU;
+        assert!((c.period_s - p / (1.0 + f)).abs() < 1e-9);
+        assert!((c.retrograde_period_s - p / (1.0 - f)).abs() < 1e-9);
+        assert!(c.delta_s > 0.0 && (c.delta_s - p * f / (1.0 + f)).abs() < 1e-9);
+        // Uniform rate: only the period error propagates.
+        assert!(c.period_unc_s > 0.0099 && c.period_unc_s < 0.01);
+        assert!(correction_from_vectors(&t, &pab[1..], &passes, p, None).is_none());
+    }
+
+    #[test]
+    fn phase_angle_track_ignores_gaps_and_bounds_rate() {
+        let omega = 0.5 * TAU / 86_164.0;
+        let (t, passes) = nights();
+        // α swings through a minimum each night and jumps across the gap.
+        let alpha: Vec<f64> = t
+            .iter()
+            .map(|&t| {
+                let night = (t / 86_400.0).floor();
+                (2.0 * omega * (t - night * 86_400.0 - 3.0 * 3600.0)).abs() + 0.3 * night
+            })
+            .collect();
+        let n = t.len();
+        let s = Series::try_new(
+            t,
+            vec![0.0; n],
+            SigmaSpec::Unknown,
+            Covariates {
+                solar_phase_rad: Some(alpha),
+                ..Covariates::default()
+            },
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: None,
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap();
+        let c = correction_from_phase(&s, &passes, 600.0, None).unwrap();
+        assert_eq!(c.source, PabSource::PhaseAngle);
+        assert!((c.pab_rate_rad_s - omega).abs() < 1e-3 * omega);
+        assert!(correction_from_phase(&s, &[], 600.0, None).is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Astronomical time scales and leap seconds.
+//!
+//! Times are carried as "scale seconds": seconds since 1970-01-01T00:00:00
+//! counted in the given scale, so an MJD in TT maps to
+//! `(mjd − 40587)·86400` TT seconds. [`to_utc`] brings such a value onto
+//! the unix (UTC) axis, [`from_utc`] goes the other way, and [`convert`]
+//! moves between any two scales. UTC seconds repeat across a leap second;
+//! TAI, TT and TDB seconds are continuous, so a campaign spanning one
+//! should be searched in one of those
+//! (see [`Series::to_time_scale`](crate::entities::series::Series::to_time_scale)).
+
+/// TT − TAI (s), exact by definition.
+pub const TT_MINUS_TAI_S: f64 = 32.184;
+/// MJD of 1970-01-01T00:00:00.
+pub const MJD_UNIX_EPOCH: f64 = 40_587.0;
+/// JD − MJD (days).
+pub const JD_MINUS_MJD: f64 = 2_400_000.5;
+/// Speed of light in vacuum (m/s), for light-travel-time corrections.
+pub const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
+/// `(UTC unix seconds, TAI − UTC)` from each leap second onwards (IERS
+/// Bulletin C). Before 1972 the offset is taken as 10 s.
+const LEAP_SECONDS: [(f64, f64); 28] = [
+    (63_072_000.0, 10.0),
+    (78_796_800.0, 11.0),
+    (94_694_400.0, 12.0),
+    (126_230_400.0, 13.0),
+    (157_766_400.0, 14.0),
+    (189_302_400.0, 15.0),
+    (220_924_800.0, 16.0),
+    (252_460_800.0, 17.0),
+    (283_996_800.0, 18.0),
+    (315_532_800.0, 19.0),
+    (362_793_600.0, 20.0),
+    (394_329_600.0, 21.0),
+    (425_865_600.0, 22.0),
+    (489_024_000.0, 23.0),
+    (567_993_600.0, 24.0),
+    (631_152_000.0, 25.0),
+    (662_688_000.0, 26.0),
+    (709_948_800.0, 27.0),
+    (741_484_800.0, 28.0),
+    (773_020_800.0, 29.0),
+    (820_454_400.0, 30.0),
+    (867_715_200.0, 31.0),
+    (915_148_800.0, 32.0),
+    (1_136_073_600.0, 33.0),
+    (1_230_768_000.0, 34.0),
+    (1_341_100_800.0, 35.0),
+    (1_435_708_800.0, 36.0),
+    (1_483_228_800.0, 37.0),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeScale {
+    #[default]
+    Utc,
+    Tai,
+    /// Terrestrial Time, `TAI + 32.184 s`.
+    Tt,
+    /// Barycentric Dynamical Time; differs from TT by < 2 ms.
+    Tdb,
+}
+
+impl TimeScale {
+    /// Parse a FITS `TIMESYS` value. `TDT` and `ET` are read as TT.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "UTC" => Some(TimeScale::Utc),
+            "TAI" => Some(TimeScale::Tai),
+            "TT" | "TDT" | "ET" => Some(TimeScale::Tt),
+            "TDB" => Some(TimeScale::Tdb),
+            _ => None,
+        }
+    }
+
+    /// FITS `TIMESYS` spelling.
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeScale::Utc => "UTC",
+            TimeScale::Tai => "TAI",
+            TimeScale::Tt => "TT",
+            TimeScale::Tdb => "TDB",
+        }
+    }
+}
+
+impl std::fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// TAI − UTC (s) at UTC unix time `utc_s`.
+pub fn tai_minus_utc(utc_s: f64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(t, _)| utc_s >= *t)
+        .map(|(_, dt)| *dt)
+        .unwrap_or(10.0)
+}
+
+/// TDB − TT (s), leading periodic term (Fairhead & Bretagnon); good to ~30 µs.
+pub fn tdb_minus_tt(tt_s: f64) -> f64 {
+    let days = tt_s / 86_400.0 - 10_957.5;
+    let g = (357.53 + 0.985_600_28 * days).to_radians();
+    0.001_657 * g.sin() + 0.000_014 * (2.0 * g).sin()
+}
+
+/// Scale seconds in `scale` → UTC unix seconds.
+pub fn to_utc(t_s: f64, scale: TimeScale) -> f64 {
+    let tai = match scale {
+        TimeScale::Utc => return t_s,
+        TimeScale::Tai => t_s,
+        TimeScale::Tt => t_s - TT_MINUS_TAI_S,
+        TimeScale::Tdb => t_s - tdb_minus_tt(t_s) - TT_MINUS_TAI_S,
+    };
+    // Step boundaries on the TAI axis sit at UTC step + new offset.
+    let dt = LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(t, dt)| tai >= t + dt)
+        .map(|(_, dt)| *dt)
+        .unwrap_or(10.0);
+    tai - dt
+}
+
+/// UTC unix seconds → scale seconds in `scale`; inverse of [`to_utc`].
+pub fn from_utc(utc_s: f64, scale: TimeScale) -> f64 {
+    if scale == TimeScale::Utc {
+        return utc_s;
+    }
+    let tt = utc_s + tai_minus_utc(utc_s) + TT_MINUS_TAI_S;
+    match scale {
+        TimeScale::Utc => unreachable!(),
+        TimeScale::Tai => tt - TT_MINUS_TAI_S,
+        TimeScale::Tt => tt,
+        TimeScale::Tdb => tt + tdb_minus_tt(tt),
+    }
+}
+
+/// Scale seconds in `from` → scale seconds in `to`.
+pub fn convert(t_s: f64, from: TimeScale, to: TimeScale) -> f64 {
+    if from == to {
+        return t_s;
+    }
+    from_utc(to_utc(t_s, from), to)
+}
+
+/// Modified Julian Date → seconds since 1970-01-01, in the same scale.
+pub fn mjd_to_seconds(mjd: f64) -> f64 {
+    (mjd - MJD_UNIX_EPOCH) * 86_400.0
+}
+
+/// Seconds since 1970-01-01 → Modified Julian Date, in the same scale.
+pub fn seconds_to_mjd(t_s: f64) -> f64 {
+    t_s / 86_400.0 + MJD_UNIX_EPOCH
+}
+
+/// Julian Date → seconds since 1970-01-01, in the same scale.
+pub fn jd_to_seconds(jd: f64) -> f64 {
+    mjd_to_seconds(jd - JD_MINUS_MJD)
+}
+
+/// Seconds since 1970-01-01 → Julian Date, in the same scale.
+pub fn seconds_to_jd(t_s: f64) -> f64 {
+    seconds_to_mjd(t_s) + JD_MINUS_MJD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leap_second_offsets_and_tt_round_trip() {
+        // 2016-12-31T23:59:59Z and 2017-01-01T00:00:00Z straddle the last step.
+        assert_eq!(tai_minus_utc(1_483_228_799.0), 36.0);
+        assert_eq!(tai_minus_utc(1_483_228_800.0), 37.0);
+        let utc = 1_735_689_600.0; // 2025-01-01
+        assert_eq!(to_utc(utc + 37.0 + TT_MINUS_TAI_S, TimeScale::Tt), utc);
+        assert_eq!(
+            to_utc(1_483_228_800.0 + 37.0, TimeScale::Tai),
+            1_483_228_800.0
+        );
+        assert_eq!(
+            to_utc(1_483_228_799.0 + 36.0, TimeScale::Tai),
+            1_483_228_799.0
+        );
+        let tdb = to_utc(utc + 69.184, TimeScale::Tdb);
+        assert!((tdb - utc).abs() < 2e-3 && tdb != utc, "{}", tdb - utc);
+        assert_eq!(TimeScale::from_name(" tdt "), Some(TimeScale::Tt));
+        assert_eq!(TimeScale::from_name("GPS"), None);
+    }
+
+    #[test]
+    fn from_utc_inverts_to_utc_and_is_continuous() {
+        for utc in [1_483_228_799.0, 1_483_228_800.0, 1_735_689_600.25] {
+            for scale in [TimeScale::Tai, TimeScale::Tt, TimeScale::Tdb] {
+                let back = to_utc(from_utc(utc, scale), scale);
+                assert!((back - utc).abs() < 1e-6, "{scale}: {}", back - utc);
+            }
+        }
+        // One UTC second across 2016-12-31T23:59:60 is two TAI seconds.
+        let a = from_utc(1_483_228_799.0, TimeScale::Tai);
+        let b = from_utc(1_483_228_800.0, TimeScale::Tai);
+        assert_eq!(b - a, 2.0);
+        let tt = convert(a, TimeScale::Tai, TimeScale::Tt);
+        assert!((tt - a - TT_MINUS_TAI_S).abs() < 1e-6);
+        assert_eq!(seconds_to_mjd(0.0), MJD_UNIX_EPOCH);
+        assert_eq!(jd_to_seconds(2_451_545.0), 946_728_000.0);
+        assert!((seconds_to_jd(jd_to_seconds(2_460_000.25)) - 2_460_000.25).abs() < 1e-9);
+        assert_eq!(TimeScale::Tdb.to_string(), "TDB");
+    }
+}
@@ -0,0 +1,1339 @@
+//! Arrow record batches and Parquet files (feature `arrow`).
+//!
+//! Series are stored long: one row per point, with a `series` column
+//! numbering each [`Series`] in write order, and that series' `label`,
+//! `modality`, `y_unit`, `time_scale`, `time_frame` and `sigma_kind`
+//! repeated on every row (Parquet dictionary-encodes them). `sigma_kind` is
+//! `Unknown`, `Homoscedastic` or `PerPoint`, so a σ column full of `1.0` is
+//! never mistaken for “no errors”. Each covariate is a nullable column, null
+//! for every row of a series that does not carry it.
+//!
+//! Assessments are one row per [`AssessmentRecord`]: the decision, FAPs,
+//! quality flags, confirmation, sidereal correction, period drift, glint
+//! ephemeris and detrend summary as scalar columns, and aliases, glint
+//! events and notes as list columns. Periodograms, passes and detrend
+//! coefficients are not stored; use the JSON schema for complete records.
+//!
+//! Every layout bump so far only added columns, so readers accept any
+//! version up to [`LAYOUT_VERSION`] and treat a missing later column as
+//! null: `time_scale` and `time_frame` default to UTC and observer time,
+//! and the FAPs, sidereal, drift and glint fields read as `None`.
+
+use crate::entities::assessment::{
+    Alias, AliasKind, Confirmation, DetrendMode, GlintEvent, GlintReport, MethodId, PabSource,
+    PeriodDrift, PeriodicityAssessment, PeriodicityDecision, ScoreKind, SearchScale,
+    SiderealCorrection,
+};
+use crate::entities::series::{
+    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
+};
+use crate::functions::time_scale::TimeScale;
+use arrow_array::builder::{BooleanBuilder, Float64Builder, ListBuilder, StringBuilder};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, UInt16Type, UInt32Type, UInt64Type};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt16Array,
+    UInt32Array, UInt64Array,
+};
+use arrow_schema::{Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::file::reader::ChunkReader;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Layout version written to the schema metadata under [`VERSION_KEY`].
+pub const LAYOUT_VERSION: u32 = 7;
+/// Schema metadata key holding [`LAYOUT_VERSION`].
+pub const VERSION_KEY: &str = "cepheid.layout_version";
+/// Schema metadata key holding `"series"` or `"assessment"`.
+pub const TABLE_KEY: &str = "cepheid.table";
+
+const DECISIONS: [PeriodicityDecision; 3] = [
+    PeriodicityDecision::Periodic,
+    PeriodicityDecision::NotPeriodic,
+    PeriodicityDecision::Inconclusive,
+];
+const SCORE_KINDS: [ScoreKind; 8] = [
+    ScoreKind::GlsPower,
+    ScoreKind::PdmTheta,
+    ScoreKind::LogOdds,
+    ScoreKind::StringLengthRatio,
+    ScoreKind::ConditionalEntropy,
+    ScoreKind::LaflerKinmanTheta,
+    ScoreKind::BlsPower,
+    ScoreKind::SpectralWindow,
+];
+const METHODS: [MethodId; 9] = [
+    MethodId::Gls,
+    MethodId::Pdm,
+    MethodId::GregoryLoredo,
+    MethodId::QuasiPeriodicGp,
+    MethodId::StringLength,
+    MethodId::MultibandGls,
+    MethodId::ConditionalEntropy,
+    MethodId::LaflerKinman,
+    MethodId::Bls,
+];
+const ALIAS_KINDS: [AliasKind; 11] = [
+    AliasKind::Harmonic,
+    AliasKind::HalfPeriod,
+    AliasKind::SiderealDay,
+    AliasKind::SolarDay,
+    AliasKind::Orbital,
+    AliasKind::OrbitalHalf,
+    AliasKind::PassCadence,
+    AliasKind::PassDuration,
+    AliasKind::WindowPeak,
+    AliasKind::OtherPeak,
+    AliasKind::EclipseIngress,
+];
+const DETREND_MODES: [DetrendMode; 5] = [
+    DetrendMode::Auto,
+    DetrendMode::LinearTime,
+    DetrendMode::PhaseFunction,
+    DetrendMode::Elevation,
+    DetrendMode::None,
+];
+const SCALES: [SearchScale; 4] = [
+    SearchScale::Auto,
+    SearchScale::IntraPass,
+    SearchScale::InterPass,
+    SearchScale::Full,
+];
+const MODALITIES: [Modality; 3] = [
+    Modality::OpticalPhotometry,
+    Modality::RfPower,
+    Modality::Generic,
+];
+const Y_UNITS: [YUnit; 4] = [
+    YUnit::Magnitude,
+    YUnit::Decibels,
+    YUnit::LinearPower,
+    YUnit::Dimensionless,
+];
+const TIME_SCALES: [TimeScale; 4] = [
+    TimeScale::Utc,
+    TimeScale::Tai,
+    TimeScale::Tt,
+    TimeScale::Tdb,
+];
+const TIME_FRAMES: [TimeFrame; 2] = [TimeFrame::Observer, TimeFrame::Target];
+const PAB_SOURCES: [PabSource; 2] = [PabSource::Vectors, PabSource::PhaseAngle];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnarError {
+    Arrow(String),
+    Parquet(String),
+    /// Missing column, wrong type, unknown enum name or layout version.
+    Layout(String),
+    Series {
+        series: u32,
+        error: SeriesError,
+    },
+}
+
+impl std::fmt::Display for ColumnarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnarError::Arrow(e) => write!(f, "arrow error: {e}"),
+            ColumnarError::Parquet(e) => write!(f, "parquet error: {e}"),
+            ColumnarError::Layout(e) => write!(f, "unexpected layout: {e}"),
+            ColumnarError::Series { series, error } => write!(f, "series {series}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ColumnarError {}
+
+impl From<arrow_schema::ArrowError> for ColumnarError {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        ColumnarError::Arrow(e.to_string())
+    }
+}
+
+impl From<parquet::errors::ParquetError> for ColumnarError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        ColumnarError::Parquet(e.to_string())
+    }
+}
+
+/// One assessment row, keyed by an optional object label.
+#[derive(Clone, Debug)]
+pub struct AssessmentRecord {
+    pub label: Option<String>,
+    pub assessment: PeriodicityAssessment,
+}
+
+/// All `series` in one batch, numbered `0..series.len()` in the `series` column.
+pub fn series_to_batch(series: &[Series]) -> Result<RecordBatch, ColumnarError> {
+    let n: usize = series.iter().map(Series::len).sum();
+    let mut id = Vec::with_capacity(n);
+    let mut label: Vec<Option<&str>> = Vec::with_capacity(n);
+    let mut modality = Vec::with_capacity(n);
+    let mut y_unit = Vec::with_capacity(n);
+    let mut time_scale = Vec::with_capacity(n);
+    let mut time_frame = Vec::with_capacity(n);
+    let mut n_merged = Vec::with_capacity(n);
+    let mut t_s = Vec::with_capacity(n);
+    let mut y = Vec::with_capacity(n);
+    let mut sigma_kind = Vec::with_capacity(n);
+    let mut sigma: Vec<Option<f64>> = Vec::with_capacity(n);
+    let mut phase: Vec<Option<f64>> = Vec::with_capacity(n);
+    let mut range: Vec<Option<f64>> = Vec::with_capacity(n);
+    let mut elev: Vec<Option<f64>> = Vec::with_capacity(n);
+    let mut illum: Vec<Option<f64>> = Vec::with_capacity(n);
+    let mut sensor: Vec<Option<u16>> = Vec::with_capacity(n);
+    let mut band: Vec<Option<u16>> = Vec::with_capacity(n);
+
+    for (k, s) in series.iter().enumerate() {
+        let k = u32::try_from(k)
+            .map_err(|_| ColumnarError::Layout("more than u32::MAX series".into()))?;
+        let m = s.len();
+        let meta = s.meta();
+        let cov = s.covariates();
+        id.extend(std::iter::repeat_n(k, m));
+        label.extend(std::iter::repeat_n(meta.label.as_deref(), m));
+        modality.extend(std::iter::repeat_n(name(meta.modality), m));
+        y_unit.extend(std::iter::repeat_n(name(meta.y_unit), m));
+        time_scale.extend(std::iter::repeat_n(name(meta.time_scale), m));
+        time_frame.extend(std::iter::repeat_n(name(meta.time_frame), m));
+        n_merged.extend(std::iter::repeat_n(s.n_merged_duplicates() as u64, m));
+        t_s.extend_from_slice(s.t_s());
+        y.extend_from_slice(s.y());
+        match s.sigma_spec() {
+            SigmaSpec::Unknown => {
+                sigma_kind.extend(std::iter::repeat_n("Unknown", m));
+                sigma.extend(std::iter::repeat_n(None, m));
+            }
+            SigmaSpec::Homoscedastic(v) => {
+                sigma_kind.extend(std::iter::repeat_n("Homoscedastic", m));
+                sigma.extend(std::iter::repeat_n(Some(*v), m));
+            }
+            SigmaSpec::PerPoint(v) => {
+                sigma_kind.extend(std::iter::repeat_n("PerPoint", m));
+                sigma.extend(v.iter().copied().map(Some));
+            }
+        }
+        push_opt(&mut phase, cov.solar_phase_rad.as_deref(), m);
+        push_opt(&mut range, cov.range_m.as_deref(), m);
+        push_opt(&mut elev, cov.elevation_rad.as_deref(), m);
+        push_opt(&mut illum, cov.illumination.as_deref(), m);
+        push_opt(&mut sensor, cov.sensor_key.as_deref(), m);
+        push_opt(&mut band, cov.band_key.as_deref(), m);
+    }
+
+    batch(
+        "series",
+        vec![
+            ("series", Arc::new(UInt32Array::from(id)), false),
+            ("label", Arc::new(StringArray::from(label)), true),
+            ("modality", Arc::new(StringArray::from(modality)), false),
+            ("y_unit", Arc::new(StringArray::from(y_unit)), false),
+            ("time_scale", Arc::new(StringArray::from(time_scale)), false),
+            ("time_frame", Arc::new(StringArray::from(time_frame)), false),
+            (
+                "n_merged_duplicates",
+                Arc::new(UInt64Array::from(n_merged)),
+                false,
+            ),
+            ("t_s", Arc::new(Float64Array::from(t_s)), false),
+            ("y", Arc::new(Float64Array::from(y)), false),
+            ("sigma_kind", Arc::new(StringArray::from(sigma_kind)), false),
+            ("sigma", Arc::new(Float64Array::from(sigma)), true),
+            ("solar_phase_rad", Arc::new(Float64Array::from(phase)), true),
+            ("range_m", Arc::new(Float64Array::from(range)), true),
+            ("elevation_rad", Arc::new(Float64Array::from(elev)), true),
+            ("illumination", Arc::new(Float64Array::from(illum)), true),
+            ("sensor_key", Arc::new(UInt16Array::from(sensor)), true),
+            ("band_key", Arc::new(UInt16Array::from(band)), true),
+        ],
+    )
+}
+
+/// Series from batches written by [`series_to_batch`], in row order. A new
+/// series starts whenever the `series` id changes and at every batch
+/// boundary, so batches from separate files never merge; pass each file as
+/// one batch (as [`read_series_parquet`] does). The per-series metadata
+/// columns must be constant within a series. Covariate columns may be absent
+/// (read as all-null); a covariate is `Some` when any of its rows is
+/// non-null, with nulls read back as NaN.
+pub fn series_from_batches(batches: &[RecordBatch]) -> Result<Vec<Series>, ColumnarError> {
+    let mut out: Vec<Series> = Vec::new();
+    for b in batches {
+        let mut cur: Option<SeriesRows> = None;
+        let mut first = 0;
+        check_layout(b, "series")?;
+        let id = b
+            .column_by_name("series")
+            .and_then(|c| c.as_primitive_opt::<UInt32Type>())
+            .ok_or_else(|| missing("series"))?;
+        let label = opt_str(b, "label")?;
+        let modality = req_str(b, "modality")?;
+        let y_unit = req_str(b, "y_unit")?;
+        let time_scale = opt_str(b, "time_scale")?;
+        let time_frame = opt_str(b, "time_frame")?;
+        let n_merged = b
+            .column_by_name("n_merged_duplicates")
+            .map(|c| {
+                c.as_primitive_opt::<UInt64Type>()
+                    .ok_or_else(|| wrong("n_merged_duplicates"))
+            })
+            .transpose()?;
+        let t_s = req_f64(b, "t_s")?;
+        let y = req_f64(b, "y")?;
+        let sigma_kind = req_str(b, "sigma_kind")?;
+        let sigma = opt_f64(b, "sigma")?;
+        let phase = opt_f64(b, "solar_phase_rad")?;
+        let range = opt_f64(b, "range_m")?;
+        let elev = opt_f64(b, "elevation_rad")?;
+        let illum = opt_f64(b, "illumination")?;
+        let sensor = opt_u16(b, "sensor_key")?;
+        let band = opt_u16(b, "band_key")?;
+
+        for i in 0..b.num_rows() {
+            let k = id.value(i);
+            if cur.as_ref().is_none_or(|c| c.id != k) {
+                if let Some(done) = cur.take() {
+                    out.push(done.finish()?);
+                }
+                first = i;
+                cur = Some(SeriesRows {
+                    id: k,
+                    label: label.and_then(|a| a.is_valid(i).then(|| a.value(i).to_string())),
+                    modality: Some(parse(&MODALITIES, modality.value(i), "modality")?),
+                    y_unit: Some(parse(&Y_UNITS, y_unit.value(i), "y_unit")?),
+                    time_scale: time_scale
+                        .map(|a| parse(&TIME_SCALES, a.value(i), "time_scale"))
+                        .transpose()?
+                        .unwrap_or_default(),
+                    time_frame: time_frame
+                        .map(|a| parse(&TIME_FRAMES, a.value(i), "time_frame"))
+                        .transpose()?
+                        .unwrap_or_default(),
+                    sigma_kind: sigma_kind.value(i).to_string(),
+                    n_merged: n_merged.map(|a| a.value(i) as usize).unwrap_or(0),
+                    ..SeriesRows::default()
+                });
+            }
+            let strs = [
+                ("label", label),
+                ("modality", Some(modality)),
+                ("y_unit", Some(y_unit)),
+                ("time_scale", time_scale),
+                ("time_frame", time_frame),
+                ("sigma_kind", Some(sigma_kind)),
+            ];
+            let changed = strs
+                .into_iter()
+                .find(|(_, a)| a.is_some_and(|a| !same_str(a, first, i)))
+                .map(|(n, _)| n)
+                .or_else(|| {
+                    (value_at(n_merged, first) != value_at(n_merged, i))
+                        .then_some("n_merged_duplicates")
+                });
+            if let Some(col) = changed {
+                return Err(ColumnarError::Layout(format!(
+                    "{col} changes within series {k} at row {i}"
+                )));
+            }
+            let r = cur.as_mut().expect("set above");
+            r.t_s.push(t_s.value(i));
+            r.y.push(y.value(i));
+            r.sigma.push(value_at(sigma, i));
+            r.phase.push(value_at(phase, i));
+            r.range.push(value_at(range, i));
+            r.elev.push(value_at(elev, i));
+            r.illum.push(value_at(illum, i));
+            r.sensor.push(value_at(sensor, i));
+            r.band.push(value_at(band, i));
+        }
+        if let Some(done) = cur {
+            out.push(done.finish()?);
+        }
+    }
+    Ok(out)
+}
+
+/// One row per record; see the module docs for what is kept.
+pub fn assessments_to_batch(rows: &[AssessmentRecord]) -> Result<RecordBatch, ColumnarError> {
+    let a = |f: &dyn Fn(&PeriodicityAssessment) -> Option<f64>| -> ArrayRef {
+        Arc::new(Float64Array::from(
+            rows.iter().map(|r| f(&r.assessment)).collect::<Vec<_>>(),
+        ))
+    };
+    let b = |f: &dyn Fn(&PeriodicityAssessment) -> Option<bool>| -> ArrayRef {
+        Arc::new(BooleanArray::from(
+            rows.iter().map(|r| f(&r.assessment)).collect::<Vec<_>>(),
+        ))
+    };
+    let s = |f: &dyn Fn(&PeriodicityAssessment) -> Option<String>| -> ArrayRef {
+        Arc::new(StringArray::from(
+            rows.iter().map(|r| f(&r.assessment)).collect::<Vec<_>>(),
+        ))
+    };
+    let u = |f: &dyn Fn(&PeriodicityAssessment) -> usize| -> ArrayRef {
+        Arc::new(UInt64Array::from(
+            rows.iter()
+                .map(|r| f(&r.assessment) as u64)
+                .collect::<Vec<_>>(),
+        ))
+    };
+    let alias_f64 = |f: fn(&Alias) -> f64| -> ArrayRef {
+        let mut lb = ListBuilder::new(Float64Builder::new());
+        for r in rows {
+            for al in &r.assessment.aliases {
+                lb.values().append_value(f(al));
+            }
+            lb.append(true);
+        }
+        Arc::new(lb.finish())
+    };
+    let glint_f64 = |f: fn(&GlintEvent) -> f64| -> ArrayRef {
+        let mut lb = ListBuilder::new(Float64Builder::new());
+        for r in rows {
+            for e in r.assessment.glint.iter().flat_map(|g| &g.events) {
+                lb.values().append_value(f(e));
+            }
+            lb.append(true);
+        }
+        Arc::new(lb.finish())
+    };
+    let mut alias_kind = ListBuilder::new(StringBuilder::new());
+    let mut alias_vetoed = ListBuilder::new(BooleanBuilder::new());
+    let mut notes = ListBuilder::new(StringBuilder::new());
+    for r in rows {
+        for al in &r.assessment.aliases {
+            alias_kind.values().append_value(name(al.kind));
+            alias_vetoed.values().append_value(al.vetoed);
+        }
+        alias_kind.append(true);
+        alias_vetoed.append(true);
+        for n in &r.assessment.notes {
+            notes.values().append_value(n);
+        }
+        notes.append(true);
+    }
+    let labels: Vec<Option<&str>> = rows.iter().map(|r| r.label.as_deref()).collect();
+
+    batch(
+        "assessment",
+        vec![
+            ("label", Arc::new(StringArray::from(labels)), true),
+            ("decision", s(&|x| Some(name(x.decision))), false),
+            ("period_s", a(&|x| x.period_s), true),
+            ("period_unc_s", a(&|x| x.period_unc_s), true),
+            ("fap", a(&|x| x.fap), true),
+            ("fap_perm", a(&|x| x.fap_perm), true),
+            ("fap_block", a(&|x| x.fap_block), true),
+            ("fap_baluev", a(&|x| x.fap_baluev), true),
+            ("fap_pdm", a(&|x| x.fap_pdm), true),
+            ("fap_bls", a(&|x| x.fap_bls), true),
+            ("score", a(&|x| Some(x.score)), false),
+            ("score_kind", s(&|x| Some(name(x.score_kind))), false),
+            ("method", s(&|x| Some(name(x.method))), false),
+            ("n", u(&|x| x.quality.n), false),
+            ("n_passes", u(&|x| x.quality.n_passes), false),
+            ("duty_cycle", a(&|x| Some(x.quality.duty_cycle)), false),
+            (
+                "window_contaminated",
+                b(&|x| Some(x.quality.window_contaminated)),
+                false,
+            ),
+            ("undersampled", b(&|x| Some(x.quality.undersampled)), false),
+            ("detrended", b(&|x| Some(x.quality.detrended)), false),
+            (
+                "estimator_agreement",
+                b(&|x| x.quality.estimator_agreement),
+                true,
+            ),
+            ("bound_snap", b(&|x| Some(x.quality.bound_snap)), false),
+            (
+                "confirmation_method",
+                s(&|x| x.confirmation.as_ref().map(|c| name(c.method))),
+                true,
+            ),
+            (
+                "confirmation_period_s",
+                a(&|x| x.confirmation.as_ref().map(|c| c.period_s)),
+                true,
+            ),
+            (
+                "confirmation_score",
+                a(&|x| x.confirmation.as_ref().map(|c| c.score)),
+                true,
+            ),
+            (
+                "confirmation_agrees",
+                b(&|x| x.confirmation.as_ref().map(|c| c.agrees)),
+                true,
+            ),
+            (
+                "sidereal_source",
+                s(&|x| x.sidereal.as_ref().map(|c| name(c.source))),
+                true,
+            ),
+            (
+                "sidereal_pab_rate_rad_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.pab_rate_rad_s)),
+                true,
+            ),
+            (
+                "sidereal_delta_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.delta_s)),
+                true,
+            ),
+            (
+                "sidereal_delta_unc_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.delta_unc_s)),
+                true,
+            ),
+            (
+                "sidereal_period_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.period_s)),
+                true,
+            ),
+            (
+                "sidereal_period_unc_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.period_unc_s)),
+                true,
+            ),
+            (
+                "sidereal_retrograde_period_s",
+                a(&|x| x.sidereal.as_ref().map(|c| c.retrograde_period_s)),
+                true,
+            ),
+            ("pdot", a(&|x| x.drift.as_ref().map(|d| d.pdot)), true),
+            (
+                "pdot_unc",
+                a(&|x| x.drift.as_ref().and_then(|d| d.pdot_unc)),
+                true,
+            ),
+            (
+                "pdot_epoch_s",
+                a(&|x| x.drift.as_ref().map(|d| d.epoch_s)),
+                true,
+            ),
+            (
+                "glint_period_s",
+                a(&|x| x.glint.as_ref().map(|g| g.period_s)),
+                true,
+            ),
+            (
+                "glint_epoch_s",
+                a(&|x| x.glint.as_ref().map(|g| g.epoch_s)),
+                true,
+            ),
+            (
+                "glint_width_s",
+                a(&|x| x.glint.as_ref().map(|g| g.width_s)),
+                true,
+            ),
+            ("glint_duty", a(&|x| x.glint.as_ref().map(|g| g.duty)), true),
+            (
+                "glint_depth",
+                a(&|x| x.glint.as_ref().map(|g| g.depth)),
+                true,
+            ),
+            (
+                "glint_power",
+                a(&|x| x.glint.as_ref().map(|g| g.power)),
+                true,
+            ),
+            ("glint_event_t_s", glint_f64(|e| e.t_s), false),
+            ("glint_event_width_s", glint_f64(|e| e.width_s), false),
+            ("glint_event_amplitude", glint_f64(|e| e.amplitude), false),
+            ("detrend_mode", s(&|x| Some(name(x.detrend.mode))), false),
+            ("detrend_scale", s(&|x| Some(name(x.detrend.scale))), false),
+            ("detrend_cond", a(&|x| Some(x.detrend.cond)), false),
+            ("detrend_n_beta", u(&|x| x.detrend.n_beta), false),
+            ("detrend_fallback", b(&|x| Some(x.detrend.fallback)), false),
+            ("span_s", a(&|x| Some(x.sampling.span_s)), false),
+            (
+                "min_searchable_period_s",
+                a(&|x| Some(x.sampling.min_searchable_period_s)),
+                false,
+            ),
+            (
+                "max_searchable_period_s",
+                a(&|x| Some(x.sampling.max_searchable_period_s)),
+                false,
+            ),
+            ("alias_period_s", alias_f64(|al| al.period_s), false),
+            ("alias_score", alias_f64(|al| al.score), false),
+            ("alias_kind", Arc::new(alias_kind.finish()), false),
+            (
+                "alias_relative_delta",
+                alias_f64(|al| al.relative_delta),
+                false,
+            ),
+            ("alias_vetoed", Arc::new(alias_vetoed.finish()), false),
+            ("notes", Arc::new(notes.finish()), false),
+        ],
+    )
+}
+
+/// Records from batches written by [`assessments_to_batch`]. Fields the
+/// layout does not store keep their [`PeriodicityAssessment::new`] defaults.
+pub fn assessments_from_batches(
+    batches: &[RecordBatch],
+) -> Result<Vec<AssessmentRecord>, ColumnarError> {
+    let mut out = Vec::new();
+    for b in batches {
+        check_layout(b, "assessment")?;
+        let label = opt_str(b, "label")?;
+        let decision = req_str(b, "decision")?;
+        let f = |n: &str| req_f64(b, n);
+        // Columns added after layout 1; absent in older files.
+        let g = |n: &str| opt_f64(b, n);
+        let flag = |n: &str| {
+            b.column_by_name(n)
+                .ok_or_else(|| missing(n))?
+                .as_boolean_opt()
+                .ok_or_else(|| wrong(n))
+        };
+        let count = |n: &str| {
+            b.column_by_name(n)
+                .ok_or_else(|| missing(n))?
+                .as_primitive_opt::<UInt64Type>()
+                .ok_or_else(|| wrong(n))
+        };
+        let list = |n: &str| {
+            b.column_by_name(n)
+                .ok_or_else(|| missing(n))?
+                .as_list_opt::<i32>()
+                .ok_or_else(|| wrong(n))
+        };
+        let opt_list = |n: &str| {
+            b.column_by_name(n)
+                .map(|c| c.as_list_opt::<i32>().ok_or_else(|| wrong(n)))
+                .transpose()
+        };
+        let (period, period_unc) = (f("period_s")?, f("period_unc_s")?);
+        let (fap, fap_perm, fap_block, fap_baluev, fap_pdm, fap_bls) = (
+            f("fap")?,
+            f("fap_perm")?,
+            f("fap_block")?,
+            f("fap_baluev")?,
+            g("fap_pdm")?,
+            g("fap_bls")?,
+        );
+        let (score, score_kind, method) = (
+            f("score")?,
+            req_str(b, "score_kind")?,
+            req_str(b, "method")?,
+        );
+        let (n, n_passes, duty) = (count("n")?, count("n_passes")?, f("duty_cycle")?);
+        let (contaminated, undersampled, detrended) = (
+            flag("window_contaminated")?,
+            flag("undersampled")?,
+            flag("detrended")?,
+        );
+        let (agreement, bound_snap) = (flag("estimator_agreement")?, flag("bound_snap")?);
+        let c_method = req_str(b, "confirmation_method")?;
+        let (c_period, c_score, c_agrees) = (
+            f("confirmation_period_s")?,
+            f("confirmation_score")?,
+            flag("confirmation_agrees")?,
+        );
+        let sd_source = opt_str(b, "sidereal_source")?;
+        let (sd_rate, sd_delta, sd_delta_unc, sd_period, sd_period_unc, sd_retro) = (
+            g("sidereal_pab_rate_rad_s")?,
+            g("sidereal_delta_s")?,
+            g("sidereal_delta_unc_s")?,
+            g("sidereal_period_s")?,
+            g("sidereal_period_unc_s")?,
+            g("sidereal_retrograde_period_s")?,
+        );
+        let (pdot, pdot_unc, pdot_epoch) = (g("pdot")?, g("pdot_unc")?, g("pdot_epoch_s")?);
+        let (g_period, g_epoch, g_width, g_duty, g_depth, g_power) = (
+            g("glint_period_s")?,
+            g("glint_epoch_s")?,
+            g("glint_width_s")?,
+            g("glint_duty")?,
+            g("glint_depth")?,
+            g("glint_power")?,
+        );
+        let (ge_t, ge_width, ge_amp) = (
+            opt_list("glint_event_t_s")?,
+            opt_list("glint_event_width_s")?,
+            opt_list("glint_event_amplitude")?,
+        );
+        let (d_mode, d_scale) = (req_str(b, "detrend_mode")?, req_str(b, "detrend_scale")?);
+        let (d_cond, d_n_beta, d_fallback) = (
+            f("detrend_cond")?,
+            count("detrend_n_beta")?,
+            flag("detrend_fallback")?,
+        );
+        let (span, min_p, max_p) = (
+            f("span_s")?,
+            f("min_searchable_period_s")?,
+            f("max_searchable_period_s")?,
+        );
+        let (al_period, al_score, al_kind, al_delta, al_vetoed, notes) = (
+            list("alias_period_s")?,
+            list("alias_score")?,
+            list("alias_kind")?,
+            list("alias_relative_delta")?,
+            list("alias_vetoed")?,
+            list("notes")?,
+        );
+
+        for i in 0..b.num_rows() {
+            let mut a = PeriodicityAssessment::new(
+                parse(&DECISIONS, decision.value(i), "decision")?,
+                value_at(Some(period), i),
+            );
+            a.period_unc_s = value_at(Some(period_unc), i);
+            a.fap = value_at(Some(fap), i);
+            a.fap_perm = value_at(Some(fap_perm), i);
+            a.fap_block = value_at(Some(fap_block), i);
+            a.fap_baluev = value_at(Some(fap_baluev), i);
+            a.fap_pdm = value_at(fap_pdm, i);
+            a.fap_bls = value_at(fap_bls, i);
+            a.score = score.value(i);
+            a.score_kind = parse(&SCORE_KINDS, score_kind.value(i), "score_kind")?;
+            a.method = parse(&METHODS, method.value(i), "method")?;
+            a.quality.n = n.value(i) as usize;
+            a.quality.n_passes = n_passes.value(i) as usize;
+            a.quality.duty_cycle = duty.value(i);
+            a.quality.window_contaminated = contaminated.value(i);
+            a.quality.undersampled = undersampled.value(i);
+            a.quality.detrended = detrended.value(i);
+            a.quality.estimator_agreement = agreement.is_valid(i).then(|| agreement.value(i));
+            a.quality.bound_snap = bound_snap.value(i);
+            if c_method.is_valid(i) {
+                a.confirmation = Some(Confirmation {
+                    method: parse(&METHODS, c_method.value(i), "confirmation_method")?,
+                    period_s: c_period.value(i),
+                    score: c_score.value(i),
+                    agrees: c_agrees.value(i),
+                });
+            }
+            // A present group with a missing companion column reads as NaN.
+            let v = |c: Option<&Float64Array>| value_at(c, i).unwrap_or(f64::NAN);
+            if let Some(src) = sd_source.filter(|c| c.is_valid(i)) {
+                a.sidereal = Some(SiderealCorrection {
+                    source: parse(&PAB_SOURCES, src.value(i), "sidereal_source")?,
+                    pab_rate_rad_s: v(sd_rate),
+                    delta_s: v(sd_delta),
+                    delta_unc_s: v(sd_delta_unc),
+                    period_s: v(sd_period),
+                    period_unc_s: v(sd_period_unc),
+                    retrograde_period_s: v(sd_retro),
+                });
+            }
+            if let Some(epoch_s) = value_at(pdot_epoch, i) {
+                a.drift = Some(PeriodDrift {
+                    pdot: v(pdot),
+                    pdot_unc: value_at(pdot_unc, i),
+                    epoch_s,
+                });
+            }
+            if let Some(period_s) = value_at(g_period, i) {
+                let events = match (ge_t, ge_width, ge_amp) {
+                    (Some(ge_t), Some(ge_width), Some(ge_amp)) => {
+                        let (et, ew, ea) = (ge_t.value(i), ge_width.value(i), ge_amp.value(i));
+                        let (et, ew, ea) = (
+                            et.as_primitive_opt::<Float64Type>()
+                                .ok_or_else(|| wrong("glint_event_t_s"))?,
+                            ew.as_primitive_opt::<Float64Type>()
+                                .ok_or_else(|| wrong("glint_event_width_s"))?,
+                            ea.as_primitive_opt::<Float64Type>()
+                                .ok_or_else(|| wrong("glint_event_amplitude"))?,
+                        );
+                        if ew.len() != et.len() || ea.len() != et.len() {
+                            return Err(ColumnarError::Layout(format!(
+                                "glint event lists differ in length at row {i}"
+                            )));
+                        }
+                        (0..et.len())
+                            .map(|j| GlintEvent {
+                                t_s: et.value(j),
+                                width_s: ew.value(j),
+                                amplitude: ea.value(j),
+                            })
+                            .collect()
+                    }
+                    _ => Vec::new(),
+                };
+                a.glint = Some(GlintReport {
+                    period_s,
+                    epoch_s: v(g_epoch),
+                    width_s: v(g_width),
+                    duty: v(g_duty),
+                    depth: v(g_depth),
+                    power: v(g_power),
+                    events,
+                });
+            }
+            a.detrend.mode = parse(&DETREND_MODES, d_mode.value(i), "detrend_mode")?;
+            a.detrend.scale = parse(&SCALES, d_scale.value(i), "detrend_scale")?;
+            a.detrend.cond = d_cond.value(i);
+            a.detrend.n_beta = d_n_beta.value(i) as usize;
+            a.detrend.fallback = d_fallback.value(i);
+            a.sampling.n = a.quality.n;
+            a.sampling.n_passes = a.quality.n_passes;
+            a.sampling.duty_cycle = a.quality.duty_cycle;
+            a.sampling.span_s = span.value(i);
+            a.sampling.min_searchable_period_s = min_p.value(i);
+            a.sampling.max_searchable_period_s = max_p.value(i);
+
+            let p = al_period.value(i);
+            let sc = al_score.value(i);
+            let k = al_kind.value(i);
+            let d = al_delta.value(i);
+            let v = al_vetoed.value(i);
+            let (p, sc, d) = (
+                p.as_primitive_opt::<Float64Type>()
+                    .ok_or_else(|| wrong("alias_period_s"))?,
+                sc.as_primitive_opt::<Float64Type>()
+                    .ok_or_else(|| wrong("alias_score"))?,
+                d.as_primitive_opt::<Float64Type>()
+                    .ok_or_else(|| wrong("alias_relative_delta"))?,
+            );
+            let k = k
+                .as_string_opt::<i32>()
+                .ok_or_else(|| wrong("alias_kind"))?;
+            let v = v.as_boolean_opt().ok_or_else(|| wrong("alias_vetoed"))?;
+            if [sc.len(), k.len(), d.len(), v.len()]
+                .iter()
+                .any(|&l| l != p.len())
+            {
+                return Err(ColumnarError::Layout(format!(
+                    "alias lists differ in length at row {i}"
+                )));
+            }
+            for j in 0..p.len() {
+                a.aliases.push(Alias {
+                    period_s: p.value(j),
+                    score: sc.value(j),
+                    kind: parse(&ALIAS_KINDS, k.value(j), "alias_kind")?,
+                    relative_delta: d.value(j),
+                    vetoed: v.value(j),
+                });
+            }
+            let nt = notes.value(i);
+            let nt = nt.as_string_opt::<i32>().ok_or_else(|| wrong("notes"))?;
+            a.notes = (0..nt.len()).map(|j| nt.value(j).to_string()).collect();
+
+            out.push(AssessmentRecord {
+                label: label.and_then(|l| l.is_valid(i).then(|| l.value(i).to_string())),
+                assessment: a,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// [`series_to_batch`] as a Parquet file.
+pub fn write_series_parquet<W: Write + Send>(w: W, series: &[Series]) -> Result<(), ColumnarError> {
+    write_parquet(w, &series_to_batch(series)?)
+}
+
+pub fn read_series_parquet<R: ChunkReader + 'static>(r: R) -> Result<Vec<Series>, ColumnarError> {
+    series_from_batches(&read_parquet(r)?)
+}
+
+/// [`assessments_to_batch`] as a Parquet file.
+pub fn write_assessments_parquet<W: Write + Send>(
+    w: W,
+    rows: &[AssessmentRecord],
+) -> Result<(), ColumnarError> {
+    write_parquet(w, &assessments_to_batch(rows)?)
+}
+
+pub fn read_assessments_parquet<R: ChunkReader + 'static>(
+    r: R,
+) -> Result<Vec<AssessmentRecord>, ColumnarError> {
+    assessments_from_batches(&read_parquet(r)?)
+}
+
+/// Rows of one series while reading.
+#[derive(Default)]
+struct SeriesRows {
+    id: u32,
+    label: Option<String>,
+    modality: Option<Modality>,
+    y_unit: Option<YUnit>,
+    time_scale: TimeScale,
+    time_frame: TimeFrame,
+    sigma_kind: String,
+    n_merged: usize,
+    t_s: Vec<f64>,
+    y: Vec<f64>,
+    sigma: Vec<Option<f64>>,
+    phase: Vec<Option<f64>>,
+    range: Vec<Option<f64>>,
+    elev: Vec<Option<f64>>,
+    illum: Vec<Option<f64>>,
+    sensor: Vec<Option<u16>>,
+    band: Vec<Option<u16>>,
+}
+
+impl SeriesRows {
+    fn finish(self) -> Result<Series, ColumnarError> {
+        let sigma = match self.sigma_kind.as_str() {
+            "Unknown" => SigmaSpec::Unknown,
+            "Homoscedastic" => SigmaSpec::Homoscedastic(
+                self.sigma
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .next()
+                    .unwrap_or(f64::NAN),
+            ),
+            "PerPoint" => {
+                SigmaSpec::PerPoint(self.sigma.iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+            }
+            other => {
+                return Err(ColumnarError::Layout(format!(
+                    "unknown sigma_kind {other:?}"
+                )));
+            }
+        };
+        let id = self.id;
+        let mut s = Series::try_new(
+            self.t_s,
+            self.y,
+            sigma,
+            Covariates {
+                solar_phase_rad: collect_opt(self.phase, f64::NAN),
+                range_m: collect_opt(self.range, f64::NAN),
+                elevation_rad: collect_opt(self.elev, f64::NAN),
+                illumination: collect_opt(self.illum, f64::NAN),
+                sensor_key: collect_opt(self.sensor, 0),
+                band_key: collect_opt(self.band, 0),
+                keep: None,
+            },
+            SeriesMeta {
+                modality: self.modality.unwrap_or(Modality::Generic),
+                y_unit: self.y_unit.unwrap_or(YUnit::Dimensionless),
+                label: self.label,
+                time_scale: self.time_scale,
+                time_frame: self.time_frame,
+            },
+        )
+        .map_err(|error| ColumnarError::Series { series: id, error })?;
+        s.add_merged_duplicates(self.n_merged);
+        Ok(s)
+    }
+}
+
+fn push_opt<T: Copy>(out: &mut Vec<Option<T>>, v: Option<&[T]>, m: usize) {
+    match v {
+        Some(v) => out.extend(v.iter().copied().map(Some)),
+        None => out.extend(std::iter::repeat_n(None, m)),
+    }
+}
+
+fn collect_opt<T: Copy>(v: Vec<Option<T>>, fill: T) -> Option<Vec<T>> {
+    v.iter()
+        .any(Option::is_some)
+        .then(|| v.into_iter().map(|x| x.unwrap_or(fill)).collect())
+}
+
+fn batch(table: &str, cols: Vec<(&str, ArrayRef, bool)>) -> Result<RecordBatch, ColumnarError> {
+    let fields: Vec<Field> = cols
+        .iter()
+        .map(|(n, a, nullable)| Field::new(*n, a.data_type().clone(), *nullable))
+        .collect();
+    let metadata = HashMap::from([
+        (VERSION_KEY.to_string(), LAYOUT_VERSION.to_string()),
+        (TABLE_KEY.to_string(), table.to_string()),
+    ]);
+    let schema = Arc::new(Schema::new(fields).with_metadata(metadata));
+    Ok(RecordBatch::try_new(
+        schema,
+        cols.into_iter().map(|(_, a, _)| a).collect(),
+    )?)
+}
+
+/// Reject batches stamped with another table or a layout newer than
+/// [`LAYOUT_VERSION`]. Unstamped batches (from other tools) are read by
+/// column name.
+#[allow(clippy::collapsible_if)]
+fn check_layout(b: &RecordBatch, table: &str) -> Result<(), ColumnarError> {
+    let schema = b.schema();
+    let md = schema.metadata();
+    if let Some(t) = md.get(TABLE_KEY) {
+        if t != table {
+            return Err(ColumnarError::Layout(format!(
+                "{t:?} table where {table:?} was expected"
+            )));
+        }
+    }
+    if let Some(v) = md.get(VERSION_KEY) {
+        if !v
+            .parse::<u32>()
+            .is_ok_and(|v| (1..=LAYOUT_VERSION).contains(&v))
+        {
+            return Err(ColumnarError::Layout(format!(
+                "layout version {v} is not supported (this build reads 1..={LAYOUT_VERSION})"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn write_parquet<W: Write + Send>(w: W, b: &RecordBatch) -> Result<(), ColumnarError> {
+    let mut writer = ArrowWriter::try_new(w, b.schema(), None)?;
+    writer.write(b)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// The whole file as one batch (none when it has no rows).
+fn read_parquet<R: ChunkReader + 'static>(r: R) -> Result<Vec<RecordBatch>, ColumnarError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(r)?;
+    let rows = builder.metadata().file_metadata().num_rows().max(1) as usize;
+    builder
+        .with_batch_size(rows)
+        .build()?
+        .map(|b| b.map_err(ColumnarError::from))
+        .collect()
+}
+
+fn missing(col: &str) -> ColumnarError {
+    ColumnarError::Layout(format!("missing column {col:?}"))
+}
+
+fn wrong(col: &str) -> ColumnarError {
+    ColumnarError::Layout(format!("column {col:?} has the wrong type"))
+}
+
+fn req_f64<'a>(b: &'a RecordBatch, col: &str) -> Result<&'a Float64Array, ColumnarError> {
+    b.column_by_name(col)
+        .ok_or_else(|| missing(col))?
+        .as_primitive_opt::<Float64Type>()
+        .ok_or_else(|| wrong(col))
+}
+
+fn opt_f64<'a>(b: &'a RecordBatch, col: &str) -> Result<Option<&'a Float64Array>, ColumnarError> {
+    b.column_by_name(col)
+        .map(|c| {
+            c.as_primitive_opt::<Float64Type>()
+                .ok_or_else(|| wrong(col))
+        })
+        .transpose()
+}
+
+fn opt_u16<'a>(b: &'a RecordBatch, col: &str) -> Result<Option<&'a UInt16Array>, ColumnarError> {
+    b.column_by_name(col)
+        .map(|c| c.as_primitive_opt::<UInt16Type>().ok_or_else(|| wrong(col)))
+        .transpose()
+}
+
+fn req_str<'a>(b: &'a RecordBatch, col: &str) -> Result<&'a StringArray, ColumnarError> {
+    b.column_by_name(col)
+        .ok_or_else(|| missing(col))?
+        .as_string_opt::<i32>()
+        .ok_or_else(|| wrong(col))
+}
+
+fn opt_str<'a>(b: &'a RecordBatch, col: &str) -> Result<Option<&'a StringArray>, ColumnarError> {
+    b.column_by_name(col)
+        .map(|c| c.as_string_opt::<i32>().ok_or_else(|| wrong(col)))
+        .transpose()
+}
+
+/// Rows `i` and `j` of `a` hold the same string, or are both null.
+fn same_str(a: &StringArray, i: usize, j: usize) -> bool {
+    a.is_valid(i) == a.is_valid(j) && (a.is_null(i) || a.value(i) == a.value(j))
+}
+
+fn value_at<T: arrow_array::ArrowPrimitiveType>(
+    a: Option<&arrow_array::PrimitiveArray<T>>,
+    i: usize,
+) -> Option<T::Native> {
+    a.filter(|a| a.is_valid(i)).map(|a| a.value(i))
+}
+
+/// Variant name as written: the `Debug` form of a fieldless enum.
+fn name<T: std::fmt::Debug>(v: T) -> String {
+    format!("{v:?}")
+}
+
+fn parse<T: Copy + std::fmt::Debug>(all: &[T], s: &str, col: &str) -> Result<T, ColumnarError> {
+    all.iter()
+        .copied()
+        .find(|v| name(*v) == s)
+        .ok_or_else(|| ColumnarError::Layout(format!("unknown {col} {s:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assessment::AliasKind;
+
+    fn series(sigma: SigmaSpec, cov: Covariates, label: Option<&str>) -> Series {
+        Series::try_new(
+            vec![0.0, 10.0, 20.0],
+            vec![1.0, 2.0, 3.0],
+            sigma,
+            cov,
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: label.map(str::to_string),
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn series_round_trip_through_parquet_keeps_sigma_kind() {
+        let written = vec![
+            series(SigmaSpec::Unknown, Covariates::default(), None),
+            series(
+                SigmaSpec::Homoscedastic(1.0),
+                Covariates {
+                    range_m: Some(vec![1.0e6, 2.0e6, 3.0e6]),
+                    illumination: Some(vec![1.0, f64::NAN, 0.5]),
+                    band_key: Some(vec![2, 2, 6]),
+                    ..Covariates::default()
+                },
+                Some("obj-7"),
+            )
+            .to_time_frame(TimeFrame::Target)
+            .unwrap(),
+            series(
+                SigmaSpec::PerPoint(vec![0.1, 0.2, 0.3]),
+                Covariates {
+                    sensor_key: Some(vec![0, 1, 0]),
+                    ..Covariates::default()
+                },
+                None,
+            ),
+        ];
+        let mut buf = Vec::new();
+        write_series_parquet(&mut buf, &written).unwrap();
+        let back = read_series_parquet(bytes::Bytes::from(buf)).unwrap();
+
+        assert_eq!(back.len(), 3);
+        assert!(back[0].sigma_spec().is_unknown());
+        assert_eq!(back[1].sigma_spec(), &SigmaSpec::Homoscedastic(1.0));
+        assert_eq!(back[2].sigma_spec(), written[2].sigma_spec());
+        assert_eq!(back[1].meta().label.as_deref(), Some("obj-7"));
+        assert_eq!(back[1].meta().y_unit, YUnit::Magnitude);
+        assert_eq!(back[1].meta().time_frame, TimeFrame::Target);
+        assert_eq!(back[0].meta().time_frame, TimeFrame::Observer);
+        for (a, b) in written.iter().zip(&back) {
+            assert_eq!(a.t_s(), b.t_s());
+            assert_eq!(a.y(), b.y());
+            let (ca, cb) = (a.covariates(), b.covariates());
+            assert_eq!(ca.range_m, cb.range_m);
+            assert_eq!(ca.solar_phase_rad.is_some(), cb.solar_phase_rad.is_some());
+            assert_eq!(ca.sensor_key, cb.sensor_key);
+            assert_eq!(ca.band_key, cb.band_key);
+        }
+        let illum = back[1].covariates().illumination.as_ref().unwrap();
+        assert!(illum[1].is_nan() && illum[2] == 0.5);
+    }
+
+    #[test]
+    fn assessments_round_trip_through_parquet() {
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Periodic, Some(47.3));
+        a.fap = Some(1e-4);
+        a.fap_pdm = Some(3e-5);
+        a.score = 0.8;
+        a.method = MethodId::MultibandGls;
+        a.quality.n = 300;
+        a.quality.estimator_agreement = Some(true);
+        a.confirmation = Some(Confirmation {
+            method: MethodId::Pdm,
+            period_s: 47.2,
+            score: 0.3,
+            agrees: true,
+        });
+        a.sidereal = Some(SiderealCorrection {
+            source: PabSource::PhaseAngle,
+            pab_rate_rad_s: 3.6e-5,
+            delta_s: 0.013,
+            delta_unc_s: 0.002,
+            period_s: 47.287,
+            period_unc_s: 0.01,
+            retrograde_period_s: 47.313,
+        });
+        a.drift = Some(PeriodDrift {
+            pdot: 2.0e-7,
+            pdot_unc: None,
+            epoch_s: 43_200.0,
+        });
+        a.fap_bls = Some(2e-6);
+        a.glint = Some(GlintReport {
+            period_s: 47.3,
+            epoch_s: 14.2,
+            width_s: 4.0,
+            duty: 0.085,
+            depth: 2.0,
+            power: 0.6,
+            events: vec![
+                GlintEvent {
+                    t_s: 14.1,
+                    width_s: 2.5,
+                    amplitude: 2.2,
+                },
+                GlintEvent {
+                    t_s: 61.5,
+                    width_s: 0.0,
+                    amplitude: 1.8,
+                },
+            ],
+        });
+        a.detrend.cond = f64::INFINITY;
+        a.aliases.push(Alias {
+            period_s: 94.6,
+            score: 0.4,
+            kind: AliasKind::Harmonic,
+            relative_delta: 0.0,
+            vetoed: false,
+        });
+        a.notes.push("eclipse veto at P=12.0 s".into());
+        let b = PeriodicityAssessment::new(PeriodicityDecision::Inconclusive, None);
+        let rows = vec![
+            AssessmentRecord {
+                label: Some("25544".into()),
+                assessment: a,
+            },
+            AssessmentRecord {
+                label: None,
+                assessment: b,
+            },
+        ];
+
+        let mut buf = Vec::new();
+        write_assessments_parquet(&mut buf, &rows).unwrap();
+        let back = read_assessments_parquet(bytes::Bytes::from(buf)).unwrap();
+        assert_eq!(back.len(), 2);
+        let (x, y) = (&back[0].assessment, &back[1].assessment);
+        assert_eq!(back[0].label.as_deref(), Some("25544"));
+        assert_eq!(x.decision, PeriodicityDecision::Periodic);
+        assert_eq!(
+            (x.period_s, x.fap, x.fap_perm, x.fap_pdm),
+            (Some(47.3), Some(1e-4), None, Some(3e-5))
+        );
+        assert_eq!(x.method, MethodId::MultibandGls);
+        assert_eq!(x.quality.n, 300);
+        assert_eq!(x.quality.estimator_agreement, Some(true));
+        assert_eq!(x.confirmation.as_ref().unwrap().method, MethodId::Pdm);
+        assert_eq!(x.sidereal, rows[0].assessment.sidereal);
+        assert_eq!(x.drift, rows[0].assessment.drift);
+        assert_eq!(x.glint, rows[0].assessment.glint);
+        assert_eq!((x.fap_bls, y.fap_bls), (Some(2e-6), None));
+        assert_eq!(x.detrend.cond, f64::INFINITY);
+        assert_eq!(x.aliases.len(), 1);
+        assert_eq!(x.aliases[0].kind, AliasKind::Harmonic);
+        assert_eq!(x.notes, rows[0].assessment.notes);
+        assert_eq!(back[1].label, None);
+        assert_eq!(y.decision, PeriodicityDecision::Inconclusive);
+        assert!(y.period_s.is_none() && y.confirmation.is_none() && y.aliases.is_empty());
+        assert!(y.sidereal.is_none() && y.drift.is_none() && y.glint.is_none());
+    }
+
+    #[test]
+    fn series_split_at_batch_boundaries_and_keep_constant_metadata() {
+        let b =
+            series_to_batch(&[series(SigmaSpec::Unknown, Covariates::default(), None)]).unwrap();
+        let back = series_from_batches(&[b.clone(), b]).unwrap();
+        assert_eq!(back.len(), 2);
+        assert!(back.iter().all(|s| s.len() == 3));
+
+        let two = [
+            series(SigmaSpec::Unknown, Covariates::default(), Some("a")),
+            series(SigmaSpec::Unknown, Covariates::default(), Some("b")),
+        ];
+        let b = series_to_batch(&two).unwrap();
+        let ids: ArrayRef = Arc::new(UInt32Array::from(vec![0; 6]));
+        let mut cols = b.columns().to_vec();
+        cols[0] = ids;
+        let b = RecordBatch::try_new(b.schema(), cols).unwrap();
+        assert!(matches!(
+            series_from_batches(&[b]),
+            Err(ColumnarError::Layout(e)) if e.contains("label changes within series 0")
+        ));
+
+        // Longer than one default Parquet read batch.
+        let meta = series(SigmaSpec::Unknown, Covariates::default(), None)
+            .meta()
+            .clone();
+        let long = Series::try_new(
+            (0..3000).map(|i| i as f64).collect(),
+            vec![1.0; 3000],
+            SigmaSpec::Unknown,
+            Covariates::default(),
+            meta,
+        )
+        .unwrap();
+        let mut buf = Vec::new();
+        write_series_parquet(&mut buf, &[long]).unwrap();
+        let back = read_series_parquet(bytes::Bytes::from(buf)).unwrap();
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].len(), 3000);
+    }
+
+    /// `b` without the columns matching `drop`, stamped as layout 1.
+    fn as_layout_1(b: &RecordBatch, drop: impl Fn(&str) -> bool) -> RecordBatch {
+        let keep: Vec<usize> = (0..b.num_columns())
+            .filter(|&j| !drop(b.schema().field(j).name()))
+            .collect();
+        let b = b.project(&keep).unwrap();
+        let mut md = b.schema().metadata().clone();
+        md.insert(VERSION_KEY.into(), "1".into());
+        let schema = Arc::new(b.schema().as_ref().clone().with_metadata(md));
+        RecordBatch::try_new(schema, b.columns().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn older_layouts_read_missing_columns_as_null() {
+        let s = series(SigmaSpec::Unknown, Covariates::default(), Some("old"));
+        let b = as_layout_1(&series_to_batch(&[s]).unwrap(), |n| n.starts_with("time_"));
+        let back = series_from_batches(&[b]).unwrap();
+        assert_eq!(back[0].meta().label.as_deref(), Some("old"));
+        assert_eq!(back[0].meta().time_scale, TimeScale::Utc);
+        assert_eq!(back[0].meta().time_frame, TimeFrame::Observer);
+
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::Periodic, Some(47.3));
+        a.fap_pdm = Some(3e-5);
+        a.drift = Some(PeriodDrift {
+            pdot: 1e-6,
+            pdot_unc: None,
+            epoch_s: 0.0,
+        });
+        let rec = AssessmentRecord {
+            label: None,
+            assessment: a,
+        };
+        let b = as_layout_1(&assessments_to_batch(&[rec]).unwrap(), |n| {
+            ["fap_pdm", "fap_bls", "glint_", "sidereal_", "pdot"]
+                .iter()
+                .any(|p| n.starts_with(p))
+        });
+        let back = assessments_from_batches(&[b]).unwrap();
+        let x = &back[0].assessment;
+        assert_eq!(x.period_s, Some(47.3));
+        assert!(x.fap_pdm.is_none() && x.fap_bls.is_none());
+        assert!(x.sidereal.is_none() && x.drift.is_none() && x.glint.is_none());
+    }
+
+    #[test]
+    fn wrong_table_and_version_are_rejected() {
+        let s = series(SigmaSpec::Unknown, Covariates::default(), None);
+        let b = series_to_batch(&[s]).unwrap();
+        assert!(matches!(
+            assessments_from_batches(std::slice::from_ref(&b)),
+            Err(ColumnarError::Layout(e)) if e.contains(r#""series" table"#)
+        ));
+        let mut md = b.schema().metadata().clone();
+        md.insert(VERSION_KEY.into(), "99".into());
+        let schema = Arc::new(b.schema().as_ref().clone().with_metadata(md));
+        let b = RecordBatch::try_new(schema, b.columns().to_vec()).unwrap();
+        assert!(matches!(
+            series_from_batches(&[b]),
+            Err(ColumnarError::Layout(e)) if e.contains("version 99")
+        ));
+    }
+}
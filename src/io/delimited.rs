@@ -0,0 +1,838 @@
+//! Delimited-text (CSV / TSV) lightcurves.
+//!
+//! [`read_delimited`] maps configurable columns onto per-row vectors and
+//! keeps every data row: rows that fail to parse are listed in
+//! [`DelimitedRows::errors`] and masked out of [`DelimitedRows::keep`], so
+//! indices line up with the file. [`write_lightcurve`] and [`write_series`]
+//! emit a header row whose names can be mapped straight back in;
+//! [`write_series`] also records its time scale and frame in leading
+//! `# time_scale: TT` / `# time_frame: target` comments, which
+//! [`read_delimited`] honours.
+
+use crate::entities::lightcurve::Lightcurve;
+use crate::entities::observation::Observation;
+use crate::entities::passband::{Passband, UNTAGGED_BAND_KEY};
+use crate::entities::series::{
+    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
+};
+use crate::functions::time_scale::{
+    TimeScale, convert, jd_to_seconds, mjd_to_seconds, seconds_to_jd, seconds_to_mjd, to_utc,
+};
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use std::cell::Cell;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// RFC 3339, or `YYYY-MM-DD[T ]hh:mm:ss[.f]` read as UTC.
+    #[default]
+    Iso8601,
+    /// Seconds since 1970-01-01T00:00:00Z.
+    Unix,
+    Mjd,
+    Jd,
+}
+
+impl TimeFormat {
+    /// Seconds since 1970-01-01 in the column's time scale (unix seconds for
+    /// UTC), microsecond-quantized like
+    /// [`Observation::unix_seconds`](crate::entities::observation::Observation::unix_seconds).
+    pub fn parse(self, s: &str) -> Option<f64> {
+        let s = s.trim();
+        let t = match self {
+            TimeFormat::Iso8601 => {
+                let dt = DateTime::parse_from_rfc3339(s)
+                    .map(|d| d.with_timezone(&Utc))
+                    .ok()
+                    .or_else(|| {
+                        ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+                            .iter()
+                            .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+                            .map(|n| n.and_utc())
+                    })?;
+                return Some(dt.timestamp_micros() as f64 / 1_000_000.0);
+            }
+            TimeFormat::Unix => s.parse::<f64>().ok()?,
+            TimeFormat::Mjd => mjd_to_seconds(s.parse::<f64>().ok()?),
+            TimeFormat::Jd => jd_to_seconds(s.parse::<f64>().ok()?),
+        };
+        t.is_finite().then_some(t)
+    }
+
+    pub fn format(self, unix_s: f64) -> String {
+        match self {
+            TimeFormat::Iso8601 => unix_to_datetime(unix_s)
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Micros, true))
+                .unwrap_or_default(),
+            TimeFormat::Unix => format!("{unix_s}"),
+            TimeFormat::Mjd => format!("{}", seconds_to_mjd(unix_s)),
+            TimeFormat::Jd => format!("{}", seconds_to_jd(unix_s)),
+        }
+    }
+}
+
+/// Unit of the phase and elevation columns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AngleUnit {
+    #[default]
+    Radians,
+    Degrees,
+}
+
+/// A column by header name or by zero-based position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColumnRef {
+    Name(String),
+    Index(usize),
+}
+
+impl From<&str> for ColumnRef {
+    fn from(name: &str) -> Self {
+        ColumnRef::Name(name.to_string())
+    }
+}
+
+impl From<usize> for ColumnRef {
+    fn from(index: usize) -> Self {
+        ColumnRef::Index(index)
+    }
+}
+
+/// Column mapping for [`read_delimited`]. Optional columns left `None` are
+/// not read; a configured column missing from the header is an error.
+#[derive(Clone, Debug)]
+pub struct DelimitedConfig {
+    pub delimiter: char,
+    pub has_header: bool,
+    /// Lines starting with this character are skipped.
+    pub comment: Option<char>,
+    pub time: ColumnRef,
+    pub time_format: TimeFormat,
+    /// Scale the time column is counted in, unless the file declares one in
+    /// a leading `time_scale` comment. [`DelimitedRows::to_series`] keeps
+    /// it; [`DelimitedRows::to_lightcurve`] converts to UTC.
+    pub time_scale: TimeScale,
+    pub vismag: ColumnRef,
+    pub sigma: Option<ColumnRef>,
+    pub range: Option<ColumnRef>,
+    /// Metres per unit of the range column (`1000.0` for km).
+    pub range_scale_m: f64,
+    pub phase: Option<ColumnRef>,
+    pub elevation: Option<ColumnRef>,
+    pub angle_unit: AngleUnit,
+    /// Free-text sensor / site id, mapped to `sensor_key` in first-seen order.
+    pub sensor: Option<ColumnRef>,
+    /// Filter name, parsed with [`Passband::from_name`]; empty is untagged.
+    pub band: Option<ColumnRef>,
+}
+
+impl DelimitedConfig {
+    /// Comma-separated with a header, `timestamp` (ISO-8601) and `vismag`.
+    pub fn csv() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            comment: Some('#'),
+            time: "timestamp".into(),
+            time_format: TimeFormat::Iso8601,
+            time_scale: TimeScale::Utc,
+            vismag: "vismag".into(),
+            sigma: None,
+            range: None,
+            range_scale_m: 1.0,
+            phase: None,
+            elevation: None,
+            angle_unit: AngleUnit::Radians,
+            sensor: None,
+            band: None,
+        }
+    }
+
+    /// [`csv`](Self::csv) with tab separators.
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::csv()
+        }
+    }
+}
+
+impl Default for DelimitedConfig {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DelimitedError {
+    Io(String),
+    /// `has_header` is set but the input has no non-comment line.
+    NoHeader,
+    MissingColumn(String),
+}
+
+impl std::fmt::Display for DelimitedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DelimitedError::Io(e) => write!(f, "read failed: {e}"),
+            DelimitedError::NoHeader => write!(f, "no header line"),
+            DelimitedError::MissingColumn(c) => write!(f, "column {c:?} not in header"),
+        }
+    }
+}
+
+impl std::error::Error for DelimitedError {}
+
+/// One rejected data row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// Row index into [`DelimitedRows`].
+    pub row: usize,
+    pub message: String,
+}
+
+/// Parsed rows, one entry per data line. Values in rows with
+/// `keep[i] == false` are placeholders.
+#[derive(Clone, Debug, Default)]
+pub struct DelimitedRows {
+    /// Seconds since 1970-01-01 in [`time_scale`](Self::time_scale).
+    pub t_s: Vec<f64>,
+    pub time_scale: TimeScale,
+    /// From a leading `time_frame` comment; [`TimeFrame::Observer`] if none.
+    pub time_frame: TimeFrame,
+    pub vismag: Vec<f64>,
+    pub sigma: Option<Vec<f64>>,
+    pub range_m: Option<Vec<f64>>,
+    pub phase_rad: Option<Vec<f64>>,
+    pub elevation_rad: Option<Vec<f64>>,
+    pub sensor_key: Option<Vec<u16>>,
+    /// Sensor ids; `sensor_key` indexes this.
+    pub sensors: Vec<String>,
+    pub band: Option<Vec<Option<Passband>>>,
+    pub keep: Vec<bool>,
+    pub errors: Vec<RowError>,
+}
+
+impl DelimitedRows {
+    pub fn len(&self) -> usize {
+        self.t_s.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.t_s.is_empty()
+    }
+
+    pub fn n_kept(&self) -> usize {
+        self.keep.iter().filter(|k| **k).count()
+    }
+
+    /// Optical series with `y` = the vismag column as read (no range /
+    /// phase normalization) and `keep` carried through. Per-point σ when a
+    /// sigma column was mapped, otherwise [`SigmaSpec::Unknown`].
+    pub fn to_series(&self) -> Result<Series, SeriesError> {
+        let sigma = match &self.sigma {
+            Some(s) => SigmaSpec::PerPoint(
+                s.iter()
+                    .zip(self.keep.iter())
+                    .map(|(&v, &k)| if k { v } else { 1.0 })
+                    .collect(),
+            ),
+            None => SigmaSpec::Unknown,
+        };
+        let band_key = self.band.as_ref().and_then(|b| {
+            b.iter().any(Option::is_some).then(|| {
+                b.iter()
+                    .map(|p| p.map(Passband::key).unwrap_or(UNTAGGED_BAND_KEY))
+                    .collect()
+            })
+        });
+        Series::try_new(
+            self.t_s.clone(),
+            self.vismag.clone(),
+            sigma,
+            Covariates {
+                solar_phase_rad: self.phase_rad.clone(),
+                range_m: self.range_m.clone(),
+                elevation_rad: self.elevation_rad.clone(),
+                sensor_key: self.sensor_key.clone(),
+                band_key,
+                keep: Some(self.keep.clone()),
+                ..Covariates::default()
+            },
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: None,
+                time_scale: self.time_scale,
+                time_frame: self.time_frame,
+            },
+        )
+    }
+
+    /// Kept rows as observations normalized to `(std_range_m, std_phase_rad)`.
+    /// A missing range or phase column means "already at standard".
+    pub fn to_lightcurve(&self, std_range_m: f64, std_phase_rad: f64) -> Lightcurve {
+        let mut obs = Vec::with_capacity(self.n_kept());
+        for i in (0..self.len()).filter(|&i| self.keep[i]) {
+            let Some(ts) = unix_to_datetime(to_utc(self.t_s[i], self.time_scale)) else {
+                continue;
+            };
+            let range = self.range_m.as_ref().map(|v| v[i]).unwrap_or(std_range_m);
+            let phase = self
+                .phase_rad
+                .as_ref()
+                .map(|v| v[i])
+                .unwrap_or(std_phase_rad);
+            let mut o =
+                Observation::new(self.vismag[i], range, phase, ts, std_range_m, std_phase_rad);
+            o.elevation_rad = self.elevation_rad.as_ref().map(|v| v[i]);
+            o.band = self.band.as_ref().and_then(|v| v[i]);
+            obs.push(o);
+        }
+        Lightcurve::new(obs, None, None)
+    }
+}
+
+/// Read a delimited table. Fatal only for I/O failures and unresolvable
+/// columns; bad rows are reported in [`DelimitedRows::errors`].
+pub fn read_delimited<R: BufRead>(
+    reader: R,
+    cfg: &DelimitedConfig,
+) -> Result<DelimitedRows, DelimitedError> {
+    let scale = Cell::new(None);
+    let frame = Cell::new(None);
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .map(|(i, l)| l.map(|l| (i + 1, l)))
+        .filter(|l| match l {
+            Ok((_, s)) => {
+                let s = s.trim();
+                let Some(body) = cfg.comment.and_then(|c| s.strip_prefix(c)) else {
+                    return !s.is_empty();
+                };
+                match body.split_once(':').map(|(k, v)| (k.trim(), v.trim())) {
+                    Some(("time_scale", v)) => scale.set(TimeScale::from_name(v)),
+                    Some(("time_frame", v)) => frame.set(time_frame_from_name(v)),
+                    _ => {}
+                }
+                false
+            }
+            Err(_) => true,
+        });
+
+    let header: Option<Vec<String>> = if cfg.has_header {
+        match lines.next() {
+            Some(Ok((_, l))) => Some(split_fields(&l, cfg.delimiter)),
+            Some(Err(e)) => return Err(DelimitedError::Io(e.to_string())),
+            None => return Err(DelimitedError::NoHeader),
+        }
+    } else {
+        None
+    };
+    let resolve = |c: &ColumnRef| -> Result<usize, DelimitedError> {
+        match (c, &header) {
+            (ColumnRef::Index(i), _) => Ok(*i),
+            (ColumnRef::Name(n), Some(h)) => h
+                .iter()
+                .position(|f| f == n)
+                .ok_or_else(|| DelimitedError::MissingColumn(n.clone())),
+            (ColumnRef::Name(n), None) => Err(DelimitedError::MissingColumn(n.clone())),
+        }
+    };
+    let resolve_opt = |c: &Option<ColumnRef>| c.as_ref().map(resolve).transpose();
+    let c_time = resolve(&cfg.time)?;
+    let c_mag = resolve(&cfg.vismag)?;
+    let c_sigma = resolve_opt(&cfg.sigma)?;
+    let c_range = resolve_opt(&cfg.range)?;
+    let c_phase = resolve_opt(&cfg.phase)?;
+    let c_elev = resolve_opt(&cfg.elevation)?;
+    let c_sensor = resolve_opt(&cfg.sensor)?;
+    let c_band = resolve_opt(&cfg.band)?;
+    let angle = |v: f64| match cfg.angle_unit {
+        AngleUnit::Radians => v,
+        AngleUnit::Degrees => v.to_radians(),
+    };
+
+    let mut out = DelimitedRows {
+        sigma: c_sigma.map(|_| Vec::new()),
+        range_m: c_range.map(|_| Vec::new()),
+        phase_rad: c_phase.map(|_| Vec::new()),
+        elevation_rad: c_elev.map(|_| Vec::new()),
+        sensor_key: c_sensor.map(|_| Vec::new()),
+        band: c_band.map(|_| Vec::new()),
+        ..DelimitedRows::default()
+    };
+    // Only comments ahead of the header (or first data row) declare these.
+    out.time_scale = scale.get().unwrap_or(cfg.time_scale);
+    out.time_frame = frame.get().unwrap_or_default();
+
+    for line in lines {
+        let (line_no, text) = line.map_err(|e| DelimitedError::Io(e.to_string()))?;
+        let fields = split_fields(&text, cfg.delimiter);
+        let row = out.t_s.len();
+        let mut problems: Vec<String> = Vec::new();
+
+        let t = match cell(&fields, c_time) {
+            Some(s) => cfg.time_format.parse(s).unwrap_or_else(|| {
+                problems.push(format!("time: cannot parse {s:?}"));
+                f64::NAN
+            }),
+            None => {
+                problems.push("time: empty or missing".into());
+                f64::NAN
+            }
+        };
+        let mag = number(&fields, c_mag, "vismag", &mut problems);
+        if let (Some(c), Some(v)) = (c_sigma, out.sigma.as_mut()) {
+            let s = number(&fields, c, "sigma", &mut problems);
+            if s <= 0.0 {
+                problems.push(format!("sigma: {s} is not positive"));
+            }
+            v.push(s);
+        }
+        if let (Some(c), Some(v)) = (c_range, out.range_m.as_mut()) {
+            v.push(number(&fields, c, "range", &mut problems) * cfg.range_scale_m);
+        }
+        if let (Some(c), Some(v)) = (c_phase, out.phase_rad.as_mut()) {
+            v.push(angle(number(&fields, c, "phase", &mut problems)));
+        }
+        if let (Some(c), Some(v)) = (c_elev, out.elevation_rad.as_mut()) {
+            v.push(angle(number(&fields, c, "elevation", &mut problems)));
+        }
+        if let (Some(c), Some(v)) = (c_sensor, out.sensor_key.as_mut()) {
+            let key = match cell(&fields, c) {
+                Some(s) => {
+                    let idx = match out.sensors.iter().position(|x| x == s) {
+                        Some(i) => i,
+                        None => {
+                            out.sensors.push(s.to_string());
+                            out.sensors.len() - 1
+                        }
+                    };
+                    u16::try_from(idx).unwrap_or_else(|_| {
+                        problems.push("sensor: more than 65536 distinct ids".into());
+                        0
+                    })
+                }
+                None => {
+                    problems.push("sensor: empty or missing".into());
+                    0
+                }
+            };
+            v.push(key);
+        }
+        if let (Some(c), Some(v)) = (c_band, out.band.as_mut()) {
+            let band = cell(&fields, c).and_then(|s| {
+                let b = Passband::from_name(s);
+                if b.is_none() {
+                    problems.push(format!("band: unknown filter {s:?}"));
+                }
+                b
+            });
+            v.push(band);
+        }
+
+        out.t_s.push(t);
+        out.vismag.push(mag);
+        out.keep.push(problems.is_empty());
+        if !problems.is_empty() {
+            out.errors.push(RowError {
+                line: line_no,
+                row,
+                message: problems.join("; "),
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// [`read_delimited`] on a file.
+pub fn read_delimited_file(
+    path: impl AsRef<Path>,
+    cfg: &DelimitedConfig,
+) -> Result<DelimitedRows, DelimitedError> {
+    let f = std::fs::File::open(path).map_err(|e| DelimitedError::Io(e.to_string()))?;
+    read_delimited(std::io::BufReader::new(f), cfg)
+}
+
+/// Lightcurve columns: `timestamp, vismag, std_magnitude, range_m,
+/// phase_rad, elevation_rad, illumination, band`. Absent optionals are
+/// written as empty cells.
+pub fn write_lightcurve<W: Write>(
+    mut w: W,
+    lc: &Lightcurve,
+    delimiter: char,
+    time_format: TimeFormat,
+) -> std::io::Result<()> {
+    let d = delimiter.to_string();
+    let header = [
+        "timestamp",
+        "vismag",
+        "std_magnitude",
+        "range_m",
+        "phase_rad",
+        "elevation_rad",
+        "illumination",
+        "band",
+    ];
+    writeln!(w, "{}", header.join(&d))?;
+    for o in &lc.observations {
+        let row = [
+            time_format.format(o.unix_seconds()),
+            o.vismag.to_string(),
+            o.std_magnitude.to_string(),
+            o.range_m.to_string(),
+            o.phase_rad.to_string(),
+            opt_cell(o.elevation_rad),
+            opt_cell(o.illumination),
+            o.band.map(|b| b.name().to_string()).unwrap_or_default(),
+        ];
+        writeln!(w, "{}", row.join(&d))?;
+    }
+    Ok(())
+}
+
+/// Series columns: `timestamp, y`, then `sigma` and each covariate the
+/// series carries, under its [`Covariates`] field name. Times stay in the
+/// series' own [`time_frame`](SeriesMeta::time_frame), and in its own
+/// [`time_scale`](SeriesMeta::time_scale) except for
+/// [`TimeFormat::Iso8601`], whose `Z` timestamps are converted to UTC. Both
+/// are recorded in comment lines ahead of the header.
+pub fn write_series<W: Write>(
+    mut w: W,
+    series: &Series,
+    delimiter: char,
+    time_format: TimeFormat,
+) -> std::io::Result<()> {
+    let d = delimiter.to_string();
+    let cov = series.covariates();
+    let sigma: Option<Vec<f64>> = match series.sigma_spec() {
+        SigmaSpec::Unknown => None,
+        SigmaSpec::Homoscedastic(s) => Some(vec![*s; series.len()]),
+        SigmaSpec::PerPoint(s) => Some(s.clone()),
+    };
+    let float_cols: Vec<(&str, &[f64])> = [
+        ("sigma", sigma.as_deref()),
+        ("solar_phase_rad", cov.solar_phase_rad.as_deref()),
+        ("range_m", cov.range_m.as_deref()),
+        ("elevation_rad", cov.elevation_rad.as_deref()),
+        ("illumination", cov.illumination.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(n, v)| v.map(|v| (n, v)))
+    .collect();
+    let key_cols: Vec<(&str, &[u16])> = [
+        ("sensor_key", cov.sensor_key.as_deref()),
+        ("band_key", cov.band_key.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(n, v)| v.map(|v| (n, v)))
+    .collect();
+
+    let meta = series.meta();
+    let scale = match time_format {
+        TimeFormat::Iso8601 => TimeScale::Utc,
+        _ => meta.time_scale,
+    };
+    writeln!(w, "# time_scale: {scale}")?;
+    writeln!(w, "# time_frame: {}", time_frame_name(meta.time_frame))?;
+    let mut header = vec!["timestamp", "y"];
+    header.extend(float_cols.iter().map(|(n, _)| *n));
+    header.extend(key_cols.iter().map(|(n, _)| *n));
+    writeln!(w, "{}", header.join(&d))?;
+    for i in 0..series.len() {
+        let mut row = vec![
+            time_format.format(convert(series.t_s()[i], meta.time_scale, scale)),
+            series.y()[i].to_string(),
+        ];
+        row.extend(float_cols.iter().map(|(_, v)| opt_cell(Some(v[i]))));
+        row.extend(key_cols.iter().map(|(_, v)| v[i].to_string()));
+        writeln!(w, "{}", row.join(&d))?;
+    }
+    Ok(())
+}
+
+fn time_frame_name(frame: TimeFrame) -> &'static str {
+    match frame {
+        TimeFrame::Observer => "observer",
+        TimeFrame::Target => "target",
+    }
+}
+
+fn time_frame_from_name(name: &str) -> Option<TimeFrame> {
+    match name.to_ascii_lowercase().as_str() {
+        "observer" => Some(TimeFrame::Observer),
+        "target" => Some(TimeFrame::Target),
+        _ => None,
+    }
+}
+
+fn cell(fields: &[String], idx: usize) -> Option<&str> {
+    fields
+        .get(idx)
+        .map(String::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+/// Finite number in column `idx`, or NaN with a note in `problems`.
+fn number(fields: &[String], idx: usize, what: &str, problems: &mut Vec<String>) -> f64 {
+    match cell(fields, idx) {
+        Some(s) => match s.parse::<f64>() {
+            Ok(v) if v.is_finite() => v,
+            _ => {
+                problems.push(format!("{what}: cannot parse {s:?}"));
+                f64::NAN
+            }
+        },
+        None => {
+            problems.push(format!("{what}: empty or missing"));
+            f64::NAN
+        }
+    }
+}
+
+fn opt_cell(v: Option<f64>) -> String {
+    match v {
+        Some(x) if x.is_finite() => x.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    cur.push('"');
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            } else {
+                cur.push(c);
+            }
+        } else if c == '"' {
+            quoted = true;
+        } else if c == delimiter {
+            out.push(cur.trim().to_string());
+            cur.clear();
+        } else {
+            cur.push(c);
+        }
+    }
+    out.push(cur.trim().to_string());
+    out
+}
+
+fn unix_to_datetime(unix_s: f64) -> Option<DateTime<Utc>> {
+    if !unix_s.is_finite() {
+        return None;
+    }
+    DateTime::from_timestamp_micros((unix_s * 1_000_000.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_with_iso_times_degrees_and_bad_rows() {
+        let text = "\
+# exported by a sensor
+timestamp,mag,err,range_km,phase_deg,site,filter
+2025-01-01T00:00:00Z,10.0,0.05,1000,30,A,V
+2025-01-01 00:00:10.5,10.2,0.05,1000,30,B,r'
+2025-01-01T00:00:20Z,,0.05,1000,30,A,V
+2025-01-01T00:00:30Z,10.4,0,1000,30,A,Q
+not-a-time,10.5,0.05,1000,30,B,
+2025-01-01T00:00:50Z,10.6,0.05,1000,30,B,
+";
+        let cfg = DelimitedConfig {
+            vismag: "mag".into(),
+            sigma: Some("err".into()),
+            range: Some("range_km".into()),
+            range_scale_m: 1000.0,
+            phase: Some("phase_deg".into()),
+            angle_unit: AngleUnit::Degrees,
+            sensor: Some("site".into()),
+            band: Some("filter".into()),
+            ..DelimitedConfig::csv()
+        };
+        let rows = read_delimited(text.as_bytes(), &cfg).unwrap();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows.keep, vec![true, true, false, false, false, true]);
+        assert_eq!(
+            rows.errors
+                .iter()
+                .map(|e| (e.line, e.row))
+                .collect::<Vec<_>>(),
+            vec![(5, 2), (6, 3), (7, 4)]
+        );
+        assert!(rows.errors[1].message.contains("sigma"));
+        assert!(rows.errors[1].message.contains("band"));
+        assert!((rows.t_s[1] - (1_735_689_600.0 + 10.5)).abs() < 1e-6);
+        assert_eq!(rows.range_m.as_ref().unwrap()[0], 1.0e6);
+        assert!((rows.phase_rad.as_ref().unwrap()[0] - 30f64.to_radians()).abs() < 1e-15);
+        assert_eq!(rows.sensors, vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(rows.sensor_key.as_deref(), Some(&[0, 1, 0, 0, 1, 1][..]));
+        assert_eq!(rows.band.as_ref().unwrap()[1], Some(Passband::SloanR));
+
+        let s = rows.to_series().unwrap();
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.y(), &[10.0, 10.2, 10.6]);
+        assert_eq!(
+            s.covariates().band_key.as_deref(),
+            Some(&[2, 6, UNTAGGED_BAND_KEY][..])
+        );
+        assert_eq!(s.covariates().sensor_key.as_deref(), Some(&[0, 1, 1][..]));
+    }
+
+    #[test]
+    fn headerless_tsv_with_mjd_and_index_columns() {
+        let text = "60676.0\t12.5\n60676.5\t12.7\n";
+        let cfg = DelimitedConfig {
+            has_header: false,
+            time: 0.into(),
+            time_format: TimeFormat::Mjd,
+            vismag: 1.into(),
+            ..DelimitedConfig::tsv()
+        };
+        let rows = read_delimited(text.as_bytes(), &cfg).unwrap();
+        assert!(rows.errors.is_empty());
+        assert_eq!(rows.t_s, vec![1_735_689_600.0, 1_735_732_800.0]);
+        let s = rows.to_series().unwrap();
+        assert!(s.sigma_spec().is_unknown());
+
+        let named = DelimitedConfig {
+            has_header: false,
+            ..DelimitedConfig::tsv()
+        };
+        assert_eq!(
+            read_delimited(text.as_bytes(), &named).unwrap_err(),
+            DelimitedError::MissingColumn("timestamp".into())
+        );
+        assert_eq!(
+            read_delimited("# only a comment\n".as_bytes(), &DelimitedConfig::csv()).unwrap_err(),
+            DelimitedError::NoHeader
+        );
+    }
+
+    #[test]
+    fn lightcurve_round_trips_through_csv() {
+        let t0 = DateTime::parse_from_rfc3339("2025-01-01T00:00:00.250Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let obs: Vec<Observation> = (0..3)
+            .map(|i| {
+                let mut o = Observation::new(
+                    10.0 + 0.1 * i as f64,
+                    2.0e6,
+                    0.4,
+                    t0 + chrono::Duration::seconds(i),
+                    1.0e6,
+                    0.4,
+                );
+                o.elevation_rad = Some(0.7);
+                if i > 0 {
+                    o = o.with_band(Passband::JohnsonV);
+                }
+                o
+            })
+            .collect();
+        let lc = Lightcurve::new(obs, None, None);
+        let mut buf = Vec::new();
+        write_lightcurve(&mut buf, &lc, ',', TimeFormat::Iso8601).unwrap();
+
+        let cfg = DelimitedConfig {
+            range: Some("range_m".into()),
+            phase: Some("phase_rad".into()),
+            elevation: Some("elevation_rad".into()),
+            band: Some("band".into()),
+            ..DelimitedConfig::csv()
+        };
+        let rows = read_delimited(buf.as_slice(), &cfg).unwrap();
+        assert!(rows.errors.is_empty(), "{:?}", rows.errors);
+        let back = rows.to_lightcurve(1.0e6, 0.4);
+        for (a, b) in lc.observations.iter().zip(&back.observations) {
+            assert_eq!(a.timestamp, b.timestamp);
+            assert_eq!(a.vismag, b.vismag);
+            assert!((a.std_magnitude - b.std_magnitude).abs() < 1e-12);
+            assert_eq!(a.elevation_rad, b.elevation_rad);
+            assert_eq!(a.band, b.band);
+        }
+    }
+
+    #[test]
+    fn series_writer_emits_present_columns_only() {
+        let text = "t,y,s,sensor\n100,1.0,0.1,x\n200,2.0,0.2,y\n";
+        let cfg = DelimitedConfig {
+            time: "t".into(),
+            time_format: TimeFormat::Unix,
+            vismag: "y".into(),
+            sigma: Some("s".into()),
+            sensor: Some("sensor".into()),
+            ..DelimitedConfig::csv()
+        };
+        let s = read_delimited(text.as_bytes(), &cfg)
+            .unwrap()
+            .to_series()
+            .unwrap();
+        let mut buf = Vec::new();
+        write_series(&mut buf, &s, '\t', TimeFormat::Unix).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "# time_scale: UTC\n# time_frame: observer\n\
+             timestamp\ty\tsigma\tsensor_key\n100\t1\t0.1\t0\n200\t2\t0.2\t1\n"
+        );
+    }
+
+    #[test]
+    fn series_scale_survives_a_round_trip() {
+        let text = "t,y\n1700000000,1.0\n1700000100,2.0\n";
+        let cfg = DelimitedConfig {
+            time: "t".into(),
+            time_format: TimeFormat::Unix,
+            vismag: "y".into(),
+            ..DelimitedConfig::csv()
+        };
+        let utc = read_delimited(text.as_bytes(), &cfg)
+            .unwrap()
+            .to_series()
+            .unwrap();
+        let tt = utc.to_time_scale(TimeScale::Tt).unwrap();
+        let back = |format: TimeFormat| {
+            let mut buf = Vec::new();
+            write_series(&mut buf, &tt, ',', format).unwrap();
+            let cfg = DelimitedConfig {
+                time: "timestamp".into(),
+                time_format: format,
+                vismag: "y".into(),
+                ..DelimitedConfig::csv()
+            };
+            read_delimited(buf.as_slice(), &cfg)
+                .unwrap()
+                .to_series()
+                .unwrap()
+        };
+
+        let unix = back(TimeFormat::Unix);
+        assert_eq!(unix.meta().time_scale, TimeScale::Tt);
+        assert_eq!(unix.meta().time_frame, TimeFrame::Observer);
+        assert_eq!(unix.t_s(), tt.t_s());
+
+        // ISO timestamps are UTC whatever the series scale.
+        let iso = back(TimeFormat::Iso8601);
+        assert_eq!(iso.meta().time_scale, TimeScale::Utc);
+        for (a, b) in iso.t_s().iter().zip(utc.t_s()) {
+            assert!((a - b).abs() < 1e-5, "{a} vs {b}");
+        }
+    }
+}
@@ -0,0 +1,779 @@
+//! FITS binary-table photometry.
+//!
+//! A small pure-Rust reader for the `BINTABLE` extensions that reduction
+//! pipelines emit: header cards are parsed, the first matching table is
+//! located, and scalar columns are read big-endian with `TSCALn` /
+//! `TZEROn` / `TNULLn` applied. Times are taken from a `TIME` column
+//! (relative to `MJDREF[I/F]` or `JDREF`, in `TIMEUNIT`) or from an absolute
+//! `MJD` / `JD` column, in the scale named by `TIMESYS`, and kept in that
+//! scale as scale seconds (see [`time_scale`](crate::functions::time_scale)).
+//! Only [`FitsPhotometry::to_lightcurve`] converts to UTC, since observation
+//! timestamps are UTC.
+
+use crate::entities::lightcurve::Lightcurve;
+use crate::entities::observation::Observation;
+use crate::entities::passband::{Passband, UNTAGGED_BAND_KEY};
+use crate::entities::series::{
+    Covariates, Modality, Series, SeriesError, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
+};
+use crate::functions::time_scale::{JD_MINUS_MJD, MJD_UNIX_EPOCH, TimeScale, to_utc};
+use chrono::{DateTime, Utc};
+use std::io::Read;
+use std::path::Path;
+
+const BLOCK: usize = 2880;
+const CARD: usize = 80;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FitsError {
+    Io(String),
+    /// Not FITS, truncated, or a malformed header card.
+    Format(String),
+    /// No `BINTABLE` extension (with the requested `EXTNAME`).
+    NoTable,
+    MissingColumn(String),
+    /// Column is an array, complex, bit or variable-length field.
+    UnsupportedColumn {
+        name: String,
+        tform: String,
+    },
+    UnsupportedTimeSys(String),
+}
+
+impl std::fmt::Display for FitsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FitsError::Io(e) => write!(f, "read failed: {e}"),
+            FitsError::Format(e) => write!(f, "malformed FITS: {e}"),
+            FitsError::NoTable => write!(f, "no BINTABLE extension"),
+            FitsError::MissingColumn(c) => write!(f, "column {c:?} not in table"),
+            FitsError::UnsupportedColumn { name, tform } => {
+                write!(f, "column {name:?} has unsupported TFORM {tform:?}")
+            }
+            FitsError::UnsupportedTimeSys(s) => write!(f, "TIMESYS {s:?} is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for FitsError {}
+
+/// Header keywords and their values, string values unquoted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FitsHeader {
+    pub cards: Vec<(String, String)>,
+}
+
+impl FitsHeader {
+    pub fn get(&self, keyword: &str) -> Option<&str> {
+        self.cards
+            .iter()
+            .find(|(k, _)| k == keyword)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn get_f64(&self, keyword: &str) -> Option<f64> {
+        self.get(keyword)?.replace(['D', 'd'], "E").parse().ok()
+    }
+
+    pub fn get_i64(&self, keyword: &str) -> Option<i64> {
+        self.get(keyword)?.parse().ok()
+    }
+}
+
+/// Which column carries time, and how to read it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimeColumn {
+    /// `TIME` (relative), else `MJD`, else `JD`.
+    #[default]
+    Auto,
+    /// Offset from `MJDREF` in `TUNITn` / `TIMEUNIT` (seconds by default).
+    Relative(String),
+    Mjd(String),
+    Jd(String),
+}
+
+/// Column names for [`read_fits`]. `magerr` and `filter` are skipped when
+/// the table lacks them; without a filter column the `FILTER` header
+/// keyword, if any, tags every row.
+#[derive(Clone, Debug)]
+pub struct FitsConfig {
+    /// Read the `BINTABLE` with this `EXTNAME`; `None` takes the first.
+    pub extname: Option<String>,
+    pub time: TimeColumn,
+    pub mag: String,
+    pub magerr: Option<String>,
+    pub filter: Option<String>,
+}
+
+impl Default for FitsConfig {
+    fn default() -> Self {
+        Self {
+            extname: None,
+            time: TimeColumn::Auto,
+            mag: "MAG".into(),
+            magerr: Some("MAGERR".into()),
+            filter: Some("FILTER".into()),
+        }
+    }
+}
+
+/// Table rows and header metadata. Rows with a null / non-finite time or
+/// magnitude, a non-positive error or an unknown filter are kept in the
+/// vectors with `keep[i] == false`.
+#[derive(Clone, Debug, Default)]
+pub struct FitsPhotometry {
+    pub primary: FitsHeader,
+    pub table: FitsHeader,
+    pub time_scale: TimeScale,
+    /// Scale seconds in [`time_scale`](Self::time_scale).
+    pub t_s: Vec<f64>,
+    pub vismag: Vec<f64>,
+    pub sigma: Option<Vec<f64>>,
+    pub band: Option<Vec<Option<Passband>>>,
+    pub keep: Vec<bool>,
+    pub object: Option<String>,
+    pub observatory: Option<String>,
+    pub telescope: Option<String>,
+    pub exptime_s: Option<f64>,
+}
+
+impl FitsPhotometry {
+    pub fn len(&self) -> usize {
+        self.t_s.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.t_s.is_empty()
+    }
+
+    pub fn n_kept(&self) -> usize {
+        self.keep.iter().filter(|k| **k).count()
+    }
+
+    /// `OBJECT / OBSERVAT / TELESCOP / exp EXPTIME s`, from whichever are set.
+    pub fn label(&self) -> Option<String> {
+        let mut parts: Vec<String> = [&self.object, &self.observatory, &self.telescope]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+        if let Some(e) = self.exptime_s {
+            parts.push(format!("exp {e} s"));
+        }
+        (!parts.is_empty()).then(|| parts.join(" / "))
+    }
+
+    /// Optical series in the file's time scale, `y` = magnitude as read,
+    /// [`label`](Self::label) as the series label.
+    pub fn to_series(&self) -> Result<Series, SeriesError> {
+        let sigma = match &self.sigma {
+            Some(s) => SigmaSpec::PerPoint(
+                s.iter()
+                    .zip(&self.keep)
+                    .map(|(&v, &k)| if k { v } else { 1.0 })
+                    .collect(),
+            ),
+            None => SigmaSpec::Unknown,
+        };
+        let band_key = self.band.as_ref().and_then(|b| {
+            b.iter().any(Option::is_some).then(|| {
+                b.iter()
+                    .map(|p| p.map(Passband::key).unwrap_or(UNTAGGED_BAND_KEY))
+                    .collect()
+            })
+        });
+        Series::try_new(
+            self.t_s.clone(),
+            self.vismag.clone(),
+            sigma,
+            Covariates {
+                band_key,
+                keep: Some(self.keep.clone()),
+                ..Covariates::default()
+            },
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: self.label(),
+                time_scale: self.time_scale,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+    }
+
+    /// Kept rows as observations already at `(std_range_m, std_phase_rad)`,
+    /// with times converted to UTC.
+    pub fn to_lightcurve(&self, std_range_m: f64, std_phase_rad: f64) -> Lightcurve {
+        let mut obs = Vec::with_capacity(self.n_kept());
+        for i in (0..self.len()).filter(|&i| self.keep[i]) {
+            let utc = to_utc(self.t_s[i], self.time_scale);
+            let Some(ts) =
+                DateTime::<Utc>::from_timestamp_micros((utc * 1_000_000.0).round() as i64)
+            else {
+                continue;
+            };
+            let mut o = Observation::new(
+                self.vismag[i],
+                std_range_m,
+                std_phase_rad,
+                ts,
+                std_range_m,
+                std_phase_rad,
+            );
+            o.band = self.band.as_ref().and_then(|v| v[i]);
+            obs.push(o);
+        }
+        Lightcurve::new(obs, None, None)
+    }
+}
+
+/// Read photometry from the first (or the named) `BINTABLE` in a FITS file.
+#[allow(clippy::collapsible_if)]
+pub fn read_fits<R: Read>(mut reader: R, cfg: &FitsConfig) -> Result<FitsPhotometry, FitsError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| FitsError::Io(e.to_string()))?;
+
+    let (primary, mut offset) = parse_header(&bytes, 0)?;
+    if primary.get("SIMPLE") != Some("T") {
+        return Err(FitsError::Format("first card is not SIMPLE = T".into()));
+    }
+    offset = advance(offset, data_len(&primary)?)?;
+    let (table, data_start) = loop {
+        if offset >= bytes.len() {
+            return Err(FitsError::NoTable);
+        }
+        let (h, start) = parse_header(&bytes, offset)?;
+        let is_table = h.get("XTENSION") == Some("BINTABLE")
+            && cfg
+                .extname
+                .as_deref()
+                .is_none_or(|n| h.get("EXTNAME").is_some_and(|e| e.eq_ignore_ascii_case(n)));
+        if is_table {
+            break (h, start);
+        }
+        offset = advance(start, data_len(&h)?)?;
+    };
+
+    let cols = parse_columns(&table)?;
+    let row_len = axis(&table, "NAXIS1")?;
+    let n_rows = axis(&table, "NAXIS2")?;
+    let data_end = row_len
+        .checked_mul(n_rows)
+        .and_then(|n| n.checked_add(data_start))
+        .ok_or_else(too_large)?;
+    if data_end > bytes.len() {
+        return Err(FitsError::Format("table data truncated".into()));
+    }
+    let data = &bytes[data_start..data_end];
+    let row = |i: usize| &data[i * row_len..(i + 1) * row_len];
+    let find = |name: &str| cols.iter().find(|c| c.name.eq_ignore_ascii_case(name));
+    let lookup = |name: &str| find(name).ok_or_else(|| FitsError::MissingColumn(name.into()));
+    let kw = |k: &str| table.get(k).or_else(|| primary.get(k));
+    let kw_f64 = |k: &str| table.get_f64(k).or_else(|| primary.get_f64(k));
+
+    let time_scale = match kw("TIMESYS") {
+        Some(s) => {
+            TimeScale::from_name(s).ok_or_else(|| FitsError::UnsupportedTimeSys(s.into()))?
+        }
+        None => TimeScale::Utc,
+    };
+    let time = match &cfg.time {
+        TimeColumn::Auto => ["TIME", "MJD", "JD"]
+            .into_iter()
+            .find(|n| find(n).is_some())
+            .map(|n| match n {
+                "TIME" => TimeColumn::Relative(n.into()),
+                "MJD" => TimeColumn::Mjd(n.into()),
+                _ => TimeColumn::Jd(n.into()),
+            })
+            .ok_or_else(|| FitsError::MissingColumn("TIME".into()))?,
+        other => other.clone(),
+    };
+    // Every time reading becomes (MJD, seconds past it) in `time_scale`.
+    let (t_col, mjd0, scale_s) = match &time {
+        TimeColumn::Relative(n) => {
+            let c = lookup(n)?;
+            let mjd_ref = match (kw_f64("MJDREFI"), kw_f64("MJDREF"), kw_f64("JDREF")) {
+                (Some(i), _, _) => i + kw_f64("MJDREFF").unwrap_or(0.0),
+                (None, Some(m), _) => m,
+                (None, None, Some(j)) => j - JD_MINUS_MJD,
+                (None, None, None) => {
+                    return Err(FitsError::Format(format!(
+                        "relative time column {n:?} without MJDREF or JDREF"
+                    )));
+                }
+            };
+            let unit = c.unit.as_deref().or_else(|| kw("TIMEUNIT")).unwrap_or("s");
+            let scale_s = time_unit_s(unit)
+                .ok_or_else(|| FitsError::Format(format!("unknown time unit {unit:?}")))?;
+            let zero = kw_f64("TIMEZERO").unwrap_or(0.0) * scale_s;
+            (c, mjd_ref + zero / 86_400.0, scale_s)
+        }
+        TimeColumn::Mjd(n) => (lookup(n)?, 0.0, 86_400.0),
+        TimeColumn::Jd(n) => (lookup(n)?, -JD_MINUS_MJD, 86_400.0),
+        TimeColumn::Auto => unreachable!("resolved above"),
+    };
+    let mag_col = lookup(&cfg.mag)?;
+    let err_col = cfg.magerr.as_deref().and_then(find);
+    let filter_col = cfg.filter.as_deref().and_then(find);
+    for c in [Some(t_col), Some(mag_col), err_col].into_iter().flatten() {
+        c.check_numeric()?;
+    }
+    if let Some(c) = filter_col {
+        if c.kind != b'A' {
+            return Err(FitsError::UnsupportedColumn {
+                name: c.name.clone(),
+                tform: c.tform.clone(),
+            });
+        }
+    }
+    // A single-filter frame names its filter in the header; an unknown
+    // name there leaves the rows untagged rather than rejecting them all.
+    let header_band = match filter_col {
+        None => kw("FILTER").and_then(Passband::from_name),
+        Some(_) => None,
+    };
+
+    let mut out = FitsPhotometry {
+        time_scale,
+        sigma: err_col.map(|_| Vec::with_capacity(n_rows)),
+        band: (filter_col.is_some() || header_band.is_some()).then(|| Vec::with_capacity(n_rows)),
+        object: kw("OBJECT").map(str::to_string),
+        observatory: kw("OBSERVAT").map(str::to_string),
+        telescope: kw("TELESCOP").map(str::to_string),
+        exptime_s: kw_f64("EXPTIME"),
+        ..FitsPhotometry::default()
+    };
+    for i in 0..n_rows {
+        let r = row(i);
+        let raw_t = t_col.number(r);
+        let t = (mjd0 - MJD_UNIX_EPOCH) * 86_400.0 + raw_t * scale_s;
+        let mag = mag_col.number(r);
+        let mut ok = t.is_finite() && mag.is_finite();
+        if let (Some(c), Some(v)) = (err_col, out.sigma.as_mut()) {
+            let e = c.number(r);
+            ok &= e.is_finite() && e > 0.0;
+            v.push(e);
+        }
+        if let Some(v) = out.band.as_mut() {
+            let band = match filter_col {
+                Some(c) => {
+                    let name = c.text(r);
+                    let b = Passband::from_name(&name);
+                    ok &= name.is_empty() || b.is_some();
+                    b
+                }
+                None => header_band,
+            };
+            v.push(band);
+        }
+        out.t_s.push(t);
+        out.vismag.push(mag);
+        out.keep.push(ok);
+    }
+    out.primary = primary;
+    out.table = table;
+    Ok(out)
+}
+
+/// [`read_fits`] on a file.
+pub fn read_fits_file(
+    path: impl AsRef<Path>,
+    cfg: &FitsConfig,
+) -> Result<FitsPhotometry, FitsError> {
+    let f = std::fs::File::open(path).map_err(|e| FitsError::Io(e.to_string()))?;
+    read_fits(std::io::BufReader::new(f), cfg)
+}
+
+/// One `BINTABLE` field.
+struct Column {
+    name: String,
+    tform: String,
+    kind: u8,
+    repeat: usize,
+    offset: usize,
+    scale: f64,
+    zero: f64,
+    null: Option<i64>,
+    unit: Option<String>,
+}
+
+impl Column {
+    fn check_numeric(&self) -> Result<(), FitsError> {
+        if self.repeat == 1 && matches!(self.kind, b'B' | b'I' | b'J' | b'K' | b'E' | b'D') {
+            Ok(())
+        } else {
+            Err(FitsError::UnsupportedColumn {
+                name: self.name.clone(),
+                tform: self.tform.clone(),
+            })
+        }
+    }
+
+    /// Scaled value; NaN for `TNULLn` or a non-numeric field.
+    fn number(&self, row: &[u8]) -> f64 {
+        let b = &row[self.offset..];
+        let int = |v: i64| {
+            if self.null == Some(v) {
+                f64::NAN
+            } else {
+                v as f64
+            }
+        };
+        let raw = match self.kind {
+            b'B' => int(b[0] as i64),
+            b'I' => int(i16::from_be_bytes([b[0], b[1]]) as i64),
+            b'J' => int(i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as i64),
+            b'K' => int(i64::from_be_bytes(b[..8].try_into().expect("8 bytes"))),
+            b'E' => f32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            b'D' => f64::from_be_bytes(b[..8].try_into().expect("8 bytes")),
+            _ => f64::NAN,
+        };
+        self.zero + self.scale * raw
+    }
+
+    fn text(&self, row: &[u8]) -> String {
+        let b = &row[self.offset..self.offset + self.repeat];
+        let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+        String::from_utf8_lossy(&b[..end]).trim().to_string()
+    }
+}
+
+/// Columns in row order. Each must fit inside the `NAXIS1`-byte row.
+fn parse_columns(h: &FitsHeader) -> Result<Vec<Column>, FitsError> {
+    let n = h.get_i64("TFIELDS").unwrap_or(0);
+    let row_len = axis(h, "NAXIS1")?;
+    let mut out = Vec::new();
+    let mut offset = 0usize;
+    for k in 1..=n {
+        let tform = h
+            .get(&format!("TFORM{k}"))
+            .ok_or_else(|| FitsError::Format(format!("TFORM{k} missing")))?
+            .trim()
+            .to_string();
+        let digits = tform.bytes().take_while(u8::is_ascii_digit).count();
+        let repeat: usize = if digits == 0 {
+            1
+        } else {
+            tform[..digits]
+                .parse()
+                .map_err(|_| FitsError::Format(format!("bad TFORM{k} {tform:?}")))?
+        };
+        let kind = *tform
+            .as_bytes()
+            .get(digits)
+            .ok_or_else(|| FitsError::Format(format!("bad TFORM{k} {tform:?}")))?;
+        let width = match kind {
+            b'L' | b'B' | b'A' => repeat,
+            b'X' => repeat.div_ceil(8),
+            b'I' => repeat.saturating_mul(2),
+            b'J' | b'E' => repeat.saturating_mul(4),
+            b'K' | b'D' | b'C' | b'P' => repeat.saturating_mul(8),
+            b'M' | b'Q' => repeat.saturating_mul(16),
+            _ => return Err(FitsError::Format(format!("bad TFORM{k} {tform:?}"))),
+        };
+        if offset.saturating_add(width) > row_len {
+            return Err(FitsError::Format(format!(
+                "TFORM{k} {tform:?} ends past NAXIS1 = {row_len}"
+            )));
+        }
+        out.push(Column {
+            name: h
+                .get(&format!("TTYPE{k}"))
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            tform,
+            kind,
+            repeat,
+            offset,
+            scale: h.get_f64(&format!("TSCAL{k}")).unwrap_or(1.0),
+            zero: h.get_f64(&format!("TZERO{k}")).unwrap_or(0.0),
+            null: h.get_i64(&format!("TNULL{k}")),
+            unit: h.get(&format!("TUNIT{k}")).map(str::to_string),
+        });
+        offset += width;
+    }
+    Ok(out)
+}
+
+/// Header starting at `offset`; returns it and the offset of its data.
+fn parse_header(bytes: &[u8], offset: usize) -> Result<(FitsHeader, usize), FitsError> {
+    let mut h = FitsHeader::default();
+    let mut pos = offset;
+    loop {
+        let card = bytes
+            .get(pos..pos + CARD)
+            .ok_or_else(|| FitsError::Format("header ends without END".into()))?;
+        pos += CARD;
+        if !card.is_ascii() {
+            return Err(FitsError::Format("non-ASCII header card".into()));
+        }
+        let card = std::str::from_utf8(card).expect("ASCII");
+        let keyword = card[..8].trim_end();
+        if keyword == "END" {
+            break;
+        }
+        if &card[8..10] != "= " {
+            continue;
+        }
+        h.cards.push((keyword.to_string(), card_value(&card[10..])));
+    }
+    Ok((h, advance(offset, pos - offset)?))
+}
+
+/// Value part of a card: quoted string (with `''` escapes, trailing blanks
+/// dropped) or the text before any `/` comment.
+fn card_value(s: &str) -> String {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('\'') {
+        let mut out = String::new();
+        let mut chars = rest.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    out.push('\'');
+                    chars.next();
+                } else {
+                    break;
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out.trim_end().to_string()
+    } else {
+        s.split('/').next().unwrap_or_default().trim().to_string()
+    }
+}
+
+/// Bytes of data (heap included) following a header, before padding.
+fn data_len(h: &FitsHeader) -> Result<usize, FitsError> {
+    let bitpix = h
+        .get_i64("BITPIX")
+        .ok_or_else(|| FitsError::Format("BITPIX missing".into()))?;
+    let naxis = h.get_i64("NAXIS").unwrap_or(0);
+    if naxis == 0 {
+        return Ok(0);
+    }
+    let mut n: usize = 1;
+    for k in 1..=naxis {
+        n = n
+            .checked_mul(axis(h, &format!("NAXIS{k}"))?)
+            .ok_or_else(too_large)?;
+    }
+    let pcount = size(h, "PCOUNT", 0)?;
+    let gcount = size(h, "GCOUNT", 1)?;
+    (bitpix.unsigned_abs() as usize / 8)
+        .checked_mul(gcount)
+        .and_then(|b| b.checked_mul(pcount.checked_add(n)?))
+        .ok_or_else(too_large)
+}
+
+/// `NAXISk` as a byte or row count; absent is 0.
+fn axis(h: &FitsHeader, key: &str) -> Result<usize, FitsError> {
+    size(h, key, 0)
+}
+
+/// Non-negative integer keyword, `default` when absent.
+fn size(h: &FitsHeader, key: &str, default: usize) -> Result<usize, FitsError> {
+    match h.get_i64(key) {
+        None => Ok(default),
+        Some(v) => usize::try_from(v)
+            .map_err(|_| FitsError::Format(format!("{key} = {v} is not a valid size"))),
+    }
+}
+
+fn too_large() -> FitsError {
+    FitsError::Format("HDU size overflows".into())
+}
+
+fn padded(n: usize) -> Result<usize, FitsError> {
+    n.div_ceil(BLOCK).checked_mul(BLOCK).ok_or_else(too_large)
+}
+
+/// `offset` past `len` bytes of data and their padding.
+fn advance(offset: usize, len: usize) -> Result<usize, FitsError> {
+    offset.checked_add(padded(len)?).ok_or_else(too_large)
+}
+
+fn time_unit_s(unit: &str) -> Option<f64> {
+    match unit.trim() {
+        "s" => Some(1.0),
+        "min" => Some(60.0),
+        "h" => Some(3600.0),
+        "d" => Some(86_400.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(cards: &[(&str, &str)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (k, v) in cards.iter().chain([("END", "")].iter()) {
+            let card = if *k == "END" {
+                "END".to_string()
+            } else {
+                format!("{k:<8}= {v:>20}")
+            };
+            out.extend(format!("{card:<80}").bytes());
+        }
+        out.resize(padded(out.len()).unwrap(), b' ');
+        out
+    }
+
+    /// Primary HDU + one BINTABLE: TIME (D), MAG (E), MAGERR (E), FILTER (4A).
+    fn file(table_cards: &[(&str, &str)], rows: &[(f64, f32, f32, &str)]) -> Vec<u8> {
+        let mut out = header(&[
+            ("SIMPLE", "T"),
+            ("BITPIX", "8"),
+            ("NAXIS", "0"),
+            ("OBSERVAT", "'Lowell  '"),
+            ("TELESCOP", "'LDT'"),
+        ]);
+        let n2 = rows.len().to_string();
+        let mut cards = vec![
+            ("XTENSION", "'BINTABLE'"),
+            ("BITPIX", "8"),
+            ("NAXIS", "2"),
+            ("NAXIS1", "20"),
+            ("NAXIS2", n2.as_str()),
+            ("PCOUNT", "0"),
+            ("GCOUNT", "1"),
+            ("TFIELDS", "4"),
+            ("TTYPE1", "'TIME'"),
+            ("TFORM1", "'D'"),
+            ("TTYPE2", "'MAG'"),
+            ("TFORM2", "'E'"),
+            ("TTYPE3", "'MAGERR'"),
+            ("TFORM3", "'E'"),
+            ("TTYPE4", "'FILTER'"),
+            ("TFORM4", "'4A'"),
+        ];
+        cards.extend_from_slice(table_cards);
+        out.extend(header(&cards));
+        let start = out.len();
+        for (t, m, e, f) in rows {
+            out.extend(t.to_be_bytes());
+            out.extend(m.to_be_bytes());
+            out.extend(e.to_be_bytes());
+            out.extend(format!("{f:<4}").bytes());
+        }
+        out.resize(start + padded(out.len() - start).unwrap(), 0);
+        out
+    }
+
+    #[test]
+    fn bintable_in_tt_days_keeps_its_scale_until_the_lightcurve() {
+        // MJD 60676 = 2025-01-01T00:00:00 (in TT here).
+        let bytes = file(
+            &[
+                ("TIMESYS", "'TT'"),
+                ("MJDREFI", "60676"),
+                ("MJDREFF", "0.0"),
+                ("TIMEUNIT", "'d'"),
+                ("EXPTIME", "10.0"),
+                ("OBJECT", "'43013'"),
+            ],
+            &[
+                (0.0, 12.0, 0.05, "V"),
+                (0.5, 12.3, 0.05, "r'"),
+                (1.0, f32::NAN, 0.05, "V"),
+                (1.5, 12.5, 0.0, "V"),
+                (2.0, 12.6, 0.05, "XX"),
+                (2.5, 12.7, 0.05, ""),
+            ],
+        );
+        let p = read_fits(bytes.as_slice(), &FitsConfig::default()).unwrap();
+        assert_eq!(p.time_scale, TimeScale::Tt);
+        assert_eq!(p.len(), 6);
+        assert_eq!(p.t_s[0], 1_735_689_600.0);
+        assert_eq!(p.t_s[1], 1_735_689_600.0 + 43_200.0);
+        assert_eq!(p.keep, vec![true, true, false, false, false, true]);
+        assert_eq!(p.band.as_ref().unwrap()[1], Some(Passband::SloanR));
+        assert_eq!(p.observatory.as_deref(), Some("Lowell"));
+        assert_eq!(p.exptime_s, Some(10.0));
+
+        let s = p.to_series().unwrap();
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.meta().time_scale, TimeScale::Tt);
+        assert_eq!(
+            s.meta().label.as_deref(),
+            Some("43013 / Lowell / LDT / exp 10 s")
+        );
+        assert_eq!(s.covariates().band_key.as_deref(), Some(&[2, 6, 0][..]));
+        assert!(matches!(s.sigma_spec(), SigmaSpec::PerPoint(v) if v[0] == 0.05f32 as f64));
+        let lc = p.to_lightcurve(1.0e6, 0.0);
+        assert_eq!(lc.observation_count(), 3);
+        let utc0 = 1_735_689_600.0 - 69.184;
+        let t0 = lc.observations[0].timestamp.timestamp_micros() as f64 / 1e6;
+        assert!((t0 - utc0).abs() < 1e-6, "{}", t0 - utc0);
+    }
+
+    #[test]
+    fn mjd_column_header_filter_and_errors() {
+        let bytes = file(&[("FILTER", "'B'")], &[(60676.25, 11.0, 0.1, "")]);
+        let cfg = FitsConfig {
+            time: TimeColumn::Mjd("TIME".into()),
+            filter: None,
+            ..FitsConfig::default()
+        };
+        let p = read_fits(bytes.as_slice(), &cfg).unwrap();
+        assert_eq!(p.time_scale, TimeScale::Utc);
+        assert_eq!(p.t_s, vec![1_735_689_600.0 + 21_600.0]);
+        assert_eq!(p.band, Some(vec![Some(Passband::JohnsonB)]));
+
+        assert_eq!(
+            read_fits(bytes.as_slice(), &FitsConfig::default()).unwrap_err(),
+            FitsError::Format("relative time column \"TIME\" without MJDREF or JDREF".into())
+        );
+        let gps = file(&[("TIMESYS", "'GPS'")], &[]);
+        assert_eq!(
+            read_fits(gps.as_slice(), &cfg).unwrap_err(),
+            FitsError::UnsupportedTimeSys("GPS".into())
+        );
+        let named = FitsConfig {
+            extname: Some("PHOT".into()),
+            ..cfg
+        };
+        assert_eq!(
+            read_fits(bytes.as_slice(), &named).unwrap_err(),
+            FitsError::NoTable
+        );
+    }
+
+    #[test]
+    fn column_past_the_row_is_a_format_error() {
+        let mut bytes = file(&[], &[(0.0, 12.0, 0.05, "V")]);
+        let at = bytes.windows(4).position(|w| w == b"'4A'").unwrap();
+        bytes[at + 1] = b'5';
+        assert_eq!(
+            read_fits(bytes.as_slice(), &FitsConfig::default()).unwrap_err(),
+            FitsError::Format("TFORM4 \"5A\" ends past NAXIS1 = 20".into())
+        );
+    }
+
+    #[test]
+    fn negative_or_oversized_naxis2_is_a_format_error() {
+        let bytes = file(&[], &[(0.0, 12.0, 0.05, "V")]);
+        let at = bytes.windows(8).position(|w| w == b"NAXIS2  ").unwrap();
+        let with_naxis2 = |v: &str| {
+            let mut b = bytes.clone();
+            b[at + 10..at + 30].copy_from_slice(format!("{v:>20}").as_bytes());
+            read_fits(b.as_slice(), &FitsConfig::default()).unwrap_err()
+        };
+        assert_eq!(
+            with_naxis2("-1"),
+            FitsError::Format("NAXIS2 = -1 is not a valid size".into())
+        );
+        assert_eq!(
+            with_naxis2("9223372036854775807"),
+            FitsError::Format("HDU size overflows".into())
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Readers and writers for on-disk lightcurve formats.
+
+#[cfg(feature = "arrow")]
+pub mod columnar;
+pub mod delimited;
+pub mod fits;
+#[cfg(feature = "plot")]
+pub mod plot;
+#[cfg(feature = "plot")]
+pub mod report;
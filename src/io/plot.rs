@@ -0,0 +1,486 @@
+//! Multi-panel review plots of a [`PeriodicityAssessment`].
+//!
+//! Five panels, in order: the raw series with one marker per pass, the
+//! search periodogram with the period and aliases marked, the spectral
+//! window from [`SamplingDiagnostics`], the detrended series folded at
+//! `period_s` (on warped time when a period drift was found) with the
+//! harmonic model, and the detrend residuals against time. The detrend is
+//! recomputed from the assessment's [`DetrendReport`] so the fold shows
+//! exactly what the search saw.
+//!
+//! [`SamplingDiagnostics`]: crate::entities::assessment::SamplingDiagnostics
+//! [`DetrendReport`]: crate::entities::assessment::DetrendReport
+
+use crate::entities::assessment::{
+    AliasKind, DetrendColumn, PeriodSearchConfig, PeriodicityAssessment, Periodogram, ScoreKind,
+    SearchScale,
+};
+use crate::entities::series::{Modality, Series, YUnit};
+use crate::functions::periodicity::detrend::{assign_passes, auto_detrend, pass_index_lists};
+use crate::functions::periodicity::fold_phase;
+use crate::functions::periodicity::pdot::warp_times;
+use bland::{Figure, Marker, PanelGridOpts, Stroke};
+use nalgebra::{DMatrix, DVector};
+use std::path::Path;
+
+/// Passes beyond this many share one legend entry.
+const MAX_PASS_LABELS: usize = 8;
+/// Strongest window peaks marked on the spectral-window panel.
+const MAX_WINDOW_MARKS: usize = 6;
+/// Model samples across one cycle of the fold panel.
+const N_MODEL: usize = 200;
+const PANEL_W: f64 = 640.0;
+const PANEL_H: f64 = 400.0;
+
+#[derive(Debug)]
+pub enum PlotError {
+    Io(std::io::Error),
+    /// The composed SVG could not be parsed for rasterization.
+    Svg(String),
+    Png(String),
+}
+
+impl std::fmt::Display for PlotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotError::Io(e) => write!(f, "I/O error: {e}"),
+            PlotError::Svg(e) => write!(f, "SVG error: {e}"),
+            PlotError::Png(e) => write!(f, "PNG error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+impl From<std::io::Error> for PlotError {
+    fn from(e: std::io::Error) -> Self {
+        PlotError::Io(e)
+    }
+}
+
+/// The five report panels as separate figures.
+pub fn assessment_figures(
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+) -> Vec<Figure> {
+    let t = series.t_s();
+    let t0 = t.first().copied().unwrap_or(0.0);
+    let span = series.span_s().unwrap_or(0.0);
+    let (t_div, t_label) = time_axis(span);
+    let y_label = y_label(series.meta().y_unit);
+    let pass_of = assign_passes(t, &assessment.sampling.passes);
+    let n_pass = assessment.sampling.passes.len().max(1);
+
+    // Raw series, one marker per pass.
+    let mut raw = panel("Raw series").xlabel(t_label).ylabel(y_label);
+    for p in 0..n_pass {
+        let (xs, ys): (Vec<f64>, Vec<f64>) = (0..t.len())
+            .filter(|&i| pass_of[i] == p)
+            .map(|i| ((t[i] - t0) / t_div, series.y()[i]))
+            .unzip();
+        raw = raw.scatter(&xs, &ys, |s| {
+            let s = s.marker(Marker::cycle(p)).marker_size(3.0);
+            if n_pass <= MAX_PASS_LABELS {
+                s.label(format!("pass {}", p + 1))
+            } else {
+                s
+            }
+        });
+    }
+    if n_pass > 1 && n_pass <= MAX_PASS_LABELS {
+        raw = raw.legend_top_right();
+    }
+
+    let periodogram = periodogram_panel(assessment);
+    let window = window_panel(assessment);
+
+    // Detrended points, restricted to the pass an intra-pass search used.
+    let idx: Option<Vec<usize>> =
+        match (assessment.detrend.scale, assessment.detrend.columns.first()) {
+            (SearchScale::IntraPass, Some(DetrendColumn::PassMean { pass })) => {
+                pass_index_lists(t, &assessment.sampling.passes)
+                    .get(*pass)
+                    .cloned()
+            }
+            _ => None,
+        };
+    let dt = auto_detrend(
+        series,
+        &assessment.sampling.passes,
+        assessment.detrend.scale,
+        assessment.detrend.mode,
+        config.phase_function,
+        idx.as_deref(),
+    );
+    let idx: Vec<usize> = idx.unwrap_or_else(|| (0..t.len()).collect());
+    let weights = series.weights();
+    let w: Vec<f64> = idx.iter().map(|&i| weights[i]).collect();
+
+    let mut fold = panel("Phase fold").xlabel("phase").ylabel(y_label);
+    match assessment.period_s.filter(|p| p.is_finite() && *p > 0.0) {
+        Some(period) if !dt.y.is_empty() => {
+            // With a period drift, fold on the warped time the search used.
+            let tf: Vec<f64> = idx.iter().map(|&i| t[i]).collect();
+            let tf = match &assessment.drift {
+                Some(d) => warp_times(&tf, d.epoch_s, d.pdot / period),
+                None => tf,
+            };
+            let phase: Vec<f64> = tf.iter().map(|&x| fold_phase(x, period)).collect();
+            let h = config
+                .n_harmonics
+                .unwrap_or(if series.meta().modality == Modality::RfPower {
+                    1
+                } else {
+                    2
+                });
+            let subtitle = match &assessment.drift {
+                Some(d) => format!("P = {period:.6} s, Ṗ = {:.3e}", d.pdot),
+                None => format!("P = {:.6} s", period),
+            };
+            fold = fold.subtitle(subtitle).scatter(&phase, &dt.y, |s| {
+                s.label("detrended")
+                    .marker(Marker::CircleFilled)
+                    .marker_size(2.5)
+            });
+            if let Some(coeffs) = harmonic_fit(&phase, &dt.y, &w, h) {
+                let xs: Vec<f64> = (0..=N_MODEL).map(|k| k as f64 / N_MODEL as f64).collect();
+                let ys: Vec<f64> = xs.iter().map(|&x| harmonic_eval(&coeffs, x)).collect();
+                fold = fold
+                    .line(&xs, &ys, |s| {
+                        s.label(format!("H = {h} model")).stroke_width(1.5)
+                    })
+                    .legend_top_right();
+            }
+        }
+        _ => fold = fold.subtitle("no period to fold"),
+    }
+
+    let (xs, ys): (Vec<f64>, Vec<f64>) = idx
+        .iter()
+        .zip(&dt.y)
+        .map(|(&i, &y)| ((t[i] - t0) / t_div, y))
+        .unzip();
+    let mut resid = panel("Detrend residuals")
+        .xlabel(t_label)
+        .ylabel(y_label)
+        .scatter(&xs, &ys, |s| s.marker(Marker::CircleOpen).marker_size(2.5))
+        .hline(0.0, |s| s.stroke(Stroke::Dotted));
+    if !assessment.detrend.columns.is_empty() {
+        resid = resid.subtitle(format!(
+            "{} columns, cond = {:.3e}",
+            assessment.detrend.columns.len(),
+            assessment.detrend.cond
+        ));
+    }
+
+    vec![raw, periodogram, window, fold, resid]
+}
+
+/// The report as one SVG document, two panels per row.
+pub fn assessment_svg(
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+) -> String {
+    let title = format!(
+        "{} — {:?}",
+        series.meta().label.as_deref().unwrap_or("series"),
+        assessment.decision
+    );
+    bland::multi_panel(
+        &assessment_figures(series, assessment, config),
+        PanelGridOpts::default()
+            .columns(2)
+            .cell_size(PANEL_W, PANEL_H)
+            .gap(12.0)
+            .title(title),
+    )
+}
+
+/// The [`assessment_svg`] report rasterized to PNG at `scale` × its SVG size.
+pub fn assessment_png(
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+    scale: f32,
+) -> Result<Vec<u8>, PlotError> {
+    svg_to_png(&assessment_svg(series, assessment, config), scale)
+}
+
+/// Write the report to `path`; `.png` rasterizes at 2×, anything else is SVG.
+pub fn write_assessment_plot(
+    path: impl AsRef<Path>,
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+) -> Result<(), PlotError> {
+    let path = path.as_ref();
+    let png = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    if png {
+        std::fs::write(path, assessment_png(series, assessment, config, 2.0)?)?;
+    } else {
+        std::fs::write(path, assessment_svg(series, assessment, config))?;
+    }
+    Ok(())
+}
+
+fn svg_to_png(svg: &str, scale: f32) -> Result<Vec<u8>, PlotError> {
+    use resvg::{tiny_skia, usvg};
+    let mut opt = usvg::Options::default();
+    opt.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(svg, &opt).map_err(|e| PlotError::Svg(e.to_string()))?;
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(scale)
+        .ok_or_else(|| PlotError::Png(format!("scale {scale} gives an empty image")))?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| PlotError::Png("pixmap allocation failed".into()))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|e| PlotError::Png(e.to_string()))
+}
+
+fn panel(title: &str) -> Figure {
+    Figure::new().dimensions(PANEL_W, PANEL_H).title(title)
+}
+
+fn periodogram_panel(a: &PeriodicityAssessment) -> Figure {
+    let pg = &a.periodogram;
+    let mut fig = periodogram_figure(pg, score_title(pg.score_kind));
+    if let Some(p) = a.period_s.filter(|p| p.is_finite() && *p > 0.0) {
+        fig = fig.vline(p, |s| {
+            s.label(format!("P = {p:.4} s")).stroke(Stroke::Solid)
+        });
+    }
+    let mut labelled = (false, false);
+    for alias in a.aliases.iter().filter(|x| x.period_s > 0.0) {
+        let (stroke, seen, label) = if alias.vetoed {
+            (Stroke::Dotted, &mut labelled.1, "vetoed alias")
+        } else {
+            (Stroke::Dashed, &mut labelled.0, "alias")
+        };
+        let first = !std::mem::replace(seen, true);
+        fig = fig.vline(alias.period_s, |s| {
+            let s = s.stroke(stroke);
+            if first { s.label(label) } else { s }
+        });
+    }
+    if pg.is_empty() {
+        fig.subtitle("no periodogram")
+    } else {
+        fig.legend_top_right()
+    }
+}
+
+fn window_panel(a: &PeriodicityAssessment) -> Figure {
+    let sw = &a.sampling.spectral_window;
+    let mut fig = periodogram_figure(sw, "Spectral window");
+    let (lo, hi) = sw
+        .period_s
+        .iter()
+        .fold((f64::INFINITY, 0.0_f64), |(lo, hi), &p| {
+            (lo.min(p), hi.max(p))
+        });
+    let mut peaks: Vec<_> = a
+        .sampling
+        .window_peaks
+        .iter()
+        .filter(|x| x.period_s >= lo && x.period_s <= hi)
+        .collect();
+    peaks.sort_by(|x, y| y.power.total_cmp(&x.power));
+    for (k, peak) in peaks.into_iter().take(MAX_WINDOW_MARKS).enumerate() {
+        fig = fig.vline(peak.period_s, |s| {
+            let s = s.stroke(Stroke::Dashed);
+            if k == 0 { s.label("window peak") } else { s }
+        });
+        if peak.kind != AliasKind::WindowPeak {
+            fig = fig.annotate_text(
+                peak.period_s,
+                peak.power,
+                format!("{:?}", peak.kind),
+                bland::TextAnchor::Start,
+            );
+        }
+    }
+    if sw.is_empty() {
+        fig.subtitle("no spectral window")
+    } else {
+        fig
+    }
+}
+
+fn periodogram_figure(pg: &Periodogram, title: &str) -> Figure {
+    let (xs, ys): (Vec<f64>, Vec<f64>) = pg
+        .period_s
+        .iter()
+        .zip(&pg.score)
+        .filter(|(p, s)| **p > 0.0 && s.is_finite())
+        .map(|(p, s)| (*p, *s))
+        .unzip();
+    let fig = panel(title)
+        .xlabel("period [s]")
+        .ylabel(score_label(pg.score_kind));
+    if xs.is_empty() {
+        return fig;
+    }
+    fig.xlog().line(&xs, &ys, |s| s.stroke_width(1.0))
+}
+
+fn score_title(kind: ScoreKind) -> &'static str {
+    match kind {
+        ScoreKind::GlsPower => "GLS periodogram",
+        ScoreKind::PdmTheta => "PDM periodogram",
+        ScoreKind::LogOdds => "Log-odds periodogram",
+        ScoreKind::StringLengthRatio => "String-length periodogram",
+        ScoreKind::ConditionalEntropy => "Conditional-entropy periodogram",
+        ScoreKind::LaflerKinmanTheta => "Lafler–Kinman periodogram",
+        ScoreKind::BlsPower => "BLS glint periodogram",
+        ScoreKind::SpectralWindow => "Spectral window",
+    }
+}
+
+fn score_label(kind: ScoreKind) -> &'static str {
+    match kind {
+        ScoreKind::GlsPower => "power",
+        ScoreKind::PdmTheta => "θ",
+        ScoreKind::LogOdds => "log odds",
+        ScoreKind::StringLengthRatio => "length ratio",
+        ScoreKind::ConditionalEntropy => "H(m|φ) (nats)",
+        ScoreKind::LaflerKinmanTheta => "Θ_LK",
+        ScoreKind::BlsPower => "box power",
+        ScoreKind::SpectralWindow => "W(f)",
+    }
+}
+
+fn y_label(unit: YUnit) -> &'static str {
+    match unit {
+        YUnit::Magnitude => "magnitude",
+        YUnit::Decibels => "power [dB]",
+        YUnit::LinearPower => "power",
+        YUnit::Dimensionless => "y",
+    }
+}
+
+/// `(divisor, label)` for a time axis of `span_s` seconds.
+fn time_axis(span_s: f64) -> (f64, &'static str) {
+    if span_s > 2.0 * 86_400.0 {
+        (86_400.0, "t − t₀ [d]")
+    } else if span_s > 2.0 * 3_600.0 {
+        (3_600.0, "t − t₀ [h]")
+    } else {
+        (1.0, "t − t₀ [s]")
+    }
+}
+
+/// Weighted least-squares Fourier series in phase with `h` harmonics:
+/// `[c, a₁, b₁, …, a_h, b_h]`. `None` if under-determined or singular.
+fn harmonic_fit(phase: &[f64], y: &[f64], w: &[f64], h: usize) -> Option<Vec<f64>> {
+    let k = 1 + 2 * h;
+    if phase.len() <= k {
+        return None;
+    }
+    let mut x = DMatrix::<f64>::zeros(phase.len(), k);
+    let mut rhs = DVector::<f64>::zeros(phase.len());
+    for (i, (&p, &yi)) in phase.iter().zip(y).enumerate() {
+        let sw = w[i].max(0.0).sqrt();
+        x[(i, 0)] = sw;
+        for j in 1..=h {
+            let (s, c) = (std::f64::consts::TAU * j as f64 * p).sin_cos();
+            x[(i, 2 * j - 1)] = sw * c;
+            x[(i, 2 * j)] = sw * s;
+        }
+        rhs[i] = sw * yi;
+    }
+    let beta = x.svd(true, true).solve(&rhs, 1e-12).ok()?;
+    beta.iter()
+        .all(|b| b.is_finite())
+        .then(|| beta.iter().copied().collect())
+}
+
+fn harmonic_eval(coeffs: &[f64], phase: f64) -> f64 {
+    let mut v = coeffs[0];
+    for j in 1..=coeffs.len() / 2 {
+        let (s, c) = (std::f64::consts::TAU * j as f64 * phase).sin_cos();
+        v += coeffs[2 * j - 1] * c + coeffs[2 * j] * s;
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::series::{Covariates, SeriesMeta, SigmaSpec, TimeFrame};
+    use crate::functions::periodicity::assess_periodicity;
+    use crate::functions::time_scale::TimeScale;
+
+    fn sine_series() -> Series {
+        let mut t = Vec::new();
+        for pass in 0..3 {
+            for i in 0..120 {
+                t.push(pass as f64 * 5_000.0 + i as f64 * 7.3);
+            }
+        }
+        let y = t
+            .iter()
+            .map(|&ti: &f64| 8.0 + 0.4 * (std::f64::consts::TAU * ti / 47.3).sin())
+            .collect();
+        Series::try_new(
+            t,
+            y,
+            SigmaSpec::Homoscedastic(0.02),
+            Covariates::default(),
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: Some("test <sine>".into()),
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn report_has_five_panels_and_rasterizes() {
+        let series = sine_series();
+        let config = PeriodSearchConfig::default();
+        let a = assess_periodicity(&series, &config);
+        assert_eq!(assessment_figures(&series, &a, &config).len(), 5);
+        let svg = assessment_svg(&series, &a, &config);
+        assert!(svg.starts_with("<svg") || svg.starts_with("<?xml"));
+        // Panel titles may be recased by the theme.
+        let lower = svg.to_lowercase();
+        assert!(lower.contains("phase fold") && lower.contains("spectral window"));
+        assert!(svg.contains("test &lt;sine&gt;"));
+        let png = assessment_png(&series, &a, &config, 0.5).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn harmonic_fit_recovers_coefficients() {
+        let phase: Vec<f64> = (0..50).map(|i| i as f64 / 50.0).collect();
+        let y: Vec<f64> = phase
+            .iter()
+            .map(|&p| {
+                1.0 + 0.5 * (std::f64::consts::TAU * p).cos()
+                    - 0.2 * (2.0 * std::f64::consts::TAU * p).sin()
+            })
+            .collect();
+        let c = harmonic_fit(&phase, &y, &vec![1.0; 50], 2).unwrap();
+        for (got, want) in c.iter().zip([1.0, 0.5, 0.0, 0.0, -0.2]) {
+            assert!((got - want).abs() < 1e-9, "{c:?}");
+        }
+        assert!((harmonic_eval(&c, 0.3) - y[15]).abs() < 1e-9);
+        assert!(harmonic_fit(&phase[..4], &y[..4], &[1.0; 4], 2).is_none());
+    }
+}
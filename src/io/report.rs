@@ -0,0 +1,664 @@
+//! Self-contained HTML review report for a [`PeriodicityAssessment`].
+//!
+//! One file, no scripts and no network assets: the [`plot`](super::plot)
+//! panels are inlined as SVG and every table is plain HTML. The report
+//! walks through each gate of the decision — FAP tests, peak position,
+//! alias and eclipse vetoes, method agreement — followed by the detrend
+//! design, sampling summary and the raw `notes`.
+
+use super::plot::assessment_svg;
+use crate::constants::SOLAR_DAY_S;
+use crate::entities::assessment::{
+    Alias, AliasKind, DetrendColumn, MethodId, PabSource, PeriodSearchConfig, PeriodicityAssessment,
+};
+use crate::entities::series::Series;
+use crate::functions::periodicity::assess::BALUEV_SKIP;
+use crate::functions::periodicity::detrend::{COND_DROP, COND_FAIL};
+use std::fmt::Write;
+use std::path::Path;
+
+const STYLE: &str = "body{font-family:Georgia,'Times New Roman',serif;max-width:1400px;\
+margin:2em auto;padding:0 1em;color:#111}\
+h1{margin-bottom:.2em}h2{border-bottom:1px solid #999;margin-top:1.6em}\
+table{border-collapse:collapse;margin:.5em 0}\
+th,td{border:1px solid #bbb;padding:.25em .6em;text-align:left;vertical-align:top}\
+th{background:#eee}td.num{text-align:right;font-family:monospace}\
+.pass{color:#165c16}.fail{color:#a01010;font-weight:bold}.skip{color:#777}\
+.decision{font-size:1.3em}svg{max-width:100%;height:auto}";
+
+#[derive(Clone, Copy)]
+enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Status {
+    fn of(ok: bool) -> Self {
+        if ok { Status::Pass } else { Status::Fail }
+    }
+
+    fn cell(self) -> &'static str {
+        match self {
+            Status::Pass => "<td class=\"pass\">pass</td>",
+            Status::Fail => "<td class=\"fail\">fail</td>",
+            Status::Skip => "<td class=\"skip\">not run</td>",
+        }
+    }
+}
+
+/// The report as a complete HTML document.
+pub fn assessment_html(
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+) -> String {
+    let a = assessment;
+    let label = series.meta().label.as_deref().unwrap_or("series");
+    let mut h = String::with_capacity(64 * 1024);
+    let _ = write!(
+        h,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<title>{} — periodicity assessment</title>\n<style>{STYLE}</style>\n</head>\n<body>\n",
+        escape(label)
+    );
+    let _ = writeln!(h, "<h1>{}</h1>", escape(label));
+    let _ = write!(h, "<p class=\"decision\">Decision: <b>{:?}</b>", a.decision);
+    if let Some(p) = a.period_s {
+        let _ = write!(h, " at P = {p:.6} s");
+        if let Some(u) = a.period_unc_s {
+            let _ = write!(h, " ± {u:.2e} s");
+        }
+    }
+    let _ = writeln!(
+        h,
+        " — {:?} score {} ({:?})</p>",
+        a.method,
+        num(a.score),
+        a.score_kind
+    );
+
+    decision_section(&mut h, a, config);
+    fap_section(&mut h, a, config);
+    alias_section(&mut h, a, config);
+    agreement_section(&mut h, a, config);
+    sidereal_section(&mut h, a);
+    drift_section(&mut h, a);
+    glint_section(&mut h, a);
+    detrend_section(&mut h, a);
+    sampling_section(&mut h, a);
+
+    h.push_str("<h2>Notes</h2>\n");
+    if a.notes.is_empty() {
+        h.push_str("<p>None.</p>\n");
+    } else {
+        h.push_str("<ul>\n");
+        for n in &a.notes {
+            let _ = writeln!(h, "<li>{}</li>", escape(n));
+        }
+        h.push_str("</ul>\n");
+    }
+
+    h.push_str("<h2>Figures</h2>\n<figure>\n");
+    let svg = assessment_svg(series, a, config);
+    let svg = match svg.find("<svg") {
+        Some(i) => &svg[i..],
+        None => svg.as_str(),
+    };
+    h.push_str(svg);
+    h.push_str(
+        "\n<figcaption>Raw series by pass; search periodogram with the period \
+(solid), aliases (dashed) and vetoed aliases (dotted); spectral window; phase \
+fold with the harmonic model; detrend residuals.</figcaption>\n</figure>\n",
+    );
+    h.push_str("</body>\n</html>\n");
+    h
+}
+
+/// Write [`assessment_html`] to `path`.
+pub fn write_assessment_html(
+    path: impl AsRef<Path>,
+    series: &Series,
+    assessment: &PeriodicityAssessment,
+    config: &PeriodSearchConfig,
+) -> std::io::Result<()> {
+    std::fs::write(path, assessment_html(series, assessment, config))
+}
+
+fn decision_section(h: &mut String, a: &PeriodicityAssessment, config: &PeriodSearchConfig) {
+    let q = &a.quality;
+    h.push_str(
+        "<h2>Decision gates</h2>\n<table>\n<tr><th>Gate</th><th>Result</th><th>Detail</th></tr>\n",
+    );
+    let fap = match a.fap {
+        Some(f) => (
+            Status::of(f < config.fap_threshold),
+            format!(
+                "FAP {} against threshold {}",
+                num(f),
+                num(config.fap_threshold)
+            ),
+        ),
+        None => (Status::Skip, "no FAP computed".into()),
+    };
+    row(h, "False-alarm probability", fap.0, &fap.1);
+    row(
+        h,
+        "Interior peak",
+        Status::of(!q.bound_snap),
+        if q.bound_snap {
+            "peak sits on the edge of the period grid (bound-snap)"
+        } else {
+            "peak is an interior maximum of the periodogram"
+        },
+    );
+    let n_window = a
+        .aliases
+        .iter()
+        .filter(|al| al.vetoed && al.kind != AliasKind::EclipseIngress)
+        .count();
+    let window = if !config.window_veto {
+        (Status::Skip, "window veto disabled".to_string())
+    } else if q.window_contaminated {
+        (
+            Status::Fail,
+            format!("{n_window} alias(es) vetoed the peak; see Aliases"),
+        )
+    } else {
+        (
+            Status::Pass,
+            "peak is not a sampling alias of the spectral window".into(),
+        )
+    };
+    row(h, "Window / alias veto", window.0, &window.1);
+    match a
+        .aliases
+        .iter()
+        .find(|al| al.kind == AliasKind::EclipseIngress)
+    {
+        Some(al) => row(
+            h,
+            "Eclipse veto",
+            Status::of(!al.vetoed),
+            &alias_reason(al, config),
+        ),
+        None => row(
+            h,
+            "Eclipse veto",
+            Status::Skip,
+            "no shadowed points in scope",
+        ),
+    }
+    let agreement = match q.estimator_agreement {
+        Some(true) => (Status::Pass, "confirming estimator agrees"),
+        Some(false) if config.require_method_agreement => (
+            Status::Fail,
+            "confirming estimator disagrees and agreement is required",
+        ),
+        Some(false) => (
+            Status::Pass,
+            "confirming estimator disagrees; agreement not required",
+        ),
+        None => (Status::Skip, "no confirming estimator result"),
+    };
+    row(h, "Method agreement", agreement.0, agreement.1);
+    row(
+        h,
+        "Sampling",
+        Status::of(!q.undersampled),
+        if q.undersampled {
+            "too few passes to resolve the inter-pass period"
+        } else {
+            "sampling supports the searched range"
+        },
+    );
+    h.push_str("</table>\n");
+}
+
+fn fap_section(h: &mut String, a: &PeriodicityAssessment, config: &PeriodSearchConfig) {
+    let b = config.n_permutations;
+    let floor = 1.0 / (b + 1) as f64;
+    let _ = writeln!(
+        h,
+        "<h2>False-alarm tests</h2>\n<p>Threshold {}; {b} permutations \
+(permutation FAP floor {}).</p>\n<table>\n\
+<tr><th>Test</th><th>FAP</th><th>Result</th><th>Criterion</th></tr>",
+        num(config.fap_threshold),
+        num(floor)
+    );
+    let tests: [(&str, Option<f64>, bool, String); 6] = [
+        (
+            "Look-elsewhere",
+            a.fap,
+            a.fap.is_some_and(|f| f < config.fap_threshold),
+            if a.method == MethodId::Pdm {
+                "PDM beta law over the searched band, below threshold".into()
+            } else if a.method == MethodId::Bls {
+                "BLS box beta law over frequencies and box positions, below threshold".into()
+            } else {
+                format!(
+                    "{:?} over the searched band, below threshold",
+                    config.fap_mode
+                )
+            },
+        ),
+        (
+            "Baluev (H = 1)",
+            a.fap_baluev,
+            a.fap_baluev.is_some_and(|f| f < config.fap_threshold),
+            format!(
+                "analytic bound below threshold; above {BALUEV_SKIP} the permutation tests are skipped"
+            ),
+        ),
+        (
+            "PDM θ (beta law)",
+            a.fap_pdm,
+            a.fap_pdm.is_some_and(|f| f < config.fap_threshold),
+            "lowest θ against white noise over the independent frequencies".into(),
+        ),
+        (
+            "BLS glint box",
+            a.fap_bls,
+            a.fap_bls.is_some_and(|f| f < config.fap_threshold),
+            "best brightening box against white noise over frequencies and box positions".into(),
+        ),
+        (
+            "Local permutation",
+            a.fap_perm,
+            a.fap_perm.is_some_and(|f| f <= floor * 1.5),
+            "no shuffled series beats the data at P, 2P or P/2".into(),
+        ),
+        (
+            "Pass-block permutation",
+            a.fap_block,
+            a.fap_block.is_some_and(|f| f <= floor * 1.5),
+            "no pass-shuffled series beats the data at P (≥ 3 passes, inter-pass scale)".into(),
+        ),
+    ];
+    for (name, fap, ok, criterion) in tests {
+        let status = if fap.is_some() {
+            Status::of(ok)
+        } else {
+            Status::Skip
+        };
+        let _ = writeln!(
+            h,
+            "<tr><td>{name}</td><td class=\"num\">{}</td>{}<td>{}</td></tr>",
+            fap.map(num).unwrap_or_else(|| "—".into()),
+            status.cell(),
+            escape(&criterion)
+        );
+    }
+    h.push_str("</table>\n");
+}
+
+fn alias_section(h: &mut String, a: &PeriodicityAssessment, config: &PeriodSearchConfig) {
+    h.push_str("<h2>Aliases</h2>\n");
+    if a.aliases.is_empty() {
+        h.push_str("<p>No window peak or eclipse check near the detected period.</p>\n");
+        return;
+    }
+    h.push_str(
+        "<table>\n<tr><th>Kind</th><th>Period [s]</th><th>Score</th>\
+<th>|ΔP| / P</th><th>Vetoed</th><th>Why</th></tr>\n",
+    );
+    for al in &a.aliases {
+        let _ = writeln!(
+            h,
+            "<tr><td>{:?}</td><td class=\"num\">{:.6}</td><td class=\"num\">{}</td>\
+<td class=\"num\">{}</td><td>{}</td><td>{}</td></tr>",
+            al.kind,
+            al.period_s,
+            num(al.score),
+            num(al.relative_delta),
+            if al.vetoed { "yes" } else { "no" },
+            escape(&alias_reason(al, config))
+        );
+    }
+    h.push_str("</table>\n");
+}
+
+fn agreement_section(h: &mut String, a: &PeriodicityAssessment, config: &PeriodSearchConfig) {
+    let _ = writeln!(
+        h,
+        "<h2>Method agreement</h2>\n<p>Methods {:?}; agreement {}.</p>",
+        config.methods,
+        if config.require_method_agreement {
+            "required"
+        } else {
+            "not required"
+        }
+    );
+    match &a.confirmation {
+        Some(c) => {
+            let _ = writeln!(
+                h,
+                "<table>\n<tr><th>Method</th><th>Period [s]</th><th>Score</th><th>Agrees</th></tr>\n\
+<tr><td>{:?}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>—</td></tr>\n\
+<tr><td>{:?}</td><td class=\"num\">{:.6}</td><td class=\"num\">{}</td><td>{}</td></tr>\n</table>",
+                a.method,
+                a.period_s
+                    .map(|p| format!("{p:.6}"))
+                    .unwrap_or_else(|| "—".into()),
+                num(a.score),
+                c.method,
+                c.period_s,
+                num(c.score),
+                if c.agrees { "yes" } else { "no" }
+            );
+        }
+        None => h.push_str("<p>No confirming estimator ran.</p>\n"),
+    }
+}
+
+fn sidereal_section(h: &mut String, a: &PeriodicityAssessment) {
+    let Some(c) = &a.sidereal else {
+        return;
+    };
+    let _ = writeln!(
+        h,
+        "<h2>Sidereal period</h2>
+<p>Phase-angle bisector from {}, turning at {} rad/s within passes{}. Synodic − sidereal = {} ± {} s for prograde spin about the bisector's pole.</p>
+<table>
+<tr><th>Spin</th><th>Sidereal period [s]</th></tr>
+<tr><td>Prograde</td><td class=\"num\">{:.6} ± {}</td></tr>
+<tr><td>Retrograde</td><td class=\"num\">{:.6}</td></tr>
+</table>",
+        match c.source {
+            PabSource::Vectors => "target and Sun vectors",
+            PabSource::PhaseAngle => "the solar phase angle",
+        },
+        num(c.pab_rate_rad_s),
+        if c.source == PabSource::PhaseAngle {
+            " (a lower bound)"
+        } else {
+            ""
+        },
+        num(c.delta_s),
+        num(c.delta_unc_s),
+        c.period_s,
+        num(c.period_unc_s),
+        c.retrograde_period_s
+    );
+}
+
+fn drift_section(h: &mut String, a: &PeriodicityAssessment) {
+    let Some(d) = &a.drift else {
+        return;
+    };
+    let _ = writeln!(
+        h,
+        "<h2>Period drift</h2>
+<p>Ṗ = {} ± {} s/s ({} s/day); the period above holds at t = {:.0} s and drifts linearly across the span.</p>",
+        num(d.pdot),
+        d.pdot_unc.map(num).unwrap_or_else(|| "—".into()),
+        num(d.pdot * SOLAR_DAY_S),
+        d.epoch_s
+    );
+}
+
+fn glint_section(h: &mut String, a: &PeriodicityAssessment) {
+    let Some(g) = &a.glint else {
+        return;
+    };
+    let _ = writeln!(
+        h,
+        "<h2>Glints</h2>
+<p>Box of {} s ({} of the cycle) at P = {:.6} s, first at t = {:.3} s; depth {} (brighter positive), \
+box power {}. {} flash(es) observed.</p>",
+        num(g.width_s),
+        num(g.duty),
+        g.period_s,
+        g.epoch_s,
+        num(g.depth),
+        num(g.power),
+        g.events.len()
+    );
+    if g.events.is_empty() {
+        return;
+    }
+    h.push_str("<table>\n<tr><th>t [s]</th><th>Lit spread [s]</th><th>Amplitude</th></tr>\n");
+    for e in &g.events {
+        let _ = writeln!(
+            h,
+            "<tr><td class=\"num\">{:.3}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            e.t_s,
+            num(e.width_s),
+            num(e.amplitude)
+        );
+    }
+    h.push_str("</table>\n");
+}
+
+fn detrend_section(h: &mut String, a: &PeriodicityAssessment) {
+    let d = &a.detrend;
+    let _ = writeln!(
+        h,
+        "<h2>Detrend</h2>\n<p>Mode {:?} at {:?} scale; {} column(s); condition number {} \
+(columns are dropped above {}, the fit falls back to a mean above {}).{}</p>",
+        d.mode,
+        d.scale,
+        d.columns.len(),
+        num(d.cond),
+        num(COND_DROP),
+        num(COND_FAIL),
+        if d.fallback {
+            " <b class=\"fail\">Fallback:</b> the design was ill-conditioned and only the mean was removed."
+        } else {
+            ""
+        }
+    );
+    if !d.columns.is_empty() {
+        h.push_str("<table>\n<tr><th>Column</th><th>Coefficient</th></tr>\n");
+        for (i, c) in d.columns.iter().enumerate() {
+            let _ = writeln!(
+                h,
+                "<tr><td>{}</td><td class=\"num\">{}</td></tr>",
+                column_name(c),
+                d.coeffs
+                    .get(i)
+                    .map(|v| num(*v))
+                    .unwrap_or_else(|| "—".into())
+            );
+        }
+        h.push_str("</table>\n");
+    }
+    for (what, offsets) in [("Band", &d.band_offsets), ("Sensor", &d.sensor_offsets)] {
+        if offsets.is_empty() {
+            continue;
+        }
+        let _ = write!(h, "<p>{what} offsets:");
+        for (k, v) in offsets {
+            let _ = write!(h, " {k}: {}", num(*v));
+        }
+        h.push_str("</p>\n");
+    }
+}
+
+fn sampling_section(h: &mut String, a: &PeriodicityAssessment) {
+    let s = &a.sampling;
+    let _ = writeln!(
+        h,
+        "<h2>Sampling</h2>\n<table>\n\
+<tr><td>Points</td><td class=\"num\">{} ({} merged duplicates)</td></tr>\n\
+<tr><td>Passes</td><td class=\"num\">{}</td></tr>\n\
+<tr><td>Span [s]</td><td class=\"num\">{}</td></tr>\n\
+<tr><td>Duty cycle</td><td class=\"num\">{:.3}</td></tr>\n\
+<tr><td>Median Δt [s]</td><td class=\"num\">{}</td></tr>\n\
+<tr><td>Searchable period [s]</td><td class=\"num\">{} – {}</td></tr>\n\
+<tr><td>Window contaminated</td><td>{}</td></tr>\n</table>",
+        s.n,
+        s.n_merged_duplicates,
+        s.n_passes,
+        num(s.span_s),
+        s.duty_cycle,
+        num(s.median_dt_s),
+        num(s.min_searchable_period_s),
+        num(s.max_searchable_period_s),
+        if a.quality.window_contaminated {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+}
+
+fn row(h: &mut String, gate: &str, status: Status, detail: &str) {
+    let _ = writeln!(
+        h,
+        "<tr><td>{gate}</td>{}<td>{}</td></tr>",
+        status.cell(),
+        escape(detail)
+    );
+}
+
+/// Why `al` was (or was not) vetoed, following the window and eclipse checks
+/// in the search.
+fn alias_reason(al: &Alias, config: &PeriodSearchConfig) -> String {
+    match (al.kind, al.vetoed) {
+        (
+            AliasKind::SiderealDay
+            | AliasKind::SolarDay
+            | AliasKind::Orbital
+            | AliasKind::OrbitalHalf
+            | AliasKind::PassCadence,
+            true,
+        ) => "named sampling alias within the frequency resolution of P".into(),
+        (
+            AliasKind::SiderealDay
+            | AliasKind::SolarDay
+            | AliasKind::Orbital
+            | AliasKind::OrbitalHalf
+            | AliasKind::PassCadence,
+            false,
+        ) => "named sampling alias, overridden: H=1 residual at P is still significant".into(),
+        (AliasKind::WindowPeak, true) => format!(
+            "data power at P is under {} × the spectral window",
+            config.window_ratio
+        ),
+        (AliasKind::WindowPeak, false) => format!(
+            "data power exceeds {} × the spectral window, or the H=1 residual is significant",
+            config.window_ratio
+        ),
+        (AliasKind::EclipseIngress, true) => {
+            "power is not significant on the fully lit points alone".into()
+        }
+        (AliasKind::EclipseIngress, false) => {
+            "power stays significant on the fully lit points alone".into()
+        }
+        (_, true) => "vetoed".into(),
+        (_, false) => "informational; not a veto check".into(),
+    }
+}
+
+fn column_name(c: &DetrendColumn) -> String {
+    match c {
+        DetrendColumn::PassMean { pass } => format!("pass mean (pass {})", pass + 1),
+        DetrendColumn::GlobalMean => "global mean".into(),
+        DetrendColumn::Tau => "τ (time within pass)".into(),
+        DetrendColumn::PhaseLambert => "phase (Lambert)".into(),
+        DetrendColumn::PhaseFunction => "phase function".into(),
+        DetrendColumn::Elevation => "elevation".into(),
+        DetrendColumn::GlobalTime => "global time".into(),
+        DetrendColumn::Illumination => "−2.5 log₁₀ illumination".into(),
+        DetrendColumn::BandOffset { band } => format!("band offset (key {band})"),
+        DetrendColumn::SensorOffset { key } => format!("sensor offset (key {key})"),
+    }
+}
+
+fn num(v: f64) -> String {
+    if !v.is_finite() {
+        format!("{v}")
+    } else if v == 0.0 || (1e-3..1e4).contains(&v.abs()) {
+        format!("{v:.4}")
+    } else {
+        format!("{v:.3e}")
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assessment::PeriodicityDecision;
+    use crate::entities::series::{Covariates, Modality, SeriesMeta, SigmaSpec, TimeFrame, YUnit};
+    use crate::functions::periodicity::assess_periodicity;
+    use crate::functions::time_scale::TimeScale;
+
+    fn series(label: &str) -> Series {
+        let t: Vec<f64> = (0..200).map(|i| i as f64 * 6.1).collect();
+        let y = t
+            .iter()
+            .map(|&ti| 8.0 + 0.3 * (std::f64::consts::TAU * ti / 61.7).sin())
+            .collect();
+        Series::try_new(
+            t,
+            y,
+            SigmaSpec::Homoscedastic(0.02),
+            Covariates::default(),
+            SeriesMeta {
+                modality: Modality::OpticalPhotometry,
+                y_unit: YUnit::Magnitude,
+                label: Some(label.into()),
+                time_scale: TimeScale::Utc,
+                time_frame: TimeFrame::Observer,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn report_is_self_contained_and_explains_gates() {
+        let s = series("R/B <1>");
+        let config = PeriodSearchConfig::default();
+        let a = assess_periodicity(&s, &config);
+        let html = assessment_html(&s, &a, &config);
+        assert!(html.starts_with("<!DOCTYPE html>") && html.ends_with("</html>\n"));
+        assert!(html.contains("<h1>R/B &lt;1&gt;</h1>"));
+        assert!(html.contains(&format!("<b>{:?}</b>", a.decision)));
+        assert!(html.contains("<svg") && !html.contains("<?xml"));
+        for gate in [
+            "False-alarm probability",
+            "Window / alias veto",
+            "Method agreement",
+        ] {
+            assert!(html.contains(gate), "missing {gate}");
+        }
+        for banned in ["<script", "<link", "src=", "@import"] {
+            assert!(!html.contains(banned), "found {banned}");
+        }
+    }
+
+    #[test]
+    fn vetoed_alias_gets_a_reason() {
+        let s = series("x");
+        let config = PeriodSearchConfig::default();
+        let mut a = PeriodicityAssessment::new(PeriodicityDecision::NotPeriodic, None);
+        a.fap = Some(0.5);
+        a.quality.window_contaminated = true;
+        a.aliases.push(Alias {
+            period_s: 86_164.1,
+            score: 0.8,
+            kind: AliasKind::SiderealDay,
+            relative_delta: 0.01,
+            vetoed: true,
+        });
+        let html = assessment_html(&s, &a, &config);
+        assert!(html.contains("named sampling alias within the frequency resolution of P"));
+        assert!(html.contains("1 alias(es) vetoed the peak"));
+        assert!(html.contains("<td class=\"fail\">fail</td>"));
+    }
+}
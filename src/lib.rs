@@ -1,6 +1,7 @@
 pub mod constants;
 pub mod entities;
 pub mod functions;
+pub mod io;
 
 pub use crate::entities::assessment::{
     PeriodSearchConfig, PeriodicityAssessment, PeriodicityDecision, SearchScale,
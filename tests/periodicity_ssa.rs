@@ -17,7 +17,12 @@ use cepheid::assess_periodicity;
 use cepheid::entities::assessment::{PeriodSearchConfig, PeriodicityDecision, SearchScale};
 use cepheid::functions::periodicity::gls::floating_mean_gls_power;
 use cepheid::functions::periodicity::window::spectral_window;
-use support::ssa_synth::{leftover_lambert, leo_week_times, series_xy, series_xy_phase};
+use cepheid::functions::periodicity::pdot::warp_times;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use support::ssa_synth::{
+    geo_week_times, leftover_lambert, leo_week_times, series_xy, series_xy_phase,
+};
 
 #[test]
 fn t_w1_window_is_not_gls_of_ones() {
@@ -79,6 +84,60 @@ fn t21_one_pass_still_works() {
     assert_eq!(a.decision, PeriodicityDecision::Periodic, "{:?}", a.notes);
 }
 
+#[test]
+fn t22_decaying_tumbler_needs_pdot() {
+    let t = geo_week_times();
+    let (t0, t1) = (t[0], t[t.len() - 1]);
+    let (epoch, half) = (0.5 * (t0 + t1), 0.5 * (t1 - t0));
+    let p0 = 1500.0;
+    // Ten cycles of phase slip at the span ends.
+    let kappa = 20.0 * p0 / (half * half);
+    let mut rng = StdRng::seed_from_u64(21);
+    let y: Vec<f64> = warp_times(&t, epoch, kappa)
+        .iter()
+        .map(|tau| {
+            0.3 * (std::f64::consts::TAU * tau / p0).sin() + rng.random::<f64>() - 0.5
+        })
+        .collect();
+    let s = series_xy(t, y);
+    let mut c = PeriodSearchConfig::conservative();
+    c.scale = SearchScale::Full;
+    c.max_period_s = Some(2000.0);
+
+    // Without Ṗ the smeared peak is not significant on its own.
+    let fixed = assess_periodicity(&s, &c);
+    assert_ne!(
+        fixed.decision,
+        PeriodicityDecision::Periodic,
+        "{:?} {:?}",
+        fixed.period_s,
+        fixed.notes
+    );
+    assert!(fixed.drift.is_none());
+
+    c.pdot.max_rel_per_day = 0.05;
+    for scale in [SearchScale::Full, SearchScale::Auto] {
+        c.scale = scale;
+        let a = assess_periodicity(&s, &c);
+        assert_eq!(
+            a.decision,
+            PeriodicityDecision::Periodic,
+            "{scale:?}: {:?}",
+            a.notes
+        );
+        let p = a.period_s.unwrap();
+        assert!((p / p0 - 1.0).abs() < 1e-3, "{scale:?}: P={p}");
+        let d = a.drift.expect("drift");
+        let unc = d.pdot_unc.expect("pdot_unc");
+        assert!(
+            (d.pdot - kappa * p0).abs() < 3.0 * unc,
+            "{d:?} vs {}",
+            kappa * p0
+        );
+        assert!(unc < 0.1 * kappa * p0);
+    }
+}
+
 /// 200-draw FPR probe. Not default CI. Prints a Clopper–Pearson 95% interval.
 #[test]
 #[ignore]
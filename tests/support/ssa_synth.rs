@@ -1,7 +1,10 @@
 //! Frozen SSA sampling generators for integration tests.
 
-use cepheid::entities::series::{Covariates, Modality, Series, SeriesMeta, SigmaSpec, YUnit};
+use cepheid::entities::series::{
+    Covariates, Modality, Series, SeriesMeta, SigmaSpec, TimeFrame, YUnit,
+};
 use cepheid::functions::sampling::{geo_night_times, leo_pass_times};
+use cepheid::functions::time_scale::TimeScale;
 
 pub const LEO_N_PASSES: usize = 18;
 pub const LEO_PTS: usize = 40;
@@ -12,7 +15,6 @@ pub fn leo_week_times() -> Vec<f64> {
     leo_pass_times(LEO_N_PASSES, LEO_PTS, LEO_PASS_LEN, LEO_ORBIT, 0.0)
 }
 
-#[allow(dead_code)]
 pub fn geo_week_times() -> Vec<f64> {
     geo_night_times(7, 80, 18_000.0, 0.0)
 }
@@ -34,6 +36,8 @@ pub fn series_xy_phase(t: Vec<f64>, y: Vec<f64>, phase: Option<Vec<f64>>) -> Ser
             modality: Modality::OpticalPhotometry,
             y_unit: YUnit::Magnitude,
             label: None,
+            time_scale: TimeScale::Utc,
+            time_frame: TimeFrame::Observer,
         },
     )
     .expect("series")